@@ -160,6 +160,7 @@ pub enum Commands {
     /// - Validate template files before using them
     /// - Test template output with sample data
     /// - Extract placeholder information from templates
+    /// - List the named templates of the template directory
     /// - Generate example templates
     ///
     /// COMPOSABILITY:
@@ -207,27 +208,328 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Find notes by approximate title, alias or ID
+    ///
+    /// Fuzzy matching tolerates missing letters and typos ("entrpy" finds
+    /// "Entropy"). Aliases come from the `aliases` list in a note's
+    /// frontmatter. Exact matches come first, then the closest ones.
+    ///
+    /// OUTPUT FORMAT:
+    /// One note per line as `ID<TAB>title<TAB>path`, or a JSON array with
+    /// scores and the name that matched.
+    ///
+    /// EXAMPLES:
+    /// zettel find entrpy                 Notes titled like "entropy"
+    /// zettel find "second law" --json    Include scores and matched alias
+    /// hx "$(zettel find -n 1 thermo | cut -f3)"   Open the best match
+    Find {
+        /// Approximate title, alias or ID (reads from stdin if not provided)
+        query: Option<String>,
+
+        /// Show at most this many matches
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List notes with content similar to a note or to text
+    ///
+    /// Notes are ranked by BM25 similarity: every word they share with the
+    /// input counts, rarer words and shorter notes weighing more. Common
+    /// English words and frontmatter are ignored.
+    ///
+    /// OUTPUT FORMAT:
+    /// Like `zettel find`, one note per line as `ID<TAB>title<TAB>path`, or
+    /// a JSON array with scores and the shared words that weighed the most.
+    ///
+    /// EXAMPLES:
+    /// zettel related 1a                    Notes similar to note 1a
+    /// zettel related --stdin < draft.md    Notes similar to a draft
+    /// zettel related 1a -n 3 --json        Top 3 with scores and shared words
+    Related {
+        /// ID of the note to compare against (reads from stdin if neither it
+        /// nor --stdin is given)
+        id: Option<String>,
+
+        /// Compare against text read from stdin instead of a note
+        #[arg(long, conflicts_with = "id")]
+        stdin: bool,
+
+        /// Show at most this many notes
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Suggest where a draft belongs in the note tree
+    ///
+    /// Proposes the notes most similar to the draft (see `zettel related`)
+    /// as parents, each with the next free child ID under it.
+    ///
+    /// OUTPUT FORMAT:
+    /// One candidate per line, best first, as
+    /// `parent ID<TAB>next child ID<TAB>parent title`.
+    ///
+    /// EXAMPLES:
+    /// zettel suggest-parent draft.md
+    /// zettel suggest-parent -n 1 < draft.md | cut -f2     Best new ID
+    /// zettel suggest-parent draft.md --json
+    SuggestParent {
+        /// Draft file to read (reads the draft from stdin if not provided)
+        file: Option<PathBuf>,
+
+        /// Show at most this many candidates
+        #[arg(short = 'n', long, default_value_t = 3)]
+        limit: usize,
+
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Find plain-text mentions of notes that could be links
+    ///
+    /// Reports where other notes mention a note's title or frontmatter
+    /// aliases (case-insensitive, whole words) without linking to it. Notes
+    /// that already link to it, headings, code and existing links are
+    /// skipped. With --link, the mentions are replaced by links formatted
+    /// per the [linking] configuration; when links show a title, the
+    /// mention as written is kept as the displayed text.
+    ///
+    /// EXAMPLES:
+    /// zettel mentions 1a                 Unlinked mentions of note 1a
+    /// zettel mentions 1a --in 3 --link   Link the mentions found in note 3
+    /// zettel mentions --all              Candidate links across the vault
+    /// zettel mentions --all --json
+    Mentions {
+        /// ID of the mentioned note (reads from stdin if neither it nor
+        /// --all is given)
+        id: Option<String>,
+
+        /// Look for mentions of every note in the vault
+        #[arg(long, conflicts_with = "id")]
+        all: bool,
+
+        /// Only report mentions in these notes (repeatable)
+        #[arg(long = "in", value_name = "ID")]
+        sources: Vec<String>,
+
+        /// Replace the reported mentions with links
+        #[arg(long)]
+        link: bool,
+
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Search notes by content or title
     ///
-    /// Simple text-based search. Future versions might add more sophisticated
-    /// search capabilities (full-text indexing, regex, etc.).
+    /// Full-text search over note titles, bodies, tags and IDs, ranked by
+    /// relevance (BM25, with title matches counting double). Uses the index
+    /// in .zettel/index/, which is created on first use and brought up to
+    /// date before every search by re-indexing files whose modification time
+    /// changed.
     ///
-    /// SEARCH STRATEGY:
-    /// 1. Search filenames first (fast)
-    /// 2. Search file content (thorough but slower)
-    /// 3. Case-insensitive substring matching
+    /// QUERY SYNTAX:
+    /// - Words: all must match (`heat entropy`); prefixes: `entrop*`
+    /// - Phrases: "exact phrase"
+    /// - Text fields: title:, body: (e.g. title:entropy, title:"second law")
+    /// - Tags and IDs: tag:physics, id:1a, id:1a*
+    /// - Tree and links: under:3c (descendants), links-to:2b
+    /// - Dates: created:>2026-01-01 (also >=, <, <=, =)
+    /// - Operators: AND, OR, -excluded / NOT excluded, (grouping)
+    ///
+    /// AND binds tighter than OR: `a b OR c` means `(a AND b) OR c`.
     ///
     /// EXAMPLES:
-    /// zettel search "machine learning"    Find notes about ML
-    /// zettel search "TODO"                Find notes with tasks
+    /// zettel search "machine learning"          Notes mentioning both words
+    /// zettel search '"machine learning"'        Notes with the exact phrase
+    /// zettel search "learn* -tag:draft"         Prefix match, excluding drafts
+    /// zettel search "under:3c links-to:2b"      Notes below 3c that link to 2b
+    /// zettel search "created:>=2026-01-01 (tag:idea OR tag:question)"
+    /// zettel search -C 2 entropy                Matching lines with 2 lines of context
+    /// zettel search --regex 'TODO|FIXME'        Lines matching a regular expression
+    /// zettel search entropy --csv > hits.csv    One row per matching line
+    /// zettel search @inbox                      Run the saved search "inbox"
+    /// zettel search @inbox --into 0             Keep its results in note 0
+    ///
+    /// SAVED SEARCHES:
+    /// Named queries live in the [searches] section of .zettel/config.toml
+    /// (`inbox = "tag:inbox -tag:done"`). --into writes a saved search's
+    /// results as links into a managed block of a note; `zettel sync searches`
+    /// refreshes every such block.
+    ///
+    /// OUTPUT:
+    /// Each note is followed by its matching lines, grep style: `N:` marks a
+    /// match and `N-` a context line. Matches are highlighted following the
+    /// `[output] color` setting. --json, --ndjson and --csv print one record
+    /// per matching line with file, line, column and snippet.
     Search {
-        /// Search query string
+        /// Search query
         ///
-        /// Currently does simple case-insensitive substring matching.
-        /// Searches both filenames and file content.
+        /// Read from stdin when omitted.
         ///
         /// TIP: Use quotes for multi-word queries: "machine learning"
         query: Option<String>,
+
+        /// Lines of context around each matching line
+        #[arg(short = 'C', long, value_name = "N", default_value_t = 0)]
+        context: usize,
+
+        /// Lines of context after each matching line (overrides -C)
+        #[arg(short = 'A', long, value_name = "N")]
+        after_context: Option<usize>,
+
+        /// Lines of context before each matching line (overrides -C)
+        #[arg(short = 'B', long, value_name = "N")]
+        before_context: Option<usize>,
+
+        /// Treat the query as a regular expression matched against lines
+        #[arg(long)]
+        regex: bool,
+
+        /// Match letter case exactly
+        #[arg(short = 's', long)]
+        case_sensitive: bool,
+
+        /// Output matches as a JSON array
+        #[arg(long, conflicts_with_all = ["ndjson", "csv"])]
+        json: bool,
+
+        /// Output matches as newline-delimited JSON, one object per line
+        #[arg(long, conflicts_with = "csv")]
+        ndjson: bool,
+
+        /// Output matches as CSV
+        #[arg(long)]
+        csv: bool,
+
+        /// List saved searches instead of searching
+        #[arg(long, conflicts_with_all = ["query", "into"])]
+        saved: bool,
+
+        /// Write the results of a saved search (@name) into this note
+        #[arg(long, value_name = "ID")]
+        into: Option<String>,
+    },
+
+    /// Manage the full-text search index
+    ///
+    /// The index in .zettel/index/ is kept up to date automatically; rebuild
+    /// it if it gets corrupted or searches return stale results.
+    ///
+    /// EXAMPLES:
+    /// zettel index rebuild          Re-index every note from scratch
+    #[cfg(feature = "search")]
+    #[command(subcommand)]
+    Index(IndexCommands),
+
+    /// Regenerate zettel-managed blocks inside notes
+    ///
+    /// Some note content is owned by zettel and delimited by markers such as
+    /// `<!-- zettel:children -->`. These commands rebuild those blocks from the
+    /// actual notes in the vault, fixing any drift after moves or deletions.
+    ///
+    /// EXAMPLES:
+    /// zettel sync children          Rebuild children blocks vault-wide
+    /// zettel sync children 1a       Rebuild only the block in note 1a
+    /// zettel sync nav               Rebuild breadcrumbs and sibling links
+    #[command(subcommand)]
+    Sync(SyncCommands),
+
+    /// Inspect and restore deleted notes
+    ///
+    /// `zettel note delete` and `zettel note merge` move files into
+    /// .zettel/trash/ instead of deleting them.
+    ///
+    /// EXAMPLES:
+    /// zettel trash list             Show trashed deletions
+    /// zettel trash restore 1a2      Restore the latest deletion of 1a2
+    /// zettel trash empty            Permanently delete trashed files
+    #[command(subcommand)]
+    Trash(TrashCommands),
+
+    /// Link operations for editor integration
+    #[command(subcommand)]
+    Link(LinkCommands),
+
+    /// Check the vault for problems
+    ///
+    /// Reports duplicate IDs, invalid or uppercase ID filenames, broken links,
+    /// missing parents, parents that don't link to their children, templates
+    /// that fail validation, configuration problems and unreadable files.
+    /// Each issue has a severity, category, file and suggested fix.
+    ///
+    /// EXIT STATUS:
+    /// 1 if any error was found, 0 otherwise (warnings don't fail the check).
+    ///
+    /// EXAMPLES:
+    /// zettel doctor                            Full health check
+    /// zettel doctor --json | jq '.[] | select(.severity == "error")'
+    /// zettel doctor --missing-parents          Only list gaps in ancestor chains
+    #[command(alias = "status")]
+    Doctor {
+        /// Only check for IDs missing from the ancestor chains of notes
+        ///
+        /// Notes below a gap (1a2b without 1a2) get no parent link when
+        /// created. Fill gaps with `zettel note create-stubs`.
+        #[arg(long)]
+        missing_parents: bool,
+
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show statistics about the vault
+    ///
+    /// Counts notes, orphans (no links in or out), links and broken links,
+    /// and reports the tree's depth and branching factor per level, the
+    /// largest subtrees, the most linked notes, word counts, top tags and
+    /// notes created per month.
+    ///
+    /// EXAMPLES:
+    /// zettel stats
+    /// zettel stats --json | jq '.notes_per_month'
+    Stats {
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Navigate the note tree from a given note
+    ///
+    /// Resolves related notes that actually exist in the vault and prints one
+    /// per line as tab-separated ID, title and path, ready for editor
+    /// keybindings and scripts.
+    ///
+    /// EXAMPLES:
+    /// zettel nav parent 1a2         Print the parent note (1a)
+    /// zettel nav next 1a2           Next note in reading order
+    /// zettel nav children 1 --json  Children as JSON
+    /// zettel nav parent --title "entropy"    Start from a note by title
+    /// hx "$(zettel nav next 1a | cut -f3)"   Jump to the next note
+    Nav {
+        #[command(subcommand)]
+        target: NavCommands,
+
+        /// Start from the note best matching this title instead of an ID
+        ///
+        /// Matched fuzzily against titles, aliases and IDs; fails with the
+        /// candidates when several notes match about equally well.
+        #[arg(long, global = true, value_name = "FUZZY")]
+        title: Option<String>,
+
+        /// Output as JSON for machine processing
+        #[arg(long, global = true)]
+        json: bool,
     },
 }
 
@@ -377,6 +679,26 @@ pub enum IdCommands {
         #[arg(short = '0', long)]
         null_terminated: bool,
     },
+
+    /// Give duplicate-ID notes IDs of their own
+    ///
+    /// When several files share an ID (`1a.md` and `1a - Title.md`), one
+    /// keeps it and each other file is renamed to the next available
+    /// sibling ID. Links naming a renamed file are updated; bare-ID links
+    /// and notes below the ID stay with the kept file.
+    ///
+    /// EXAMPLES:
+    /// zettel id dedupe                          Fix every duplicate ID
+    /// zettel id dedupe 1a                       Keep the first 1a file
+    /// zettel id dedupe 1a --keep "1a - Title.md"
+    Dedupe {
+        /// Duplicate ID to resolve (defaults to all duplicates)
+        id: Option<String>,
+
+        /// File name of the note that keeps the ID (defaults to the first by name)
+        #[arg(long, requires = "id")]
+        keep: Option<String>,
+    },
 }
 
 /// Note management subcommands
@@ -398,7 +720,7 @@ pub enum NoteCommands {
     /// This is the main note creation command. It handles:
     /// - File creation with proper naming
     /// - Initial content generation
-    /// - Template processing (future feature)
+    /// - Template processing with variables (see --var)
     /// - Optional editor launching
     ///
     /// NAMING STRATEGY:
@@ -409,7 +731,7 @@ pub enum NoteCommands {
     /// CONTENT GENERATION:
     /// - Creates markdown heading from title
     /// - Adds blank line for writing
-    /// - Future: template system support
+    /// - Uses the vault template when enabled
     ///
     /// EXAMPLES:
     /// zettel note create 1 "First Note"         # Create and stay in terminal
@@ -420,6 +742,8 @@ pub enum NoteCommands {
         ///
         /// Should typically be generated using the `id` subcommands to ensure
         /// it fits properly in the hierarchy and doesn't conflict with existing notes.
+        /// Omit it when using --child-of or --sibling-of; the first positional
+        /// argument is then taken as the title.
         ///
         /// SAFETY: Command will refuse to overwrite existing notes.
         ///
@@ -437,7 +761,7 @@ pub enum NoteCommands {
         /// TITLE PROCESSING:
         /// - Used in filename: "1a - My Title.md"
         /// - Used as markdown heading: "# My Title"
-        /// - Future: template variable substitution
+        /// - Available to templates as {{title}}
         title: Option<String>,
 
         /// Open note in editor after creation
@@ -452,6 +776,48 @@ pub enum NoteCommands {
         #[arg(long)]
         #[arg(help = "Open note in editor after creation")]
         open: bool,
+
+        /// Create the note as the next available child of this note
+        ///
+        /// The ID is allocated while holding the vault lock, so concurrent
+        /// invocations never receive the same ID. The new ID is printed on
+        /// stdout (status messages go to stderr) for use in scripts.
+        ///
+        /// EXAMPLE:
+        /// zettel note create --child-of 1a "New Idea"    Creates e.g. 1a3
+        #[arg(long, value_name = "ID", conflicts_with = "sibling_of")]
+        child_of: Option<String>,
+
+        /// Create the note as the next available sibling of this note
+        ///
+        /// EXAMPLE:
+        /// zettel note create --sibling-of 1a2 "Related"  Creates e.g. 1a3
+        #[arg(long, value_name = "ID")]
+        sibling_of: Option<String>,
+
+        /// Use a named template from the template directory
+        ///
+        /// Overrides the configured template and template rules.
+        ///
+        /// EXAMPLE:
+        /// zettel note create 3a "On Entropy" --template academic
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+
+        /// Note type, for template rules selecting by type
+        ///
+        /// Also available in templates as {{type}}.
+        #[arg(long = "type", value_name = "TYPE")]
+        note_type: Option<String>,
+
+        /// Set a template variable, overriding the vault configuration
+        ///
+        /// Repeatable. Available in templates as {{KEY}}.
+        ///
+        /// EXAMPLE:
+        /// zettel note create 1a "Paper" --var source=Hawking --var year=1974
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
     },
 
     /// Open existing note in editor
@@ -477,6 +843,16 @@ pub enum NoteCommands {
         /// zettel note open 1        # Open root note
         /// zettel note open 1a2      # Open specific note in hierarchy
         id: Option<String>,
+
+        /// Open the note best matching this title instead of an ID
+        ///
+        /// Matched fuzzily against titles, aliases and IDs; fails with the
+        /// candidates when several notes match about equally well.
+        ///
+        /// EXAMPLE:
+        /// zettel note open --title "entrpy"   # Opens "Entropy"
+        #[arg(long, value_name = "FUZZY", conflicts_with = "id")]
+        title: Option<String>,
     },
 
     /// Display note content to stdout
@@ -498,6 +874,310 @@ pub enum NoteCommands {
         /// Must match an existing note in the vault.
         /// Outputs the complete content to stdout for processing.
         id: Option<String>,
+
+        /// Show the note best matching this title instead of an ID
+        #[arg(long, value_name = "FUZZY", conflicts_with = "id")]
+        title: Option<String>,
+    },
+
+    /// Link two notes to each other
+    ///
+    /// Adds a link to <TO> in <FROM> and a backlink to <FROM> in <TO>, placed
+    /// according to [linking] insertion_point and create_links_section.
+    /// A direction that already has a link is left unchanged.
+    ///
+    /// EXAMPLES:
+    /// zettel note link 1a2 3b       Connect two distant notes
+    Link {
+        /// ID of the first note
+        from: String,
+
+        /// ID of the second note
+        to: String,
+    },
+
+    /// Remove the links between two notes, in both directions
+    ///
+    /// Link-only lines are deleted, inline links are cut out of their
+    /// sentence, and a `## Links` section left empty is removed.
+    ///
+    /// EXAMPLES:
+    /// zettel note unlink 1a2 3b
+    Unlink {
+        /// ID of the first note
+        from: String,
+
+        /// ID of the second note
+        to: String,
+    },
+
+    /// Delete a note, moving it to the vault trash
+    ///
+    /// Links to the deleted note are removed or struck through according to
+    /// [linking] on_delete. A note with descendants is only deleted with
+    /// --recursive (trash them too) or --reparent (move them up a level).
+    ///
+    /// EXAMPLES:
+    /// zettel note delete 1a2              Delete a leaf note
+    /// zettel note delete 1a2 --recursive  Delete 1a2 and everything below it
+    /// zettel note delete 1a2 --reparent   Delete 1a2; 1a2a becomes e.g. 1a5
+    Delete {
+        /// ID of the note to delete
+        id: String,
+
+        /// Also delete all descendants
+        #[arg(long, conflicts_with = "reparent")]
+        recursive: bool,
+
+        /// Move descendants under the deleted note's parent
+        ///
+        /// Each child subtree gets the next available child ID of the
+        /// grandparent (or the next free top-level number for root notes),
+        /// and links to the moved notes are updated.
+        #[arg(long)]
+        reparent: bool,
+    },
+
+    /// Merge one note into another
+    ///
+    /// The absorbed note's body is appended to the kept note under a heading
+    /// with its title, and its frontmatter is merged in (new keys added, lists
+    /// combined). Its children move under the kept note with the next free
+    /// child IDs, every link to it is pointed at the kept note, and the
+    /// absorbed file goes to the vault trash.
+    ///
+    /// EXAMPLES:
+    /// zettel note merge 1a 3b    Fold 3b (and its children) into 1a
+    Merge {
+        /// ID of the note to keep
+        keep: String,
+
+        /// ID of the note to fold into the kept note
+        absorb: String,
+    },
+
+    /// Split a note into child notes, one per heading section
+    ///
+    /// Every section at the given heading level becomes a new child note
+    /// titled after its heading, with IDs from the next free child onwards.
+    /// Each section in the original note is replaced by a link to its new
+    /// note. Deeper headings stay with their section.
+    ///
+    /// EXAMPLES:
+    /// zettel note split 1a                  Split 1a at its ## headings
+    /// zettel note split 1a --level 3        Split at ### headings instead
+    /// zettel note split 1a --dry-run        Show the files without writing
+    Split {
+        /// ID of the note to split
+        id: String,
+
+        /// Heading level that starts a new note (2 means `##`)
+        #[arg(long, default_value_t = 2)]
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=6))]
+        level: u8,
+
+        /// Print the resulting files instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Create placeholder notes for missing ancestors
+    ///
+    /// For a vault with 1a2b but no 1a2, creates 1a2 (and any other gaps
+    /// up the chain) from the stub template in [template] stub_file, and
+    /// links the stubs to their parents and to the notes below them.
+    /// `zettel doctor --missing-parents` lists what would be created.
+    ///
+    /// EXAMPLES:
+    /// zettel note create-stubs
+    CreateStubs,
+
+    /// Extract a range of lines into a new note
+    ///
+    /// Moves the selected lines out of the source note into a new child (or
+    /// sibling) note and puts a link to the new note where the selection was.
+    /// The first heading in the selection, or else its first line, becomes the
+    /// new note's title. The new ID is printed on stdout for editor bindings.
+    ///
+    /// EXAMPLES:
+    /// zettel note extract 1a --line-start 5 --line-end 12
+    /// zettel note extract 1a --line-start 5 --line-end 5 --as sibling
+    ///
+    /// HELIX:
+    /// :sh zettel note extract $(zettel id parse %{buffer_name}) --line-start %{selection_line_start} --line-end %{selection_line_end}
+    Extract {
+        /// ID of the note containing the selection
+        id: String,
+
+        /// First selected line (1-based)
+        #[arg(long)]
+        line_start: usize,
+
+        /// Last selected line (1-based, inclusive)
+        #[arg(long)]
+        line_end: usize,
+
+        /// Create the new note as a child or a sibling of the source note
+        #[arg(long = "as", value_name = "RELATION", default_value = "child")]
+        #[arg(value_parser = ["child", "sibling"])]
+        relation: String,
+
+        /// Open the new note in editor after extraction
+        #[arg(long)]
+        open: bool,
+    },
+}
+
+/// Managed block synchronization subcommands
+#[derive(Subcommand)]
+pub enum SyncCommands {
+    /// Rebuild the managed list of child links in parent notes
+    ///
+    /// Children are the existing notes one level below the parent
+    /// (1a -> 1a1, 1a2, ...), listed in Folgezettel order and formatted
+    /// according to the [linking] configuration.
+    Children {
+        /// Only rebuild the block in this note (defaults to the whole vault)
+        id: Option<String>,
+    },
+
+    /// Rebuild breadcrumb and previous/next sibling navigation blocks
+    ///
+    /// What the block contains is set in [linking.navigation]. With both
+    /// breadcrumbs and siblings disabled, existing navigation blocks are removed.
+    Nav {
+        /// Only rebuild the block in this note (defaults to the whole vault)
+        id: Option<String>,
+    },
+
+    /// Refresh the results of saved searches written into notes
+    ///
+    /// Rebuilds every block created by `zettel search @name --into <ID>`
+    /// from the current [searches] configuration.
+    Searches {
+        /// Only refresh the blocks in this note (defaults to the whole vault)
+        id: Option<String>,
+    },
+}
+
+/// Search index subcommands
+#[cfg(feature = "search")]
+#[derive(Subcommand)]
+pub enum IndexCommands {
+    /// Delete the search index and index every note again
+    Rebuild,
+}
+
+/// Trash subcommands
+#[derive(Subcommand)]
+pub enum TrashCommands {
+    /// List trash entries, oldest first
+    List {
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move a trash entry's files back into the vault
+    Restore {
+        /// Entry name from `trash list`, or a note ID (latest entry for it)
+        entry: String,
+    },
+
+    /// Permanently delete everything in the trash
+    Empty,
+}
+
+/// Link subcommands
+#[derive(Subcommand)]
+pub enum LinkCommands {
+    /// Insert a link to a note at a cursor position
+    ///
+    /// The link is formatted according to the [linking] configuration
+    /// (use_title_alias, format). Lines and columns are 1-based; --col-unit
+    /// says what the column counts, since editors differ for non-ASCII text.
+    ///
+    /// With --json nothing is written; instead an LSP-style text edit
+    /// (0-based line/character in the same unit) is printed for the editor
+    /// to apply itself.
+    ///
+    /// EXAMPLES:
+    /// zettel link insert notes/1a.md --line 3 --col 10 1b
+    /// zettel link insert 1a.md --line 3 --col 10 --col-unit utf16 --json 1b
+    ///
+    /// HELIX:
+    /// :sh zettel link insert %{buffer_name} --line %{cursor_line} --col %{cursor_column} 1b
+    Insert {
+        /// File to insert the link into
+        file: PathBuf,
+
+        /// Cursor line (1-based)
+        #[arg(long)]
+        line: usize,
+
+        /// Cursor column (1-based)
+        #[arg(long)]
+        col: usize,
+
+        /// What the column counts: characters, UTF-8 bytes or UTF-16 code units
+        #[arg(long, default_value = "char")]
+        #[arg(value_parser = ["char", "byte", "utf16"])]
+        col_unit: String,
+
+        /// Print the edit as JSON instead of modifying the file
+        #[arg(long)]
+        json: bool,
+
+        /// ID of the note to link to (reads from stdin if not provided)
+        target: Option<String>,
+    },
+}
+
+/// Tree navigation subcommands
+///
+/// Every subcommand takes the starting note's ID as argument or from stdin.
+/// Single-note lookups (parent, next, prev, first-child) exit with an error
+/// when there is no such note; list lookups simply print nothing.
+#[derive(Subcommand)]
+pub enum NavCommands {
+    /// The note's parent (1a2 -> 1a)
+    Parent {
+        /// Note ID to start from (reads from stdin if not provided)
+        id: Option<String>,
+    },
+
+    /// The note's existing direct children, in order
+    Children {
+        /// Note ID to start from (reads from stdin if not provided)
+        id: Option<String>,
+    },
+
+    /// The note's existing siblings, excluding the note itself
+    Siblings {
+        /// Note ID to start from (reads from stdin if not provided)
+        id: Option<String>,
+    },
+
+    /// The next note in Luhmann reading order
+    ///
+    /// Reading order walks the tree depth-first: a note is followed by its
+    /// first child, then its next sibling, then the next sibling of its
+    /// nearest ancestor (1, 1a, 1a1, 1a2, 1b, 2). Missing IDs are skipped.
+    Next {
+        /// Note ID to start from (reads from stdin if not provided)
+        id: Option<String>,
+    },
+
+    /// The previous note in Luhmann reading order
+    Prev {
+        /// Note ID to start from (reads from stdin if not provided)
+        id: Option<String>,
+    },
+
+    /// The note's first existing child
+    FirstChild {
+        /// Note ID to start from (reads from stdin if not provided)
+        id: Option<String>,
     },
 }
 
@@ -521,6 +1201,24 @@ pub enum TemplateCommands {
         /// Template file to test (or read from stdin)
         #[arg(long)]
         file: Option<String>,
+
+        /// Note ID to use for {{id}} and {{parent_id}}
+        #[arg(long, default_value = "1a")]
+        id: String,
+
+        /// Set a template variable (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
+    },
+
+    /// List the templates in the template directory
+    ///
+    /// Shows each template's name and file, which one is the default and
+    /// which rules select it.
+    List {
+        /// Output as JSON for machine processing
+        #[arg(long)]
+        json: bool,
     },
 
     /// Extract placeholder information from template
@@ -545,6 +1243,16 @@ pub enum TemplateCommands {
     },
 }
 
+/// Parse a `KEY=VALUE` template variable
+fn parse_var(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{}'", s)),
+    }
+}
+
 // CLI DESIGN PRINCIPLES EXPLAINED:
 //
 // 1. DISCOVERABILITY:
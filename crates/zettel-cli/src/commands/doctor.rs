@@ -0,0 +1,383 @@
+// crates/zettel-cli/src/commands/doctor.rs - Vault Health Checks
+//
+// `zettel doctor` looks for problems that other commands work around
+// silently. The checks only read the vault; commands such as
+// `zettel note create-stubs` do the fixing.
+//
+// CHECKS:
+// - Files: unreadable or non-UTF-8 files, invalid or uppercase ID filenames
+// - IDs: duplicate IDs across files
+// - Links: broken links, parents that don't link to their children
+// - Hierarchy: gaps in the ancestor chain of existing notes (1a2b exists but
+//   1a2 doesn't), which leave notes without a parent link
+// - Configuration: missing vault config, settings other commands reject,
+//   templates (including the template directory's) that can't be read or
+//   fail validation
+//
+// The pure checks live in `zettel_core::validate`; this module gathers the
+// data they need from disk and reports the results.
+//
+// EXIT STATUS:
+// 1 when any error-severity issue is found, so scripts and CI can gate on it.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use zettel_core::config::{ConfigManager, TemplateConfig};
+use zettel_core::note::Note;
+use zettel_core::template::{Template, TemplateService};
+use zettel_core::validate::{self, IssueCategory, IssueSeverity, ValidationIssue};
+
+use crate::commands::note::LinkResolver;
+use crate::context::Context;
+
+/// Handle the doctor command
+pub fn handle(ctx: &Context, missing_parents: bool, json: bool) -> Result<()> {
+    let tree = ctx.note_tree();
+
+    let mut issues = if missing_parents {
+        validate::check_missing_parents(&tree)
+    } else {
+        check_vault(ctx)
+    };
+    issues.sort_by(|a, b| (a.severity, &a.file).cmp(&(b.severity, &b.file)));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&issues)?);
+    } else {
+        print_issues(ctx, &issues);
+    }
+
+    if issues.iter().any(|i| i.severity == IssueSeverity::Error) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Run every check against the vault
+fn check_vault(ctx: &Context) -> Vec<ValidationIssue> {
+    let id_manager = ctx.get_id_manager();
+    let tree = ctx.note_tree();
+    let mut issues = Vec::new();
+
+    // Read every file once; later checks only look at readable notes
+    let mut contents: HashMap<PathBuf, String> = HashMap::new();
+    for path in ctx.vault_service.get_vault_files() {
+        match ctx.vault_service.read_file(&path) {
+            Ok(content) => {
+                contents.insert(path.clone(), content);
+            }
+            Err(e) => issues.push(
+                ValidationIssue::new(
+                    IssueSeverity::Error,
+                    IssueCategory::UnreadableFile,
+                    format!("Cannot read file: {:#}", e),
+                )
+                .with_file(&path)
+                .with_suggestion("Check the file's permissions and convert it to UTF-8"),
+            ),
+        }
+
+        let id = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| id_manager.extract_from_filename(n));
+        issues.extend(validate::check_filename(&path, id.as_ref()));
+    }
+
+    issues.extend(validate::check_duplicate_ids(&tree));
+    issues.extend(validate::check_missing_parents(&tree));
+
+    let resolver = LinkResolver::new(ctx, &tree);
+    let resolves = |target: &str| {
+        resolver.resolve(target).is_some()
+            || ctx.vault_path().join(target).exists()
+            || ctx.vault_path().join(format!("{}.md", target)).exists()
+    };
+    for note in tree.notes() {
+        if let Some(content) = contents.get(&note.path) {
+            issues.extend(validate::check_links(note, content, resolves));
+        }
+    }
+
+    // Child links are only expected when zettel is configured to add them
+    if ctx.config().linking.insert_in_parent {
+        let links_to = |parent: &Note, child: &Note| {
+            contents.get(&parent.path).is_none_or(|content| {
+                zettel_core::link::LinkService::find_links(content)
+                    .iter()
+                    .any(|link| {
+                        resolver
+                            .resolve(&link.target)
+                            .is_some_and(|note| note.id == child.id)
+                    })
+            })
+        };
+        issues.extend(validate::check_child_links(&tree, links_to));
+    }
+
+    issues.extend(check_configuration(ctx));
+    issues
+}
+
+/// Check the vault configuration and the templates it refers to
+fn check_configuration(ctx: &Context) -> Vec<ValidationIssue> {
+    let config = &ctx.config().template;
+    let mut issues = Vec::new();
+
+    let config_path = ctx.vault_path().join(".zettel").join("config.toml");
+    if !config_path.exists() {
+        issues.push(
+            ValidationIssue::new(
+                IssueSeverity::Info,
+                IssueCategory::ConfigurationIssue,
+                "No vault configuration found; using defaults",
+            )
+            .with_suggestion("Run `zettel init` in the vault to create .zettel/config.toml"),
+        );
+    }
+
+    // The context fell back to defaults for a config that doesn't parse
+    if let Err(e) = ConfigManager::load_config_unvalidated(Some(ctx.vault_path())) {
+        issues.push(
+            ValidationIssue::new(
+                IssueSeverity::Error,
+                IssueCategory::ConfigurationIssue,
+                e.to_string(),
+            )
+            .with_file(&config_path)
+            .with_suggestion("Fix the syntax; doctor checked the vault with default settings"),
+        );
+    }
+
+    // The context skipped validation, so every rejected setting shows up here
+    for error in ConfigManager::validation_errors(ctx.config()) {
+        issues.push(
+            ValidationIssue::new(
+                IssueSeverity::Error,
+                IssueCategory::ConfigurationIssue,
+                error,
+            )
+            .with_file(&config_path)
+            .with_suggestion("Fix the setting; other commands refuse to run until then"),
+        );
+    }
+
+    let mut included = Vec::new();
+
+    if TemplateService::should_use_template(config) {
+        match TemplateService::resolve_template_path(config, None) {
+            Err(e) => issues.push(
+                ValidationIssue::new(
+                    IssueSeverity::Error,
+                    IssueCategory::ConfigurationIssue,
+                    format!("Template configuration error: {}", e),
+                )
+                .with_file(&config_path)
+                .with_suggestion("Set [template] file, or disable templates"),
+            ),
+            Ok(template_path) => {
+                let file = ctx.vault_path().join(&template_path);
+                match ctx.vault_service.read_template_file(&template_path) {
+                    Err(e) => issues.push(
+                        ValidationIssue::new(
+                            IssueSeverity::Error,
+                            IssueCategory::InvalidTemplate,
+                            format!("{:#}", e),
+                        )
+                        .with_file(&file),
+                    ),
+                    Ok(content) => {
+                        included.extend(partials(&content));
+                        issues.extend(check_template(&file, &content, config));
+                    }
+                }
+            }
+        }
+    }
+
+    if !config.stub_file.is_empty()
+        && let Err(e) = ctx.vault_service.read_template_file(&config.stub_file)
+    {
+        issues.push(
+            ValidationIssue::new(
+                IssueSeverity::Error,
+                IssueCategory::InvalidTemplate,
+                format!("{:#}", e),
+            )
+            .with_file(ctx.vault_path().join(&config.stub_file)),
+        );
+    }
+
+    // Named templates in the template directory; files other templates
+    // include are partials, which don't have to stand on their own
+    let directory = config.directory.trim();
+    if !directory.is_empty()
+        && let Ok(entries) = fs::read_dir(ctx.vault_path().join(directory))
+    {
+        let mut templates: Vec<(PathBuf, String)> = Vec::new();
+        for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            match fs::read_to_string(&path) {
+                Ok(content) => templates.push((path, content)),
+                Err(e) => issues.push(
+                    ValidationIssue::new(
+                        IssueSeverity::Error,
+                        IssueCategory::InvalidTemplate,
+                        format!("Cannot read template: {}", e),
+                    )
+                    .with_file(&path),
+                ),
+            }
+        }
+        templates.sort();
+
+        for (_, content) in &templates {
+            included.extend(partials(content));
+        }
+        let default = TemplateService::resolve_template_path(config, None)
+            .ok()
+            .map(|path| ctx.vault_path().join(path));
+        for (path, content) in &templates {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            if included
+                .iter()
+                .any(|partial| partial.trim_end_matches(".md") == name)
+                || default.as_ref() == Some(path)
+            {
+                continue;
+            }
+            issues.extend(check_template(path, content, config));
+        }
+    }
+
+    // Templates named by rules must exist in the template directory
+    for rule in &config.rules {
+        let Ok(path) = TemplateService::resolve_template_path(config, Some(&rule.template)) else {
+            continue;
+        };
+        if !ctx.vault_path().join(&path).is_file() {
+            issues.push(
+                ValidationIssue::new(
+                    IssueSeverity::Error,
+                    IssueCategory::InvalidTemplate,
+                    format!("Template rule names missing template '{}'", rule.template),
+                )
+                .with_file(ctx.vault_path().join(&path))
+                .with_suggestion("Create the template or fix [[template.rules]]"),
+            );
+        }
+    }
+
+    issues
+}
+
+/// Validate one template's content, as `zettel template validate` does
+fn check_template(file: &Path, content: &str, config: &TemplateConfig) -> Option<ValidationIssue> {
+    let result = TemplateService::validate_template(content, config);
+    (!result.valid).then(|| {
+        ValidationIssue::new(
+            IssueSeverity::Error,
+            IssueCategory::InvalidTemplate,
+            result
+                .message
+                .unwrap_or_else(|| "Template validation failed".to_string()),
+        )
+        .with_file(file)
+        .with_suggestion("Check it with `zettel template validate`")
+    })
+}
+
+/// Names of the partials a template includes, if it parses
+fn partials(content: &str) -> Vec<String> {
+    Template::parse(content)
+        .map(|template| template.partials())
+        .unwrap_or_default()
+}
+
+/// Print issues for humans, most severe first, followed by a summary
+fn print_issues(ctx: &Context, issues: &[ValidationIssue]) {
+    if issues.is_empty() {
+        println!("✅ No problems found");
+        return;
+    }
+
+    for issue in issues {
+        let icon = match issue.severity {
+            IssueSeverity::Error => "❌",
+            IssueSeverity::Warning => "⚠️ ",
+            IssueSeverity::Info => "ℹ️ ",
+        };
+        println!("{} [{}] {}", icon, issue.category, issue.description);
+        if let Some(file) = &issue.file {
+            let shown = file.strip_prefix(ctx.vault_path()).unwrap_or(file);
+            println!("   file: {}", shown.display());
+        }
+        if let Some(suggestion) = &issue.suggestion {
+            println!("   💡 {}", suggestion);
+        }
+    }
+
+    let count = |severity| issues.iter().filter(|i| i.severity == severity).count();
+    println!(
+        "\n{} error(s), {} warning(s), {} info",
+        count(IssueSeverity::Error),
+        count(IssueSeverity::Warning),
+        count(IssueSeverity::Info)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_configuration_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".zettel")).unwrap();
+        fs::create_dir_all(dir.path().join("templates")).unwrap();
+        fs::write(
+            dir.path().join(".zettel/config.toml"),
+            "[template]\ndirectory = \"templates\"\n\n\
+             [linking.navigation]\nposition = \"middle\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("templates/idea.md"), "# {{title}}\n").unwrap();
+        fs::write(dir.path().join("templates/quote.md"), "{{> footer}}\n").unwrap();
+        fs::write(dir.path().join("templates/footer.md"), "Back: {{link}}\n").unwrap();
+        fs::write(dir.path().join("1.md"), "# One\n").unwrap();
+
+        assert!(Context::new(Some(dir.path().to_path_buf())).is_err());
+        let ctx = Context::unvalidated(Some(dir.path().to_path_buf())).unwrap();
+        let issues = check_vault(&ctx);
+
+        let errors: Vec<_> = issues
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .collect();
+        assert_eq!(errors.len(), 3, "{:#?}", errors);
+        assert!(errors.iter().any(|issue| {
+            issue.category == IssueCategory::ConfigurationIssue
+                && issue.description.contains("middle")
+        }));
+        // Named templates missing the required placeholders; the footer
+        // partial isn't checked on its own
+        for name in ["idea.md", "quote.md"] {
+            assert!(errors.iter().any(|issue| {
+                issue.category == IssueCategory::InvalidTemplate
+                    && issue.file.as_ref().is_some_and(|file| file.ends_with(name))
+            }));
+        }
+
+        // A config that doesn't parse is reported too, checked with defaults
+        fs::write(dir.path().join(".zettel/config.toml"), "[linking\n").unwrap();
+        let ctx = Context::unvalidated(Some(dir.path().to_path_buf())).unwrap();
+        let issues = check_configuration(&ctx);
+        assert!(issues.iter().any(|issue| {
+            issue.severity == IssueSeverity::Error && issue.description.contains("TOML")
+        }));
+    }
+}
@@ -0,0 +1,133 @@
+// crates/zettel-cli/src/commands/find.rs - Fuzzy Note Lookup
+//
+// `zettel find` lists the notes whose title, aliases or ID approximately
+// match some text. The same matching backs the `--title` option of commands
+// that act on one note (`note open`, `note show`, `nav`), which resolves to
+// the best match or fails with the close candidates.
+//
+// Matching and ranking live in `zettel_core::fuzzy`; this module reads
+// aliases from the notes' frontmatter and reports the results.
+//
+// OUTPUT FORMAT:
+// Like `zettel nav`, one note per line as `ID<TAB>title<TAB>path`; `--json`
+// adds the score and which name (title, alias or ID) matched.
+
+use anyhow::Result;
+use zettel_core::frontmatter;
+use zettel_core::fuzzy::{self, Candidate, FuzzyMatch, MatchedOn, Resolution};
+use zettel_core::id::Id;
+use zettel_core::note::{Note, NoteTree};
+
+use crate::context::Context;
+
+/// Handle the find command
+pub fn handle(ctx: &Context, query: Option<String>, limit: usize, json: bool) -> Result<()> {
+    let query_str = crate::stdin::read_input_or_stdin(query.as_deref())?;
+
+    let tree = ctx.note_tree();
+    let candidates = candidates(ctx, &tree);
+    let mut matches = fuzzy::rank(&query_str, &candidates);
+    matches.truncate(limit);
+
+    if json {
+        let json_matches: Vec<_> = matches
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "id": m.note.id.to_string(),
+                    "title": m.note.title,
+                    "path": m.note.path.display().to_string(),
+                    "score": m.score,
+                    "exact": m.exact,
+                    "matched": matched_name(m),
+                    "alias": match &m.matched {
+                        MatchedOn::Alias(alias) => Some(alias),
+                        _ => None,
+                    },
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_matches)?);
+    } else if matches.is_empty() {
+        eprintln!("No notes match: {}", query_str);
+        std::process::exit(1);
+    } else {
+        for m in &matches {
+            println!(
+                "{}\t{}\t{}",
+                m.note.id,
+                m.note.title.as_deref().unwrap_or(""),
+                m.note.path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve the note a command should act on, given as an ID or a fuzzy title
+///
+/// With neither, the ID is read from stdin. Exits with a message when no
+/// note matches, or with the candidates when a title is ambiguous.
+pub fn select_note<'a>(
+    ctx: &Context,
+    tree: &'a NoteTree,
+    id: Option<&str>,
+    title: Option<&str>,
+) -> Result<&'a Note> {
+    let Some(title) = title else {
+        let id_str = crate::stdin::read_input_or_stdin(id)?;
+        let parsed_id = Id::parse(&id_str)?;
+        return match ctx.lookup(tree, &parsed_id) {
+            Some(note) => Ok(note),
+            None => {
+                eprintln!("❌ No note found with ID: {}", id_str);
+                std::process::exit(1);
+            }
+        };
+    };
+
+    match fuzzy::resolve(title, &candidates(ctx, tree)) {
+        Resolution::Found(note) => Ok(note),
+        Resolution::NotFound => {
+            eprintln!("❌ No note title matches: {}", title);
+            std::process::exit(1);
+        }
+        Resolution::Ambiguous(options) => {
+            eprintln!("❌ \"{}\" matches several notes:", title);
+            for m in &options {
+                eprintln!(
+                    "   {}\t{}",
+                    m.note.id,
+                    m.note.title.as_deref().unwrap_or("")
+                );
+            }
+            eprintln!("Use a more specific title or the note's ID");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Every note in the tree with its frontmatter aliases
+fn candidates<'a>(ctx: &Context, tree: &'a NoteTree) -> Vec<Candidate<'a>> {
+    tree.notes()
+        .iter()
+        .map(|note| Candidate {
+            note,
+            aliases: ctx
+                .vault_service
+                .read_file(&note.path)
+                .map(|content| frontmatter::aliases(&content))
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Which kind of name a match was found by, for JSON output
+fn matched_name(m: &FuzzyMatch) -> &'static str {
+    match m.matched {
+        MatchedOn::Title => "title",
+        MatchedOn::Alias(_) => "alias",
+        MatchedOn::Id => "id",
+    }
+}
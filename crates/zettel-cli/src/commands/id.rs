@@ -15,7 +15,9 @@ use anyhow::Result;
 use zettel_core::id::Id;
 
 use crate::cli::IdCommands;
+use crate::commands::{refactor, sync};
 use crate::context::Context;
+use crate::services::VaultLock;
 use crate::stdin;
 
 /// Handle all ID manipulation commands
@@ -53,6 +55,7 @@ pub fn handle(ctx: &Context, cmd: IdCommands) -> Result<()> {
             files,
             null_terminated,
         } => handle_extract_ids(ctx, files, null_terminated),
+        IdCommands::Dedupe { id, keep } => handle_dedupe(ctx, id.as_deref(), keep.as_deref()),
     }
 }
 
@@ -401,15 +404,91 @@ fn handle_extract_ids(ctx: &Context, files: Vec<String>, null_terminated: bool)
     };
 
     for file_path in files_to_process {
-        if let Some(filename) = std::path::Path::new(&file_path).file_name() {
-            if let Some(filename_str) = filename.to_str() {
-                if let Some(id) = id_manager.extract_from_filename(filename_str) {
-                    println!("{}", id);
-                }
+        if let Some(filename) = std::path::Path::new(&file_path).file_name()
+            && let Some(filename_str) = filename.to_str()
+            && let Some(id) = id_manager.extract_from_filename(filename_str)
+        {
+            println!("{}", id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Rename all but one note of each duplicate ID to a fresh sibling ID
+///
+/// Runs under the vault lock. The kept note is the `keep` file if given,
+/// otherwise the first file by name.
+fn handle_dedupe(ctx: &Context, id: Option<&str>, keep: Option<&str>) -> Result<()> {
+    let lock = VaultLock::acquire(ctx.vault_path())?;
+    let tree = ctx.note_tree();
+
+    let duplicates: Vec<Id> = match id {
+        Some(id_str) => {
+            let parsed = Id::parse(id_str)?;
+            if tree.get_all(&parsed).len() < 2 {
+                lock.abort(&format!("ID {} is not shared by several notes", id_str));
+            }
+            vec![parsed]
+        }
+        None => tree.duplicate_ids().into_iter().cloned().collect(),
+    };
+
+    if duplicates.is_empty() {
+        println!("✅ No duplicate IDs");
+        return Ok(());
+    }
+
+    let id_manager = ctx.get_id_manager();
+    let mut new_ids = Vec::new();
+    for dup_id in &duplicates {
+        let notes = tree.get_all(dup_id);
+        let keep_index = match keep {
+            Some(name) => match notes
+                .iter()
+                .position(|n| n.path.file_name() == Some(name.as_ref()))
+            {
+                Some(index) => index,
+                None => lock.abort(&format!(
+                    "{} is not one of the notes with ID {}",
+                    name, dup_id
+                )),
+            },
+            None => 0,
+        };
+        println!(
+            "📌 Keeping {} as {}",
+            notes[keep_index].path.display(),
+            dup_id
+        );
+
+        for (index, note) in notes.iter().enumerate() {
+            if index == keep_index {
+                continue;
             }
+            let new_id = id_manager.next_available_sibling(dup_id)?;
+            let moved = refactor::rename_note(ctx, note, &new_id)?;
+            println!(
+                "↪️  Renamed {} to {}",
+                note.path.display(),
+                moved.new_path.display()
+            );
+            new_ids.push(new_id);
+        }
+    }
+
+    // Renamed notes join their parent's children and sibling navigation
+    let tree = ctx.note_tree();
+    for new_id in &new_ids {
+        if ctx.config().linking.managed_children
+            && let Some(parent) = tree.parent(new_id)
+        {
+            sync::update_children_block(ctx, &tree, parent, false)?;
         }
+        sync::refresh_nav_around(ctx, new_id)?;
     }
 
+    println!("✅ Resolved {} duplicate ID(s)", duplicates.len());
     Ok(())
 }
 
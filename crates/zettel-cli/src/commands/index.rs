@@ -0,0 +1,31 @@
+// crates/zettel-cli/src/commands/index.rs - Search Index Commands
+//
+// Maintenance for the full-text index behind `zettel search`. Searches keep
+// the index up to date on their own; these commands are for recovering from
+// a damaged or outdated index.
+
+use anyhow::Result;
+
+use crate::cli::IndexCommands;
+use crate::commands::search::note_fields;
+use crate::context::Context;
+use crate::services::{IndexService, VaultLock};
+
+/// Handle index commands
+pub fn handle(ctx: &Context, cmd: IndexCommands) -> Result<()> {
+    match cmd {
+        IndexCommands::Rebuild => {
+            // Searches must not update the index while it is rebuilt
+            let _lock = VaultLock::acquire(ctx.vault_path())?;
+            let notes = ctx.load_notes();
+            let indexed = IndexService::new(ctx.vault_path()).rebuild(
+                &ctx.vault_service,
+                &notes,
+                |note, content| note_fields(ctx, note, content),
+            )?;
+            println!("✅ Indexed {} note(s)", indexed);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// crates/zettel-cli/src/commands/link.rs - Link Commands
+//
+// These commands place links to notes inside arbitrary files, typically at
+// the editor's cursor. Link text always comes from `LinkService`, so links
+// inserted here look exactly like the ones zettel writes when creating notes.
+
+use anyhow::Result;
+use zettel_core::id::Id;
+use zettel_core::link::LinkService;
+use zettel_core::position::{self, ColumnUnit};
+
+use crate::cli::LinkCommands;
+use crate::context::Context;
+
+/// Handle link commands
+pub fn handle(ctx: &Context, cmd: LinkCommands) -> Result<()> {
+    match cmd {
+        LinkCommands::Insert {
+            file,
+            line,
+            col,
+            col_unit,
+            json,
+            target,
+        } => {
+            let target_str = crate::stdin::read_input_or_stdin(target.as_deref())?;
+            let target_id = Id::parse(&target_str)?;
+            let unit: ColumnUnit = col_unit.parse()?;
+
+            let tree = ctx.note_tree();
+            let Some(target_note) = ctx.lookup(&tree, &target_id) else {
+                eprintln!("❌ No note found with ID: {}", target_str);
+                std::process::exit(1);
+            };
+
+            let link = LinkService::format_link(
+                &ctx.config().linking,
+                &target_note.id.to_string(),
+                &target_note.filename(),
+                target_note.title.as_deref(),
+            );
+
+            let content = ctx.vault_service.read_file(&file)?;
+            let offset = match position::byte_offset(&content, line, col, unit) {
+                Ok(offset) => offset,
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            if json {
+                // LSP TextEdit: an empty range at the cursor with the link as new text
+                let (edit_line, character) = position::line_col_at(&content, offset, unit);
+                let point = serde_json::json!({ "line": edit_line, "character": character });
+                let edit = serde_json::json!({
+                    "range": { "start": point, "end": point },
+                    "newText": link
+                });
+                println!("{}", serde_json::to_string_pretty(&edit)?);
+            } else {
+                ctx.vault_service.insert_content_at(&file, offset, &link)?;
+                println!("🔗 Inserted {} into {}", link, file.display());
+            }
+        }
+    }
+
+    Ok(())
+}
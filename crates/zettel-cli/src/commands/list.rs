@@ -26,28 +26,28 @@ pub fn handle(ctx: &Context, full_paths: bool, json: bool) -> Result<()> {
     // Collect all notes with their information
     let mut notes = Vec::new();
     for file in files {
-        if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
-            if let Some(id) = id_manager.extract_from_filename(filename) {
-                // Extract title from filename for prettier display
-                let title = if filename.contains(" - ") {
-                    filename
-                        .split(" - ")
-                        .nth(1)
-                        .unwrap_or("")
-                        .strip_suffix(".md")
-                        .unwrap_or("")
-                        .to_string()
-                } else {
-                    String::new()
-                };
+        if let Some(filename) = file.file_name().and_then(|n| n.to_str())
+            && let Some(id) = id_manager.extract_from_filename(filename)
+        {
+            // Extract title from filename for prettier display
+            let title = if filename.contains(" - ") {
+                filename
+                    .split(" - ")
+                    .nth(1)
+                    .unwrap_or("")
+                    .strip_suffix(".md")
+                    .unwrap_or("")
+                    .to_string()
+            } else {
+                String::new()
+            };
 
-                notes.push(NoteInfo {
-                    id,
-                    filename: filename.to_string(),
-                    path: file.display().to_string(),
-                    title: if title.is_empty() { None } else { Some(title) },
-                });
-            }
+            notes.push(NoteInfo {
+                id,
+                filename: filename.to_string(),
+                path: file.display().to_string(),
+                title: if title.is_empty() { None } else { Some(title) },
+            });
         }
     }
 
@@ -0,0 +1,240 @@
+// crates/zettel-cli/src/commands/mentions.rs - Unlinked Mentions Command
+//
+// `zettel mentions` finds notes that mention another note's title or
+// aliases in plain text without linking to it, and with `--link` turns those
+// mentions into links. Matching lives in `zettel_core::mention`; this module
+// decides which notes already link where and writes the links.
+//
+// OVERLAPPING MENTIONS:
+// With `--all`, one stretch of text can mention two notes ("Second Law" and
+// "Law"). Each note keeps the earliest mention at a position, preferring the
+// longer one, so every piece of text is reported and linked at most once.
+//
+// LINKING:
+// `--link` writes `[[filename|mention text]]`, so the sentence still reads
+// the way the author wrote it. A custom `[linking] format` is used as
+// configured instead, with the mention text as its `{title}`.
+//
+// OUTPUT FORMAT:
+// Mentions are grouped by mentioned note, one per line, grep style:
+// `source ID:line:column: line text`. `--json` prints one object per
+// mention.
+
+use anyhow::Result;
+use zettel_core::frontmatter;
+use zettel_core::link::LinkService;
+use zettel_core::mention::{self, Mention, NameMatcher};
+use zettel_core::note::Note;
+
+use crate::commands::find::select_note;
+use crate::commands::note::LinkResolver;
+use crate::context::Context;
+
+/// A mention of one note found in another
+struct Found<'a> {
+    source: &'a Note,
+    target: &'a Note,
+    mention: Mention,
+}
+
+/// Handle the mentions command
+pub fn handle(
+    ctx: &Context,
+    id: Option<String>,
+    all: bool,
+    sources: Vec<String>,
+    link: bool,
+    json: bool,
+) -> Result<()> {
+    let tree = ctx.note_tree();
+    let notes = tree.notes();
+    let targets: Vec<&Note> = if all {
+        notes.iter().collect()
+    } else {
+        vec![select_note(ctx, &tree, id.as_deref(), None)?]
+    };
+
+    let contents: Vec<String> = notes
+        .iter()
+        .map(|note| ctx.vault_service.read_file(&note.path).unwrap_or_default())
+        .collect();
+    // One compiled pattern per target, reused for every source note
+    let matchers: Vec<Option<NameMatcher>> = targets
+        .iter()
+        .map(|target| {
+            let content = notes
+                .iter()
+                .position(|note| note.path == target.path)
+                .map(|i| contents[i].as_str())
+                .unwrap_or_default();
+            let mut names = frontmatter::aliases(content);
+            names.extend(target.title.clone());
+            NameMatcher::new(&names)
+        })
+        .collect();
+
+    // Mentions per source note, in content order
+    let resolver = LinkResolver::new(ctx, &tree);
+    let mut found: Vec<Found> = Vec::new();
+    let mut linked_notes = 0;
+    let mut linked_mentions = 0;
+    for (source, content) in notes.iter().zip(&contents) {
+        if !sources.is_empty() && !sources.iter().any(|s| *s == source.id.to_string()) {
+            continue;
+        }
+
+        let linked: Vec<_> = LinkService::find_links(content)
+            .iter()
+            .filter_map(|link| resolver.resolve(&link.target))
+            .map(|note| note.id.clone())
+            .collect();
+        let mut in_source: Vec<Found> = Vec::new();
+        for (target, matcher) in targets.iter().zip(&matchers) {
+            let Some(matcher) = matcher else {
+                continue;
+            };
+            if target.path == source.path || linked.contains(&target.id) {
+                continue;
+            }
+            in_source.extend(matcher.find(content).into_iter().map(|mention| Found {
+                source,
+                target,
+                mention,
+            }));
+        }
+
+        in_source.sort_by_key(|f| {
+            (
+                f.mention.range.start,
+                std::cmp::Reverse(f.mention.range.end),
+            )
+        });
+        let mut last_end = 0;
+        in_source.retain(|f| {
+            let keep = f.mention.range.start >= last_end;
+            if keep {
+                last_end = f.mention.range.end;
+            }
+            keep
+        });
+
+        if link && !in_source.is_empty() {
+            let links: Vec<Mention> = in_source.iter().map(|f| f.mention.clone()).collect();
+            let new_content = mention::link(content, &links, |m| {
+                let target = in_source
+                    .iter()
+                    .find(|f| f.mention.range == m.range)
+                    .map(|f| f.target)
+                    .expect("every mention comes from a found entry");
+                let linking = &ctx.config().linking;
+                if linking.format.is_some() {
+                    LinkService::format_link(
+                        linking,
+                        &target.id.to_string(),
+                        &target.filename(),
+                        Some(&m.text),
+                    )
+                } else {
+                    format!("[[{}|{}]]", target.filename(), m.text)
+                }
+            });
+            ctx.vault_service.write_file(&source.path, &new_content)?;
+            linked_notes += 1;
+            linked_mentions += in_source.len();
+        }
+        found.extend(in_source);
+    }
+
+    if json {
+        let json_mentions: Vec<_> = found
+            .iter()
+            .map(|f| {
+                let file = f.source.path.strip_prefix(ctx.vault_path());
+                serde_json::json!({
+                    "target_id": f.target.id.to_string(),
+                    "target_title": f.target.title,
+                    "source_id": f.source.id.to_string(),
+                    "source_title": f.source.title,
+                    "file": file.unwrap_or(&f.source.path).display().to_string(),
+                    "line": f.mention.line,
+                    "column": f.mention.column,
+                    "text": f.mention.text,
+                    "snippet": f.mention.line_text,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_mentions)?);
+        return Ok(());
+    }
+
+    if found.is_empty() {
+        match targets.as_slice() {
+            [target] if !all => println!("No unlinked mentions of {}", target.id),
+            _ => println!("No unlinked mentions found"),
+        }
+        return Ok(());
+    }
+
+    let mut first = true;
+    for target in &targets {
+        let mentions: Vec<&Found> = found
+            .iter()
+            .filter(|f| f.target.path == target.path)
+            .collect();
+        if mentions.is_empty() {
+            continue;
+        }
+        if !first {
+            println!();
+        }
+        first = false;
+        println!(
+            "🔗 {}: {}",
+            target.id,
+            target.title.as_deref().unwrap_or("No title")
+        );
+        for f in mentions {
+            println!(
+                "   {}:{}:{}: {}",
+                f.source.id, f.mention.line, f.mention.column, f.mention.line_text
+            );
+        }
+    }
+
+    if link {
+        println!();
+        println!(
+            "✅ Linked {} mention(s) in {} note(s)",
+            linked_mentions, linked_notes
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_link_keeps_mention_text() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("1.md"),
+            "# Root\n\nText one mentions Child A here.\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("1a.md"), "# Child A\n").unwrap();
+        let ctx = Context::new(Some(dir.path().to_path_buf())).unwrap();
+        assert!(!ctx.config().linking.use_title_alias);
+
+        handle(&ctx, Some("1a".to_string()), false, Vec::new(), true, false).unwrap();
+
+        let content = fs::read_to_string(dir.path().join("1.md")).unwrap();
+        assert_eq!(
+            content,
+            "# Root\n\nText one mentions [[1a|Child A]] here.\n"
+        );
+    }
+}
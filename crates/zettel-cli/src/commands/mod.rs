@@ -6,10 +6,21 @@
 //
 // MODULE ORGANIZATION:
 // - init: Vault initialization (special case, no context needed)
+// - doctor: Vault health checks
+// - find: Fuzzy lookup of notes by title, alias or ID
 // - id: ID manipulation commands (pure computation)
+// - index: Search index maintenance (with the `search` feature)
 // - note: Note management commands (file creation, editing)
+// - link: Link insertion at editor cursor positions
 // - list: Vault listing and discovery commands
+// - mentions: Unlinked mentions of note titles and aliases
+// - nav: Tree navigation between existing notes
+// - refactor: Shared helpers for renaming subtrees and rewriting links
+// - related: Similar notes and parent suggestions for drafts
 // - search: Content-based search commands
+// - stats: Vault statistics
+// - sync: Regeneration of zettel-managed blocks inside notes
+// - trash: Listing, restoring and emptying deleted notes
 //
 // DESIGN BENEFITS:
 // - Each module can focus on its specific domain
@@ -17,9 +28,21 @@
 // - Clear separation between different types of operations
 // - Modules can have their own helper functions and types
 
+pub mod doctor;
+pub mod find;
 pub mod id;
+#[cfg(feature = "search")]
+pub mod index;
 pub mod init;
+pub mod link;
 pub mod list;
+pub mod mentions;
+pub mod nav;
 pub mod note;
+pub mod refactor;
+pub mod related;
 pub mod search;
+pub mod stats;
+pub mod sync;
 pub mod template;
+pub mod trash;
@@ -0,0 +1,109 @@
+// crates/zettel-cli/src/commands/nav.rs - Tree Navigation Commands
+//
+// These commands answer "where do I go from here?" for editor integrations.
+// Unlike `zettel id next-child` and friends, which compute hypothetical IDs,
+// navigation only ever resolves to notes that exist in the vault.
+//
+// OUTPUT FORMAT:
+// One note per line as `ID<TAB>title<TAB>path`, so keybindings can use
+// `cut -f3` to get the file to open. `--json` prints an array of objects.
+
+use anyhow::Result;
+use zettel_core::id::Id;
+use zettel_core::note::{Note, NoteTree};
+
+use crate::cli::NavCommands;
+use crate::commands::find::select_note;
+use crate::context::Context;
+
+/// Handle tree navigation commands
+pub fn handle(ctx: &Context, target: NavCommands, title: Option<String>, json: bool) -> Result<()> {
+    let tree = ctx.note_tree();
+
+    let (NavCommands::Parent { id }
+    | NavCommands::Children { id }
+    | NavCommands::Siblings { id }
+    | NavCommands::Next { id }
+    | NavCommands::Prev { id }
+    | NavCommands::FirstChild { id }) = &target;
+
+    if id.is_some() && title.is_some() {
+        eprintln!("❌ Give either a note ID or --title, not both");
+        std::process::exit(1);
+    }
+    let parsed_id = select_note(ctx, &tree, id.as_deref(), title.as_deref())?
+        .id
+        .clone();
+    let id_str = parsed_id.to_string();
+
+    // List lookups may legitimately be empty; single lookups name what's missing
+    let (results, missing): (Vec<&Note>, Option<&str>) = match target {
+        NavCommands::Parent { .. } => (
+            tree.parent(&parsed_id).into_iter().collect(),
+            Some("parent"),
+        ),
+        NavCommands::Children { .. } => (tree.children(&parsed_id), None),
+        NavCommands::Siblings { .. } => (
+            tree.siblings(&parsed_id)
+                .into_iter()
+                .filter(|note| note.id != parsed_id)
+                .collect(),
+            None,
+        ),
+        NavCommands::Next { .. } => (
+            tree.next_in_order(&parsed_id).into_iter().collect(),
+            Some("next note"),
+        ),
+        NavCommands::Prev { .. } => (
+            tree.previous_in_order(&parsed_id).into_iter().collect(),
+            Some("previous note"),
+        ),
+        NavCommands::FirstChild { .. } => (
+            first_child(&tree, &parsed_id).into_iter().collect(),
+            Some("first child"),
+        ),
+    };
+
+    if let Some(relation) = missing
+        && results.is_empty()
+    {
+        eprintln!("❌ Note {} has no {}", id_str, relation);
+        std::process::exit(1);
+    }
+
+    print_notes(&results, json)
+}
+
+/// The first existing child, which may not be `Id::first_child` if that
+/// note was deleted
+fn first_child<'a>(tree: &'a NoteTree, id: &Id) -> Option<&'a Note> {
+    tree.children(id).into_iter().next()
+}
+
+/// Print resolved notes as tab-separated lines or JSON
+fn print_notes(notes: &[&Note], json: bool) -> Result<()> {
+    if json {
+        let json_notes: Vec<_> = notes
+            .iter()
+            .map(|note| {
+                serde_json::json!({
+                    "id": note.id.to_string(),
+                    "title": note.title,
+                    "path": note.path.display().to_string()
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_notes)?);
+    } else {
+        for note in notes {
+            println!(
+                "{}\t{}\t{}",
+                note.id,
+                note.title.as_deref().unwrap_or(""),
+                note.path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -4,12 +4,20 @@
 // They coordinate between ID generation, file creation, and editor integration.
 
 use anyhow::Result;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use zettel_core::block::ManagedBlock;
+use zettel_core::frontmatter;
 use zettel_core::id::Id;
-use zettel_core::template::TemplateService;
+use zettel_core::link::{DeadLinkAction, LinkService};
+use zettel_core::note;
+use zettel_core::template::{TemplateContext, TemplateService, TemplateSettings};
 
 use crate::cli::NoteCommands;
+use crate::commands::find::select_note;
+use crate::commands::{refactor, sync, template};
 use crate::context::Context;
-use crate::services::EditorService;
+use crate::services::{EditorService, TrashService, VaultLock};
 
 /// Handle note management commands
 ///
@@ -19,7 +27,45 @@ pub fn handle(ctx: &Context, cmd: NoteCommands) -> Result<()> {
     let id_manager = ctx.get_id_manager();
 
     match cmd {
-        NoteCommands::Create { id, title, open } => {
+        NoteCommands::Create {
+            id,
+            title,
+            open,
+            child_of,
+            sibling_of,
+            template,
+            note_type,
+            vars,
+        } => {
+            let choice = TemplateChoice {
+                name: template,
+                note_type,
+                vars,
+            };
+            if child_of.is_some() || sibling_of.is_some() {
+                // With automatic allocation the only positional is the title
+                if id.is_some() && title.is_some() {
+                    eprintln!(
+                        "❌ Cannot give an explicit ID together with --child-of/--sibling-of"
+                    );
+                    std::process::exit(1);
+                }
+                let title = title.or(id);
+                let (new_id, note_path) = create_allocated_note(
+                    ctx,
+                    child_of.as_deref(),
+                    sibling_of.as_deref(),
+                    title,
+                    &choice,
+                )?;
+
+                println!("{}", new_id);
+                if open {
+                    EditorService::open_file(&note_path, Some(&ctx.config().editor))?;
+                }
+                return Ok(());
+            }
+
             // Get ID from argument or stdin
             let id_str = crate::stdin::read_input_or_stdin(id.as_deref())?;
             let parsed_id = Id::parse(&id_str)?;
@@ -30,120 +76,859 @@ pub fn handle(ctx: &Context, cmd: NoteCommands) -> Result<()> {
                 std::process::exit(1);
             }
 
-            // Generate filename based on configuration
-            let filename = generate_filename(&id_str, &title, ctx);
+            let note_path = create_note(ctx, &parsed_id, &title, &choice, true, &|msg| {
+                println!("{}", msg)
+            })?;
+
+            // Optionally open in editor
+            if open {
+                EditorService::open_file(&note_path, Some(&ctx.config().editor))?;
+            }
+        }
+        NoteCommands::Open { id, title } => {
+            let tree = ctx.note_tree();
+            let note = select_note(ctx, &tree, id.as_deref(), title.as_deref())?;
+            EditorService::open_file(&note.path, Some(&ctx.config().editor))?;
+        }
+
+        NoteCommands::Link { from, to } => {
+            let tree = ctx.note_tree();
+            let (from_note, to_note) = resolve_pair(ctx, &tree, &from, &to)?;
 
-            // Determine parent relationships for bidirectional linking
-            let parent_info = determine_parent_info(&parsed_id, ctx)?;
+            for (source, target) in [(from_note, to_note), (to_note, from_note)] {
+                if add_link(ctx, source, target)? {
+                    println!("🔗 Added link to {} in {}", target.id, source.id);
+                } else {
+                    println!("✓ {} already links to {}", source.id, target.id);
+                }
+            }
+        }
 
-            // Generate note content with parent link (if enabled)
-            let content = generate_note_content(&title, &parent_info, ctx)?;
+        NoteCommands::Unlink { from, to } => {
+            let tree = ctx.note_tree();
+            let (from_note, to_note) = resolve_pair(ctx, &tree, &from, &to)?;
 
-            // Create the file
-            let note_path = ctx.vault_service.create_file(&filename, &content)?;
-            println!("✅ Created note: {}", note_path.display());
+            let mut total = 0;
+            for (source, target) in [(from_note, to_note), (to_note, from_note)] {
+                let removed = remove_links(ctx, source, target)?;
+                if removed > 0 {
+                    println!(
+                        "✂️  Removed {} link(s) to {} from {}",
+                        removed, target.id, source.id
+                    );
+                }
+                total += removed;
+            }
 
-            // Insert child link into parent file (if enabled and parent exists)
-            if let Some(ref parent) = parent_info {
-                insert_child_link_in_parent(&id_str, &title, parent, ctx)?;
+            if total == 0 {
+                println!("✓ {} and {} were not linked", from_note.id, to_note.id);
             }
+        }
 
-            // Optionally open in editor
+        NoteCommands::Delete {
+            id,
+            recursive,
+            reparent,
+        } => delete_note(ctx, &id, recursive, reparent)?,
+
+        NoteCommands::Merge { keep, absorb } => merge_notes(ctx, &keep, &absorb)?,
+
+        NoteCommands::Split { id, level, dry_run } => {
+            split_note(ctx, &id, level as usize, dry_run)?
+        }
+
+        NoteCommands::CreateStubs => create_stubs(ctx)?,
+
+        NoteCommands::Extract {
+            id,
+            line_start,
+            line_end,
+            relation,
+            open,
+        } => {
+            let extracted = extract_selection(ctx, &id, line_start, line_end, &relation)?;
+            println!("{}", extracted.id);
             if open {
-                EditorService::open_file(&note_path, Some(&ctx.config().editor))?;
+                EditorService::open_file(&extracted.path, Some(&ctx.config().editor))?;
             }
         }
-        NoteCommands::Open { id } => {
-            // Get ID from argument or stdin
-            let id_str = crate::stdin::read_input_or_stdin(id.as_deref())?;
-            let parsed_id = Id::parse(&id_str)?;
 
-            // Find and open the note
-            let files = ctx.vault_service.get_vault_files();
-            let mut found_file = None;
-
-            for file in files {
-                if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
-                    if let Some(file_id) = id_manager.extract_from_filename(filename) {
-                        if file_id == parsed_id {
-                            found_file = Some(file);
-                            break;
-                        }
-                    }
-                }
+        NoteCommands::Show { id, title } => {
+            let tree = ctx.note_tree();
+            let note = select_note(ctx, &tree, id.as_deref(), title.as_deref())?;
+
+            let content = ctx.vault_service.read_file(&note.path)?;
+            println!("📄 {}", note.path.display());
+            println!("{}", "─".repeat(50));
+            println!("{}", content);
+        }
+    }
+
+    Ok(())
+}
+
+/// Allocate the next child or sibling ID under the vault lock and create it
+///
+/// The lock is held from ID computation until the file exists, which is what
+/// makes allocation atomic across concurrent invocations. Status messages go
+/// to stderr so stdout carries only the new ID.
+fn create_allocated_note(
+    ctx: &Context,
+    child_of: Option<&str>,
+    sibling_of: Option<&str>,
+    title: Option<String>,
+    choice: &TemplateChoice,
+) -> Result<(Id, std::path::PathBuf)> {
+    let _lock = VaultLock::acquire(ctx.vault_path())?;
+    let id_manager = ctx.get_id_manager();
+
+    let new_id = match (child_of, sibling_of) {
+        (Some(parent), _) => id_manager.next_available_child(&Id::parse(parent)?),
+        (None, Some(sibling)) => id_manager.next_available_sibling(&Id::parse(sibling)?)?,
+        (None, None) => unreachable!("caller checks that an allocation flag is set"),
+    };
+
+    let note_path = create_note(ctx, &new_id, &title, choice, true, &|msg| {
+        eprintln!("{}", msg)
+    })?;
+    Ok((new_id, note_path))
+}
+
+/// Delete a note into the trash, handling descendants and inbound links
+fn delete_note(ctx: &Context, id_str: &str, recursive: bool, reparent: bool) -> Result<()> {
+    let lock = VaultLock::acquire(ctx.vault_path())?;
+
+    let id = Id::parse(id_str)?;
+    let tree = ctx.note_tree();
+    let Some(target) = ctx.lookup(&tree, &id) else {
+        lock.abort(&format!("No note found with ID: {}", id_str));
+    };
+
+    let descendants: Vec<&note::Note> = tree
+        .notes()
+        .iter()
+        .filter(|n| n.id.is_descendant_of(&id))
+        .collect();
+
+    if !descendants.is_empty() && !recursive && !reparent {
+        lock.abort(&format!(
+            "Note {} has {} descendant note(s). Use --recursive to delete them too or --reparent to keep them",
+            id_str,
+            descendants.len()
+        ));
+    }
+
+    let mut doomed = vec![target.clone()];
+    if recursive {
+        doomed.extend(descendants.iter().map(|n| (*n).clone()));
+    }
+
+    let handled = refactor::handle_dead_links(ctx, &tree, &doomed)?;
+    if handled > 0 {
+        let verb = match refactor::dead_link_action(ctx) {
+            DeadLinkAction::Remove => "Removed",
+            DeadLinkAction::Mark => "Marked",
+        };
+        println!("✂️  {} {} link(s) to deleted notes", verb, handled);
+    }
+
+    let mut new_roots = Vec::new();
+    if reparent && !descendants.is_empty() {
+        let id_manager = ctx.get_id_manager();
+        for child_root in refactor::child_subtrees(&tree, &id) {
+            let new_root = match id.parent()? {
+                Some(grandparent) => id_manager.next_available_child(&grandparent),
+                None => id_manager.next_available_sibling(&id)?,
+            };
+
+            let moved = refactor::move_subtree(ctx, &ctx.note_tree(), &child_root, &new_root)?;
+            for m in &moved {
+                println!("↪️  Moved {} to {}", m.old.id, m.new_id);
             }
+            new_roots.push(new_root);
+        }
+    }
 
-            if let Some(file_path) = found_file {
-                EditorService::open_file(&file_path, Some(&ctx.config().editor))?;
-            } else {
-                eprintln!("❌ No note found with ID: {}", id_str);
-                std::process::exit(1);
+    let paths: Vec<std::path::PathBuf> = doomed.iter().map(|n| n.path.clone()).collect();
+    let entry = TrashService::new(ctx.vault_path()).trash_files(id_str, "delete", &paths)?;
+    println!(
+        "🗑️  Moved {} note(s) to trash (restore with: zettel trash restore {})",
+        paths.len(),
+        entry.name
+    );
+
+    // Managed blocks were built from the old tree
+    let tree = ctx.note_tree();
+    if ctx.config().linking.managed_children
+        && let Some(parent) = tree.parent(&id)
+    {
+        sync::update_children_block(ctx, &tree, parent, false)?;
+    }
+    sync::refresh_nav_around(ctx, &id)?;
+    for new_root in &new_roots {
+        sync::refresh_nav_around(ctx, new_root)?;
+    }
+
+    Ok(())
+}
+
+/// Fold the `absorb_str` note into `keep_str`, then trash it
+///
+/// Runs under the vault lock. The kept note's content is written first, then
+/// children are moved and links retargeted, so links inside the appended
+/// body are rewritten along with the rest of the vault.
+fn merge_notes(ctx: &Context, keep_str: &str, absorb_str: &str) -> Result<()> {
+    let lock = VaultLock::acquire(ctx.vault_path())?;
+
+    let keep_id = Id::parse(keep_str)?;
+    let absorb_id = Id::parse(absorb_str)?;
+    if keep_id == absorb_id {
+        lock.abort("Cannot merge a note into itself");
+    }
+    if keep_id.is_descendant_of(&absorb_id) {
+        lock.abort(&format!(
+            "Cannot merge {} into its own descendant {}",
+            absorb_id, keep_id
+        ));
+    }
+
+    let tree = ctx.note_tree();
+    let (Some(keep), Some(absorb)) = (ctx.lookup(&tree, &keep_id), ctx.lookup(&tree, &absorb_id))
+    else {
+        let missing = if tree.contains(&keep_id) {
+            absorb_str
+        } else {
+            keep_str
+        };
+        lock.abort(&format!("No note found with ID: {}", missing));
+    };
+    let (keep, absorb) = (keep.clone(), absorb.clone());
+
+    let keep_content = ctx.vault_service.read_file(&keep.path)?;
+    let absorb_content = ctx.vault_service.read_file(&absorb.path)?;
+
+    let merged = match frontmatter::merge_into(&keep_content, &absorb_content) {
+        Ok(merged) => merged,
+        Err(e) => lock.abort(&format!("Cannot merge frontmatter: {}", e)),
+    };
+
+    // Managed blocks describe the absorbed note's position, not the kept one's
+    let mut body = note::strip_title(&absorb_content);
+    for name in [sync::CHILDREN_BLOCK, sync::NAV_BLOCK] {
+        body = ManagedBlock::new(name).remove(&body);
+    }
+    let heading = absorb.title.clone().unwrap_or_else(|| absorb.filename());
+    let merged = format!(
+        "{}\n\n## {}\n\n{}\n",
+        merged.trim_end(),
+        heading,
+        body.trim()
+    );
+    ctx.vault_service.write_file(&keep.path, &merged)?;
+    println!("📎 Appended {} to {}", absorb.id, keep.id);
+
+    let id_manager = ctx.get_id_manager();
+    let mut new_roots = Vec::new();
+    for child_root in refactor::child_subtrees(&tree, &absorb_id) {
+        let new_root = id_manager.next_available_child(&keep_id);
+        let moved = refactor::move_subtree(ctx, &ctx.note_tree(), &child_root, &new_root)?;
+        for m in &moved {
+            println!("↪️  Moved {} to {}", m.old.id, m.new_id);
+        }
+        new_roots.push(new_root);
+    }
+
+    let retargeted = refactor::redirect_links(ctx, &absorb, &keep)?;
+    if retargeted > 0 {
+        println!("🔗 Retargeted {} link(s) to {}", retargeted, keep.id);
+    }
+    // Links between the two notes would now point the kept note at itself
+    remove_links(ctx, &keep, &keep)?;
+
+    let entry = TrashService::new(ctx.vault_path()).trash_files(
+        absorb_str,
+        "merge",
+        std::slice::from_ref(&absorb.path),
+    )?;
+    println!(
+        "🗑️  Moved {} to trash (restore with: zettel trash restore {})",
+        absorb.path.display(),
+        entry.name
+    );
+
+    let tree = ctx.note_tree();
+    if ctx.config().linking.managed_children {
+        for parent_id in [Some(keep_id.clone()), absorb_id.parent()?]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(parent) = tree.get(&parent_id) {
+                sync::update_children_block(ctx, &tree, parent, false)?;
             }
         }
+    }
+    sync::refresh_nav_around(ctx, &keep_id)?;
+    sync::refresh_nav_around(ctx, &absorb_id)?;
 
-        NoteCommands::Show { id } => {
-            // Get ID from argument or stdin
-            let id_str = crate::stdin::read_input_or_stdin(id.as_deref())?;
-            let parsed_id = Id::parse(&id_str)?;
+    println!("✅ Merged {} into {}", absorb.id, keep.id);
+    Ok(())
+}
 
-            // Find and display the note
-            let files = ctx.vault_service.get_vault_files();
-            let mut found_file = None;
-
-            for file in files {
-                if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
-                    if let Some(file_id) = id_manager.extract_from_filename(filename) {
-                        if file_id == parsed_id {
-                            found_file = Some(file);
-                            break;
-                        }
-                    }
-                }
+/// Look up two distinct existing notes by ID
+fn resolve_pair<'a>(
+    ctx: &Context,
+    tree: &'a note::NoteTree,
+    from: &str,
+    to: &str,
+) -> Result<(&'a note::Note, &'a note::Note)> {
+    let from_id = Id::parse(from)?;
+    let to_id = Id::parse(to)?;
+
+    if from_id == to_id {
+        eprintln!("❌ Cannot link a note to itself");
+        std::process::exit(1);
+    }
+
+    let lookup = |id: &Id, id_str: &str| match ctx.lookup(tree, id) {
+        Some(note) => note,
+        None => {
+            eprintln!("❌ No note found with ID: {}", id_str);
+            std::process::exit(1);
+        }
+    };
+
+    Ok((lookup(&from_id, from), lookup(&to_id, to)))
+}
+
+/// Whether a link target (as found in note text) refers to `note`
+///
+/// Targets can be the full file stem ("1a - Title") or anything the
+/// configured ID matching rule resolves to the note's ID.
+pub fn target_is_note(ctx: &Context, target: &str, note: &note::Note) -> bool {
+    target == note.filename()
+        || ctx
+            .get_id_manager()
+            .extract_from_filename(target)
+            .is_some_and(|id| id == note.id)
+}
+
+/// Resolves link targets to the notes of one tree
+///
+/// Answers what `target_is_note` would for every note at once, for commands
+/// that resolve all links in the vault. File stems are indexed up front and
+/// the ID found in each distinct target is remembered, so the configured
+/// filename patterns aren't rebuilt for every link.
+pub struct LinkResolver<'a> {
+    ctx: &'a Context,
+    tree: &'a note::NoteTree,
+    by_stem: HashMap<String, &'a note::Note>,
+    ids: RefCell<HashMap<String, Option<Id>>>,
+}
+
+impl<'a> LinkResolver<'a> {
+    pub fn new(ctx: &'a Context, tree: &'a note::NoteTree) -> Self {
+        let mut by_stem = HashMap::new();
+        for note in tree.notes() {
+            by_stem.entry(note.filename()).or_insert(note);
+        }
+        Self {
+            ctx,
+            tree,
+            by_stem,
+            ids: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The note a link target refers to, if any
+    pub fn resolve(&self, target: &str) -> Option<&'a note::Note> {
+        if let Some(note) = self.by_stem.get(target) {
+            return Some(note);
+        }
+        let id = self
+            .ids
+            .borrow_mut()
+            .entry(target.to_string())
+            .or_insert_with(|| self.ctx.get_id_manager().extract_from_filename(target))
+            .clone();
+        id.and_then(|id| self.tree.get(&id))
+    }
+}
+
+/// Add a link to `target` in `source` unless one exists; returns whether added
+fn add_link(ctx: &Context, source: &note::Note, target: &note::Note) -> Result<bool> {
+    let content = ctx.vault_service.read_file(&source.path)?;
+    let already_linked = LinkService::find_links(&content)
+        .iter()
+        .any(|link| target_is_note(ctx, &link.target, target));
+    if already_linked {
+        return Ok(false);
+    }
+
+    let config = &ctx.config().linking;
+    let link_text = generate_link_text(
+        &target.id.to_string(),
+        &target.filename(),
+        target.title.as_deref(),
+        ctx,
+    );
+    ctx.vault_service.insert_content_into_file(
+        &source.path.to_string_lossy(),
+        &link_text,
+        &config.insertion_point,
+        config.create_links_section,
+    )?;
+    Ok(true)
+}
+
+/// Remove all links to `target` from `source`; returns how many were removed
+fn remove_links(ctx: &Context, source: &note::Note, target: &note::Note) -> Result<usize> {
+    let content = ctx.vault_service.read_file(&source.path)?;
+    let (new_content, removed) = LinkService::remove_links(&content, |link_target| {
+        target_is_note(ctx, link_target, target)
+    });
+
+    if removed > 0 {
+        ctx.vault_service.write_file(&source.path, &new_content)?;
+    }
+    Ok(removed)
+}
+
+/// Move lines `start..=end` of a note into a new child or sibling note
+///
+/// The new note is prepared first, so template errors stop the extract before
+/// anything is written. The selection is then replaced by a link to the new
+/// note before the note is written, so any managed blocks refreshed during
+/// creation see the final source content. If writing fails, the source is
+/// restored. Everything happens under the vault lock.
+fn extract_selection(
+    ctx: &Context,
+    source_id: &str,
+    start: usize,
+    end: usize,
+    relation: &str,
+) -> Result<note::Note> {
+    let lock = VaultLock::acquire(ctx.vault_path())?;
+
+    let parsed_id = Id::parse(source_id)?;
+    let tree = ctx.note_tree();
+    let Some(source) = ctx.lookup(&tree, &parsed_id) else {
+        lock.abort(&format!("No note found with ID: {}", source_id));
+    };
+
+    let content = ctx.vault_service.read_file(&source.path)?;
+    let Some((range_start, range_end)) = note::line_range(&content, start, end) else {
+        lock.abort(&format!(
+            "Invalid line range {}-{} for note {} ({} lines)",
+            start,
+            end,
+            source_id,
+            content.lines().count()
+        ));
+    };
+
+    let selection = &content[range_start..range_end];
+    let (title, body) = note::split_title(selection);
+    if title.is_none() {
+        lock.abort("Selected lines are empty");
+    }
+
+    let id_manager = ctx.get_id_manager();
+    let new_id = match relation {
+        "sibling" => id_manager.next_available_sibling(&parsed_id)?,
+        _ => id_manager.next_available_child(&parsed_id),
+    };
+
+    let mut prepared = prepare_note(ctx, &new_id, &title, &TemplateChoice::default())?;
+    prepared.file.append_body(&body);
+
+    // Replace the selection with a link to the note about to be created
+    let new_id_str = new_id.to_string();
+    let link = generate_link_text(&new_id_str, &prepared.file.stem(), title.as_deref(), ctx);
+    let line_ending = if selection.ends_with('\n') { "\n" } else { "" };
+    let new_source = format!(
+        "{}{}{}{}",
+        &content[..range_start],
+        link,
+        line_ending,
+        &content[range_end..]
+    );
+    ctx.vault_service.write_file(&source.path, &new_source)?;
+
+    // The source already links to a new child, so don't append another link
+    let link_in_parent = relation == "sibling";
+    let written = write_note(
+        ctx,
+        &new_id,
+        &title,
+        &prepared.file,
+        &prepared.parent_info,
+        link_in_parent,
+        &|msg| eprintln!("{}", msg),
+    );
+    let note_path = match written {
+        Ok(path) => path,
+        Err(e) => {
+            roll_back(ctx, &source.path, &content, &[&prepared.file]);
+            return Err(e);
+        }
+    };
+
+    eprintln!("✂️  Extracted lines {}-{} of {}", start, end, source_id);
+    Ok(note::Note::new(new_id, note_path, title))
+}
+
+/// Turn each heading section of a note into a new child note
+///
+/// IDs are allocated in order under the vault lock: the first free child,
+/// then the next free sibling of each new note. Every new note is prepared
+/// before the source is rewritten, and a failed write restores the source, so
+/// no section is lost. With `dry_run` the new files and the rewritten source
+/// are printed and nothing is written.
+fn split_note(ctx: &Context, source_id: &str, level: usize, dry_run: bool) -> Result<()> {
+    let lock = VaultLock::acquire(ctx.vault_path())?;
+
+    let parsed_id = Id::parse(source_id)?;
+    let tree = ctx.note_tree();
+    let Some(source) = ctx.lookup(&tree, &parsed_id) else {
+        lock.abort(&format!("No note found with ID: {}", source_id));
+    };
+
+    let content = ctx.vault_service.read_file(&source.path)?;
+    let sections = note::sections(&content, level);
+    if sections.is_empty() {
+        lock.abort(&format!(
+            "Note {} has no {} headings to split at",
+            source_id,
+            "#".repeat(level)
+        ));
+    }
+
+    // Allocate IDs and prepare every new note before anything is written
+    let id_manager = ctx.get_id_manager();
+    let mut planned = Vec::new();
+    let mut next_id = id_manager.next_available_child(&parsed_id);
+    for section in &sections {
+        let title = Some(section.title.clone());
+        let mut prepared = prepare_note(ctx, &next_id, &title, &TemplateChoice::default())?;
+        prepared.file.append_body(&section.body);
+        let link = generate_link_text(
+            &next_id.to_string(),
+            &prepared.file.stem(),
+            title.as_deref(),
+            ctx,
+        );
+
+        let sibling = id_manager.next_available_sibling(&next_id)?;
+        planned.push((next_id, title, prepared, link));
+        next_id = sibling;
+    }
+
+    let mut new_source = String::new();
+    let mut copied = 0;
+    for (section, (_, _, _, link)) in sections.iter().zip(&planned) {
+        new_source.push_str(&content[copied..section.start]);
+        new_source.push_str(link);
+        new_source.push('\n');
+        copied = section.end;
+    }
+    new_source.push_str(&content[copied..]);
+
+    if dry_run {
+        for (_, _, prepared, _) in &planned {
+            println!("📄 {} (new)", prepared.file.path);
+            println!("{}", "─".repeat(50));
+            println!("{}", prepared.file.content);
+        }
+        println!("📄 {}", source.path.display());
+        println!("{}", "─".repeat(50));
+        println!("{}", new_source);
+        return Ok(());
+    }
+
+    // The source links to every new child itself, as with extract
+    ctx.vault_service.write_file(&source.path, &new_source)?;
+    for (new_id, title, prepared, _) in &planned {
+        let written = write_note(
+            ctx,
+            new_id,
+            title,
+            &prepared.file,
+            &prepared.parent_info,
+            false,
+            &|msg| println!("{}", msg),
+        );
+        if let Err(e) = written {
+            let files: Vec<&NoteFile> = planned.iter().map(|(_, _, p, _)| &p.file).collect();
+            roll_back(ctx, &source.path, &content, &files);
+            return Err(e);
+        }
+    }
+
+    println!("✂️  Split {} into {} note(s)", source_id, planned.len());
+    Ok(())
+}
+
+/// Create stub notes for every missing ancestor and link them into the tree
+///
+/// Stubs are created parents-first, so each stub links to an existing parent
+/// like a regularly created note. Notes that were already waiting below a
+/// stub get linked to it in both directions, per the [linking] settings.
+fn create_stubs(ctx: &Context) -> Result<()> {
+    let lock = VaultLock::acquire(ctx.vault_path())?;
+
+    let missing = ctx.note_tree().missing_ancestors();
+    if missing.is_empty() {
+        println!("✅ No missing parents");
+        return Ok(());
+    }
+
+    let config = ctx.config();
+    let stub_template = if config.template.stub_file.is_empty() {
+        None
+    } else {
+        match ctx
+            .vault_service
+            .read_template_file(&config.template.stub_file)
+        {
+            Ok(template) => Some(template),
+            Err(e) => lock.abort(&format!("Cannot read stub template: {}", e)),
+        }
+    };
+
+    let report = |msg: &str| println!("{}", msg);
+    for id in &missing {
+        let id_str = id.to_string();
+        let parent_info = determine_parent_info(id, ctx)?;
+        let backlink = match &parent_info {
+            Some(parent) if config.linking.insert_in_child => {
+                generate_link_text(&parent.id, &parent.filename, parent.title.as_deref(), ctx)
             }
+            _ => String::new(),
+        };
+        let content = TemplateService::generate_stub(stub_template.as_deref(), &id_str, &backlink);
+        let file = NoteFile {
+            path: note_path(id, &None, &TemplateSettings::default(), ctx)?,
+            content,
+        };
+        write_note(ctx, id, &None, &file, &parent_info, true, &report)?;
 
-            if let Some(file_path) = found_file {
-                let content = ctx.vault_service.read_file(&file_path)?;
-                println!("📄 {}", file_path.display());
-                println!("{}", "─".repeat(50));
-                println!("{}", content);
-            } else {
-                eprintln!("❌ No note found with ID: {}", id_str);
-                std::process::exit(1);
+        // Link the existing notes that were orphaned by the gap
+        let tree = ctx.note_tree();
+        let Some(stub) = tree.get(id) else {
+            continue;
+        };
+        for child in tree.children(id) {
+            if config.linking.insert_in_child {
+                add_link(ctx, child, stub)?;
             }
+            if config.linking.insert_in_parent && !config.linking.managed_children {
+                add_link(ctx, stub, child)?;
+            }
+        }
+        if config.linking.insert_in_parent && config.linking.managed_children {
+            sync::update_children_block(ctx, &tree, stub, false)?;
         }
     }
 
+    println!("✅ Created {} stub note(s)", missing.len());
     Ok(())
 }
 
+/// Create a note file for an ID known to be free, with all linking side effects
+///
+/// Generates the filename and content (template, backlink), writes the file,
+/// links it from its parent and refreshes navigation blocks. `choice` holds
+/// the template options given on the command line. `report`
+/// receives human-readable status lines. `link_in_parent` can be turned off
+/// by callers that already placed a link in the parent themselves; a managed
+/// children block is still regenerated.
+pub fn create_note(
+    ctx: &Context,
+    id: &Id,
+    title: &Option<String>,
+    choice: &TemplateChoice,
+    link_in_parent: bool,
+    report: &dyn Fn(&str),
+) -> Result<std::path::PathBuf> {
+    let prepared = prepare_note(ctx, id, title, choice)?;
+    write_note(
+        ctx,
+        id,
+        title,
+        &prepared.file,
+        &prepared.parent_info,
+        link_in_parent,
+        report,
+    )
+}
+
+/// A new note whose file is ready to be written
+struct PreparedNote {
+    file: NoteFile,
+    parent_info: Option<ParentInfo>,
+}
+
+/// Work out a new note's path and content without writing anything
+///
+/// Everything that depends on the templates (choice, rendering, filename
+/// pattern) can fail here, before commands that also rewrite other notes
+/// have touched them.
+fn prepare_note(
+    ctx: &Context,
+    id: &Id,
+    title: &Option<String>,
+    choice: &TemplateChoice,
+) -> Result<PreparedNote> {
+    // Determine parent relationships for bidirectional linking
+    let parent_info = determine_parent_info(id, ctx)?;
+
+    // Generate note content with parent link (if enabled)
+    let note_template = load_note_template(id, choice, ctx)?;
+    let content = generate_note_content(id, title, &parent_info, choice, &note_template, ctx)?;
+
+    let file = NoteFile {
+        path: note_path(id, title, &note_template.settings, ctx)?,
+        content,
+    };
+    Ok(PreparedNote { file, parent_info })
+}
+
+/// The file of a note about to be created
+struct NoteFile {
+    /// Path relative to the vault root
+    path: String,
+    content: String,
+}
+
+impl NoteFile {
+    /// Filename without directory and extension, as used in links
+    fn stem(&self) -> String {
+        let filename = self.path.rsplit('/').next().unwrap_or(&self.path);
+        filename.strip_suffix(".md").unwrap_or(filename).to_string()
+    }
+
+    /// Add text moved in from another note below the generated content
+    fn append_body(&mut self, body: &str) {
+        if !body.is_empty() {
+            self.content = format!("{}\n\n{}\n", self.content.trim_end(), body);
+        }
+    }
+}
+
+/// Undo an extract or split whose new notes could not all be written
+///
+/// Puts the source note's original content back and removes the new notes
+/// written so far. Best effort, since it runs while reporting another error.
+fn roll_back(ctx: &Context, source: &std::path::Path, original: &str, files: &[&NoteFile]) {
+    let _ = ctx.vault_service.write_file(source, original);
+    for file in files {
+        let path = ctx.vault_path().join(&file.path);
+        if path.exists() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Write a new note with ready-made content and link it into the tree
+fn write_note(
+    ctx: &Context,
+    id: &Id,
+    title: &Option<String>,
+    file: &NoteFile,
+    parent_info: &Option<ParentInfo>,
+    link_in_parent: bool,
+    report: &dyn Fn(&str),
+) -> Result<std::path::PathBuf> {
+    let id_str = id.to_string();
+
+    // Create the file
+    let note_path = ctx.vault_service.create_file(&file.path, &file.content)?;
+    report(&format!("✅ Created note: {}", note_path.display()));
+
+    // Insert child link into parent file (if enabled and parent exists)
+    if let Some(parent) = parent_info
+        && (link_in_parent || ctx.config().linking.managed_children)
+    {
+        let stem = note_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| id_str.clone());
+        insert_child_link_in_parent(&id_str, &stem, title, parent, ctx, report)?;
+    }
+
+    // Keep breadcrumb and sibling navigation in step with the tree
+    sync::refresh_nav_around(ctx, id)?;
+
+    Ok(note_path)
+}
+
 /// Generate filename based on configuration and optional title
 ///
 /// Business rules for filename generation:
-/// - Always starts with ID
+/// - A template's filename pattern wins when it declares one
+/// - Otherwise always starts with ID
 /// - Includes title if configured and provided
 /// - Uses configured separator between ID and title
 /// - Always ends with .md extension
-fn generate_filename(id: &str, title: &Option<String>, ctx: &Context) -> String {
+fn generate_filename(
+    id: &Id,
+    title: &Option<String>,
+    settings: &TemplateSettings,
+    ctx: &Context,
+) -> Result<String> {
     let config = ctx.config();
 
-    match title {
+    if settings.filename.is_some() {
+        let mut context = TemplateContext::new(title.as_deref().unwrap_or(""), "");
+        context.set_time(chrono::Local::now().naive_local());
+        context.set("id", id.to_string());
+        if let Ok(Some(parent)) = id.parent() {
+            context.set("parent_id", parent.to_string());
+        }
+        context.set("type", settings.note_type.as_deref().unwrap_or_default());
+        if let Some(stem) = settings.filename(&context, &config.id.separator)? {
+            return Ok(format!("{}.md", stem.trim_end_matches(".md")));
+        }
+    }
+
+    Ok(match title {
         Some(t) if config.note.add_title => {
             format!("{}{}{}.md", id, config.id.separator, t)
         }
         _ => format!("{}.md", id),
-    }
+    })
+}
+
+/// Vault-relative path of a new note: its filename, in the template's
+/// directory when it declares one
+fn note_path(
+    id: &Id,
+    title: &Option<String>,
+    settings: &TemplateSettings,
+    ctx: &Context,
+) -> Result<String> {
+    let filename = generate_filename(id, title, settings, ctx)?;
+    Ok(match settings.directory.as_deref().map(str::trim) {
+        Some(directory) if !directory.is_empty() => {
+            format!("{}/{}", directory.trim_end_matches('/'), filename)
+        }
+        _ => filename,
+    })
 }
 
 /// Generate note content using template system or built-in format
 ///
 /// This implements the core content generation logic:
-/// 1. Check if templates are enabled and configured
-/// 2. If using templates: read, validate, and process template
-/// 3. If not using templates: use built-in format
+/// 1. Fill the template chosen by `load_note_template` in, or
+/// 2. Use the built-in format when there is none
+/// 3. Add the template's declared tags and type to the frontmatter
 /// 4. Handle all error cases with helpful messages
 fn generate_note_content(
+    id: &Id,
     title: &Option<String>,
     parent_info: &Option<ParentInfo>,
+    choice: &TemplateChoice,
+    note_template: &NoteTemplate,
     ctx: &Context,
 ) -> Result<String> {
     let config = ctx.config();
@@ -159,15 +944,97 @@ fn generate_note_content(
         String::new()
     };
 
-    if TemplateService::should_use_template(&config.template) {
-        generate_template_content(title_str, &backlink_content, ctx)
+    let mut context =
+        template::template_context(ctx, title_str, &backlink_content, Some(id), &choice.vars);
+    let settings = &note_template.settings;
+    context.set("type", settings.note_type.as_deref().unwrap_or_default());
+
+    let partials = |name: &str| template::read_partial(ctx, note_template.path.as_deref(), name);
+    let content =
+        TemplateService::generate_content(note_template.content.as_deref(), &context, &partials)?;
+    Ok(settings.apply(&content)?)
+}
+
+/// The template a new note is generated from
+///
+/// `content` is `None` for the built-in format. `settings` are the ones the
+/// template declares, with the note type from `--type` taking precedence.
+struct NoteTemplate {
+    path: Option<String>,
+    content: Option<String>,
+    settings: TemplateSettings,
+}
+
+/// Choose, read and validate the template for a new note
+///
+/// Handles the complete template workflow:
+/// 1. Choose: an explicit --template wins over the rules, which only
+///    apply when templates are enabled
+/// 2. Resolve template file path from the name or configuration
+/// 3. Read template content from disk
+/// 4. Validate template against requirements
+/// 5. Split off the settings in its frontmatter
+fn load_note_template(id: &Id, choice: &TemplateChoice, ctx: &Context) -> Result<NoteTemplate> {
+    let config = &ctx.config().template;
+
+    let name = choice.name.as_deref().or_else(|| {
+        config
+            .enabled
+            .then(|| TemplateService::select_template(config, id, choice.note_type.as_deref()))
+            .flatten()
+    });
+    let mut note_template = if name.is_some() || TemplateService::should_use_template(config) {
+        // Step 2: Resolve template file path
+        let template_path = TemplateService::resolve_template_path(config, name)
+            .map_err(|e| anyhow::anyhow!("Template configuration error: {}", e))?;
+
+        // Step 3: Read template content from disk
+        let template_content = ctx
+            .vault_service
+            .read_template_file(&template_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read template: {}", e))?;
+
+        // Step 4: Validate template content
+        let validation_result = TemplateService::validate_template(&template_content, config);
+        if !validation_result.valid {
+            return Err(anyhow::anyhow!(
+                "Template validation failed: {}",
+                validation_result
+                    .message
+                    .unwrap_or_else(|| "Unknown validation error".to_string())
+            ));
+        }
+
+        // Step 5: Settings are for note creation, not part of the note
+        let (settings, content) = TemplateSettings::extract(&template_content)?;
+        NoteTemplate {
+            path: Some(template_path),
+            content: Some(content),
+            settings,
+        }
     } else {
-        Ok(TemplateService::generate_content(
-            None,
-            title_str,
-            &backlink_content,
-        ))
+        NoteTemplate {
+            path: None,
+            content: None,
+            settings: TemplateSettings::default(),
+        }
+    };
+
+    if let Some(note_type) = &choice.note_type {
+        note_template.settings.note_type = Some(note_type.clone());
     }
+    Ok(note_template)
+}
+
+/// Template options of `note create`
+#[derive(Debug, Default)]
+pub struct TemplateChoice {
+    /// Named template from the template directory (`--template`)
+    pub name: Option<String>,
+    /// Note type matched by template rules (`--type`)
+    pub note_type: Option<String>,
+    /// Template variables (`--var`)
+    pub vars: Vec<(String, String)>,
 }
 
 /// Information about the parent note for bidirectional linking
@@ -182,27 +1049,15 @@ struct ParentInfo {
 /// Determine parent information for bidirectional linking
 fn determine_parent_info(new_id: &Id, ctx: &Context) -> Result<Option<ParentInfo>> {
     if let Ok(Some(parent_id)) = new_id.parent() {
-        let files = ctx.vault_service.get_vault_files();
-        let id_manager = ctx.get_id_manager();
-
-        for file in files {
-            if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
-                if let Some(file_id) = id_manager.extract_from_filename(filename) {
-                    if file_id == parent_id {
-                        let title = extract_title_from_file(&file, ctx).ok();
-                        return Ok(Some(ParentInfo {
-                            id: parent_id.to_string(),
-                            filename: file
-                                .file_stem()
-                                .and_then(|s| s.to_str())
-                                .unwrap_or(filename)
-                                .to_string(),
-                            path: file.to_string_lossy().to_string(),
-                            title,
-                        }));
-                    }
-                }
-            }
+        let tree = ctx.note_tree();
+        if let Some(parent) = ctx.lookup(&tree, &parent_id) {
+            let title = extract_title_from_file(&parent.path, ctx).ok();
+            return Ok(Some(ParentInfo {
+                id: parent_id.to_string(),
+                filename: parent.filename(),
+                path: parent.path.to_string_lossy().to_string(),
+                title,
+            }));
         }
     }
     Ok(None)
@@ -212,10 +1067,8 @@ fn determine_parent_info(new_id: &Id, ctx: &Context) -> Result<Option<ParentInfo
 fn extract_title_from_file(file: &std::path::Path, ctx: &Context) -> Result<String> {
     let content = ctx.vault_service.read_file(file)?;
 
-    for line in content.lines().take(5) {
-        if let Some(title) = line.strip_prefix("# ") {
-            return Ok(title.trim().to_string());
-        }
+    if let Some(title) = note::title_from_content(&content) {
+        return Ok(title);
     }
 
     let filename = file
@@ -224,23 +1077,22 @@ fn extract_title_from_file(file: &std::path::Path, ctx: &Context) -> Result<Stri
         .unwrap_or("Untitled");
 
     let id_manager = ctx.get_id_manager();
-    let id = id_manager
-        .extract_from_filename(filename)
-        .map(|id| id.to_string())
-        .unwrap_or_default();
-    let title = filename.strip_prefix(&id.to_string()).unwrap_or(filename);
+    let title = match id_manager.extract_from_filename(filename) {
+        Some(id) => note::title_from_filename(filename, &id).unwrap_or_default(),
+        None => filename.to_string(),
+    };
 
-    Ok(title
-        .trim_start_matches(|c: char| !c.is_alphanumeric())
-        .to_string())
+    Ok(title)
 }
 
 /// Insert child link into parent file
 fn insert_child_link_in_parent(
     child_id: &str,
+    child_filename: &str,
     child_title: &Option<String>,
     parent: &ParentInfo,
     ctx: &Context,
+    report: &dyn Fn(&str),
 ) -> Result<()> {
     let config = &ctx.config().linking;
 
@@ -248,11 +1100,18 @@ fn insert_child_link_in_parent(
         return Ok(());
     }
 
-    let child_filename = generate_filename(child_id, child_title, ctx);
-    let child_filename_base = child_filename
-        .strip_suffix(".md")
-        .unwrap_or(&child_filename);
-    let link_text = generate_link_text(child_id, child_filename_base, child_title.as_deref(), ctx);
+    // Managed mode: rebuild the parent's whole children block from the vault
+    if config.managed_children {
+        let tree = ctx.note_tree();
+        let parent_id = Id::parse(&parent.id)?;
+        if let Some(parent_note) = tree.get(&parent_id) {
+            sync::update_children_block(ctx, &tree, parent_note, true)?;
+            report(&format!("🔗 Updated children of parent {}", parent.id));
+        }
+        return Ok(());
+    }
+
+    let link_text = generate_link_text(child_id, child_filename, child_title.as_deref(), ctx);
 
     ctx.vault_service.insert_content_into_file(
         &parent.path,
@@ -261,64 +1120,176 @@ fn insert_child_link_in_parent(
         config.create_links_section,
     )?;
 
-    println!(
+    report(&format!(
         "🔗 Added link to child {} in parent {}",
         child_id, parent.id
-    );
+    ));
     Ok(())
 }
 
 /// Generate link text based on configuration
 fn generate_link_text(id: &str, filename: &str, title: Option<&str>, ctx: &Context) -> String {
-    let config = &ctx.config().linking;
+    LinkService::format_link(&ctx.config().linking, id, filename, title)
+}
 
-    if let Some(format_template) = &config.format {
-        format_template
-            .replace("{id}", id)
-            .replace("{filename}", filename)
-            .replace("{title}", title.unwrap_or(filename))
-    } else if config.use_title_alias && title.is_some() {
-        format!("[[{}|{}]]", filename, title.unwrap())
-    } else {
-        format!("[[{}]]", filename)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    /// A vault holding `notes` and, when given, a default template
+    fn vault(notes: &[(&str, &str)], template: Option<&str>) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".zettel")).unwrap();
+        if let Some(template) = template {
+            fs::create_dir(dir.path().join("templates")).unwrap();
+            fs::write(dir.path().join("templates/note.md"), template).unwrap();
+            fs::write(
+                dir.path().join(".zettel/config.toml"),
+                "[template]\nenabled = true\nfile = \"templates/note.md\"\n",
+            )
+            .unwrap();
+        }
+        for (name, content) in notes {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        dir
     }
-}
 
-/// Generate content using template file
-///
-/// Handles the complete template workflow:
-/// 1. Resolve template file path from configuration
-/// 2. Read template content from disk
-/// 3. Validate template against requirements
-/// 4. Generate final content with placeholder substitution
-fn generate_template_content(title: &str, backlink: &str, ctx: &Context) -> Result<String> {
-    let config = &ctx.config().template;
+    fn context(dir: &Path) -> Context {
+        Context::new(Some(dir.to_path_buf())).unwrap()
+    }
 
-    // Step 1: Resolve template file path
-    let template_path = TemplateService::resolve_template_path(config)
-        .map_err(|e| anyhow::anyhow!("Template configuration error: {}", e))?;
-
-    // Step 2: Read template content from disk
-    let template_content = ctx
-        .vault_service
-        .read_template_file(&template_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read template: {}", e))?;
-
-    // Step 3: Validate template content
-    let validation_result = TemplateService::validate_template(&template_content, config);
-    if !validation_result.valid {
-        return Err(anyhow::anyhow!(
-            "Template validation failed: {}",
-            validation_result
-                .message
-                .unwrap_or_else(|| "Unknown validation error".to_string())
-        ));
+    #[test]
+    fn test_delete_and_restore() {
+        let dir = vault(
+            &[
+                ("1.md", "# One\n\n[[1a]]\n"),
+                ("2.md", "# Two\n\nSee [[1a]].\n"),
+            ],
+            None,
+        );
+        fs::create_dir(dir.path().join("literature")).unwrap();
+        fs::write(dir.path().join("literature/1a.md"), "# Child\n\n[[1]]\n").unwrap();
+        let ctx = context(dir.path());
+
+        delete_note(&ctx, "1", true, false).unwrap();
+        assert!(!dir.path().join("1.md").exists());
+        assert!(!dir.path().join("literature/1a.md").exists());
+        assert!(
+            !fs::read_to_string(dir.path().join("2.md"))
+                .unwrap()
+                .contains("[[1a]]")
+        );
+        assert!(!dir.path().join(".zettel/lock").exists());
+
+        let trash = TrashService::new(dir.path());
+        let entry = trash.find("1").unwrap();
+        trash.restore(&entry).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("literature/1a.md")).unwrap(),
+            "# Child\n\n[[1]]\n"
+        );
+        assert!(dir.path().join("1.md").exists());
     }
 
-    // Step 4: Generate final content
-    Ok(TemplateService::generate_content(
-        Some(&template_content),
-        title,
-        backlink,
-    ))
+    #[test]
+    fn test_split_note() {
+        let dir = vault(
+            &[(
+                "1.md",
+                "# One\n\nIntro\n\n## A\nAlpha\n\n## B\nBeta\n\n[[2]]\n",
+            )],
+            None,
+        );
+        let ctx = context(dir.path());
+
+        split_note(&ctx, "1", 2, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("1.md")).unwrap(),
+            "# One\n\nIntro\n\n[[1a]]\n\n[[1b]]\n\n[[2]]\n"
+        );
+        let second = fs::read_to_string(dir.path().join("1b.md")).unwrap();
+        assert!(second.starts_with("# B\n"));
+        assert!(second.trim_end().ends_with("Beta"));
+        assert!(!second.contains("[[2]]"));
+    }
+
+    #[test]
+    fn test_split_keeps_source_on_template_error() {
+        let source = "# One\n\n## A\nAlpha\n\n## B\nBeta\n";
+        let dir = vault(
+            &[("1.md", source)],
+            Some("# {{title}}\n{{link}}\n{{> nope}}\n"),
+        );
+        let ctx = context(dir.path());
+
+        assert!(split_note(&ctx, "1", 2, false).is_err());
+        assert_eq!(fs::read_to_string(dir.path().join("1.md")).unwrap(), source);
+        assert!(!dir.path().join("1a.md").exists());
+        assert!(!dir.path().join(".zettel/lock").exists());
+    }
+
+    #[test]
+    fn test_merge_notes() {
+        let dir = vault(
+            &[
+                ("1.md", "# One\n\nFirst\n"),
+                ("2.md", "Status: draft\n# Two\n\nSecond\n"),
+                ("3.md", "# Three\n\nSee [[2]].\n"),
+            ],
+            None,
+        );
+        let ctx = context(dir.path());
+
+        merge_notes(&ctx, "1", "2").unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("1.md")).unwrap(),
+            "# One\n\nFirst\n\n## Two\n\nStatus: draft\n\nSecond\n"
+        );
+        assert!(!dir.path().join("2.md").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("3.md")).unwrap(),
+            "# Three\n\nSee [[1]].\n"
+        );
+        assert_eq!(
+            TrashService::new(dir.path()).find("2").unwrap().reason,
+            "merge"
+        );
+    }
+
+    #[test]
+    fn test_extract_selection() {
+        let dir = vault(&[("1.md", "# One\n\nline a\nline b\n")], None);
+        let ctx = context(dir.path());
+
+        let new_note = extract_selection(&ctx, "1", 3, 4, "child").unwrap();
+        assert_eq!(new_note.id.to_string(), "1a");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("1.md")).unwrap(),
+            "# One\n\n[[1a]]\n"
+        );
+        let created = fs::read_to_string(dir.path().join("1a.md")).unwrap();
+        assert!(created.starts_with("# line a\n"));
+        assert!(created.contains("[[1]]"));
+        assert!(created.trim_end().ends_with("line b"));
+        assert!(!dir.path().join(".zettel/lock").exists());
+    }
+
+    #[test]
+    fn test_extract_keeps_source_on_template_error() {
+        let source = "# One\n\nline a\nline b\n";
+        let dir = vault(
+            &[("1.md", source)],
+            Some("# {{title}}\n{{link}}\n{{> nope}}\n"),
+        );
+        let ctx = context(dir.path());
+
+        let error = extract_selection(&ctx, "1", 3, 3, "child").unwrap_err();
+        assert!(error.to_string().contains("nope"));
+        assert_eq!(fs::read_to_string(dir.path().join("1.md")).unwrap(), source);
+        assert!(!dir.path().join("1a.md").exists());
+        assert!(!dir.path().join(".zettel/lock").exists());
+    }
 }
@@ -0,0 +1,207 @@
+// crates/zettel-cli/src/commands/refactor.rs - Vault Restructuring Helpers
+//
+// Operations that change which notes exist or what they are called (delete,
+// merge, dedupe) share the same chores: renaming whole subtrees of files and
+// keeping every link in the vault pointing at the right place. These helpers
+// do that work; the commands decide what to move and report the results.
+//
+// DESIGN PRINCIPLES:
+// - Link rewriting goes through `LinkService`, never ad-hoc string replace
+// - Callers hold the vault lock while restructuring
+// - Conflicts are detected before any file is touched
+
+use anyhow::Result;
+use std::path::PathBuf;
+use zettel_core::id::Id;
+use zettel_core::link::{DeadLinkAction, LinkService};
+use zettel_core::note::{self, Note, NoteTree};
+
+use crate::commands::note::target_is_note;
+use crate::context::Context;
+
+/// A note that was renamed by `move_subtree`
+#[derive(Debug, Clone)]
+pub struct MovedNote {
+    pub old: Note,
+    pub new_id: Id,
+    pub new_path: PathBuf,
+}
+
+/// Give `from` and all its existing descendants new IDs under `to`
+///
+/// Files are renamed (keeping any title after the ID) and every link in
+/// the vault that pointed at a moved note is rewritten. IDs below the new
+/// root follow `Id::rebase`, so 1a2b moved from 1a2 to 1a5 becomes 1a5b.
+pub fn move_subtree(ctx: &Context, tree: &NoteTree, from: &Id, to: &Id) -> Result<Vec<MovedNote>> {
+    let mut moves = Vec::new();
+    for existing in tree.notes() {
+        let Some(new_id) = existing.id.rebase(from, to) else {
+            continue;
+        };
+
+        let new_path = renamed_path(existing, &new_id);
+        moves.push(MovedNote {
+            old: existing.clone(),
+            new_id,
+            new_path,
+        });
+    }
+
+    // Refuse to move onto notes that aren't part of the move themselves
+    for moved in &moves {
+        let occupied = tree
+            .get(&moved.new_id)
+            .is_some_and(|other| !moves.iter().any(|m| m.old.path == other.path));
+        if occupied {
+            return Err(anyhow::anyhow!(
+                "Cannot move {} to {}: a note with that ID already exists",
+                moved.old.id,
+                moved.new_id
+            ));
+        }
+    }
+
+    // Rename deepest notes first so a parent never lands on a pending child path
+    for moved in moves.iter().rev() {
+        ctx.vault_service
+            .rename_file(&moved.old.path, &moved.new_path)?;
+    }
+
+    retarget_links(ctx, &moves)?;
+    Ok(moves)
+}
+
+/// Give a single note a new ID, leaving notes below its old ID alone
+///
+/// Meant for notes whose ID is shared with another file: only links naming
+/// this file ("1a - Title") are retargeted, since bare-ID links ("1a") can't
+/// be told apart and stay with the other file.
+pub fn rename_note(ctx: &Context, existing: &Note, new_id: &Id) -> Result<MovedNote> {
+    let new_path = renamed_path(existing, new_id);
+    ctx.vault_service.rename_file(&existing.path, &new_path)?;
+
+    let old_stem = existing.filename();
+    let new_stem = new_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let rename = |target: &str| (target == old_stem).then(|| new_stem.clone());
+    for path in ctx.vault_service.get_vault_files() {
+        let content = ctx.vault_service.read_file(&path)?;
+        let (new_content, changed) = LinkService::retarget_links(&content, rename);
+        if changed > 0 {
+            ctx.vault_service.write_file(&path, &new_content)?;
+        }
+    }
+
+    Ok(MovedNote {
+        old: existing.clone(),
+        new_id: new_id.clone(),
+        new_path,
+    })
+}
+
+/// The path a note would have under a new ID, keeping title and extension
+fn renamed_path(existing: &Note, new_id: &Id) -> PathBuf {
+    let new_stem = note::rename_stem(&existing.filename(), &existing.id, new_id);
+    let extension = existing
+        .path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("md");
+    existing
+        .path
+        .with_file_name(format!("{}.{}", new_stem, extension))
+}
+
+/// The child-level IDs that head subtrees below `id`
+///
+/// A child ID is included when any descendant exists, even if the child
+/// itself is missing (1a2a3 alone yields 1a2a for 1a2).
+pub fn child_subtrees(tree: &NoteTree, id: &Id) -> Vec<Id> {
+    let depth = id.depth() + 1;
+    let mut roots: Vec<Id> = tree
+        .notes()
+        .iter()
+        .filter(|n| n.id.is_descendant_of(id))
+        .filter_map(|n| Id::new(n.id.components()[..depth].to_vec()).ok())
+        .collect();
+    roots.dedup();
+    roots
+}
+
+/// Point every link to `from` at `to` instead, across the whole vault
+pub fn redirect_links(ctx: &Context, from: &Note, to: &Note) -> Result<usize> {
+    let redirect = MovedNote {
+        old: from.clone(),
+        new_id: to.id.clone(),
+        new_path: to.path.clone(),
+    };
+    retarget_links(ctx, &[redirect])
+}
+
+/// Rewrite links to moved notes across the whole vault
+fn retarget_links(ctx: &Context, moves: &[MovedNote]) -> Result<usize> {
+    let rename = |target: &str| {
+        moves.iter().find_map(|moved| {
+            if !target_is_note(ctx, target, &moved.old) {
+                return None;
+            }
+            // Keep the link's style: bare-ID links stay bare IDs
+            if target == moved.old.id.to_string() {
+                Some(moved.new_id.to_string())
+            } else {
+                moved
+                    .new_path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.to_string())
+            }
+        })
+    };
+
+    let mut total = 0;
+    for path in ctx.vault_service.get_vault_files() {
+        let content = ctx.vault_service.read_file(&path)?;
+        let (new_content, changed) = LinkService::retarget_links(&content, rename);
+        if changed > 0 {
+            ctx.vault_service.write_file(&path, &new_content)?;
+            total += changed;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Remove or mark (per `linking.on_delete`) links to notes that are going away
+///
+/// The dead notes themselves are skipped. Returns the number of links handled.
+pub fn handle_dead_links(ctx: &Context, tree: &NoteTree, dead: &[Note]) -> Result<usize> {
+    let action = dead_link_action(ctx);
+    let is_dead = |target: &str| dead.iter().any(|note| target_is_note(ctx, target, note));
+
+    let mut total = 0;
+    for existing in tree.notes() {
+        if dead.iter().any(|note| note.path == existing.path) {
+            continue;
+        }
+
+        let content = ctx.vault_service.read_file(&existing.path)?;
+        let (new_content, handled) = LinkService::handle_dead_links(&content, action, is_dead);
+        if handled > 0 {
+            ctx.vault_service.write_file(&existing.path, &new_content)?;
+            total += handled;
+        }
+    }
+
+    Ok(total)
+}
+
+/// The configured treatment of links to deleted notes
+pub fn dead_link_action(ctx: &Context) -> DeadLinkAction {
+    match ctx.config().linking.on_delete.as_str() {
+        "mark" => DeadLinkAction::Mark,
+        _ => DeadLinkAction::Remove,
+    }
+}
@@ -0,0 +1,150 @@
+// crates/zettel-cli/src/commands/related.rs - Similar Notes and Parent Suggestions
+//
+// `zettel related` lists the notes whose content resembles a note or a
+// draft; `zettel suggest-parent` turns the same ranking into placement
+// advice for a draft: the most similar notes as parents, each with the next
+// free child ID under it.
+//
+// Scoring lives in `zettel_core::similarity`; this module reads the notes
+// and reports the results.
+//
+// OUTPUT FORMAT:
+// `related` prints `ID<TAB>title<TAB>path` like `zettel find`;
+// `suggest-parent` prints `parent ID<TAB>next child ID<TAB>parent title`.
+// `--json` adds the scores and the shared words that weighed the most.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use zettel_core::note::Note;
+use zettel_core::similarity::{Corpus, Similar};
+
+use crate::commands::find::select_note;
+use crate::context::Context;
+
+/// Handle the related command
+pub fn handle(
+    ctx: &Context,
+    id: Option<String>,
+    stdin: bool,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let tree = ctx.note_tree();
+    let (text, exclude) = if stdin {
+        (crate::stdin::read_input_or_stdin(None)?, None)
+    } else {
+        let note = select_note(ctx, &tree, id.as_deref(), None)?;
+        (ctx.vault_service.read_file(&note.path)?, Some(&note.id))
+    };
+
+    let notes = tree.notes();
+    let mut similar = rank(ctx, notes, &text);
+    similar.retain(|s| Some(&notes[s.index].id) != exclude);
+    similar.truncate(limit);
+
+    if json {
+        let json_notes: Vec<_> = similar
+            .iter()
+            .map(|s| {
+                let note = &notes[s.index];
+                serde_json::json!({
+                    "id": note.id.to_string(),
+                    "title": note.title,
+                    "path": note.path.display().to_string(),
+                    "score": s.score,
+                    "terms": s.terms,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_notes)?);
+    } else if similar.is_empty() {
+        eprintln!("No similar notes found");
+        std::process::exit(1);
+    } else {
+        for s in &similar {
+            let note = &notes[s.index];
+            println!(
+                "{}\t{}\t{}",
+                note.id,
+                note.title.as_deref().unwrap_or(""),
+                note.path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the suggest-parent command
+pub fn suggest_parent(
+    ctx: &Context,
+    file: Option<PathBuf>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let draft = match &file {
+        Some(path) => ctx.vault_service.read_file(path)?,
+        None => crate::stdin::read_input_or_stdin(None)?,
+    };
+
+    let tree = ctx.note_tree();
+    let notes = tree.notes();
+    let mut similar = rank(ctx, notes, &draft);
+    // A draft already saved in the vault would propose itself
+    if let Some(path) = &file
+        && let Ok(path) = path.canonicalize()
+    {
+        similar.retain(|s| notes[s.index].path.canonicalize().ok() != Some(path.clone()));
+    }
+    similar.truncate(limit);
+
+    let id_manager = ctx.get_id_manager();
+    let suggestions: Vec<(&Note, String, &Similar)> = similar
+        .iter()
+        .map(|s| {
+            let parent = &notes[s.index];
+            let child = id_manager.next_available_child(&parent.id).to_string();
+            (parent, child, s)
+        })
+        .collect();
+
+    if json {
+        let json_suggestions: Vec<_> = suggestions
+            .iter()
+            .map(|(parent, child, s)| {
+                serde_json::json!({
+                    "parent_id": parent.id.to_string(),
+                    "parent_title": parent.title,
+                    "parent_path": parent.path.display().to_string(),
+                    "next_child_id": child,
+                    "score": s.score,
+                    "terms": s.terms,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_suggestions)?);
+    } else if suggestions.is_empty() {
+        eprintln!("No similar notes found; consider starting a new top-level note");
+        std::process::exit(1);
+    } else {
+        for (parent, child, _) in &suggestions {
+            println!(
+                "{}\t{}\t{}",
+                parent.id,
+                child,
+                parent.title.as_deref().unwrap_or("")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Notes of the vault ranked by similarity to `text`
+fn rank(ctx: &Context, notes: &[Note], text: &str) -> Vec<Similar> {
+    let contents: Vec<String> = notes
+        .iter()
+        .map(|note| ctx.vault_service.read_file(&note.path).unwrap_or_default())
+        .collect();
+    Corpus::new(contents.iter().map(String::as_str)).rank(text)
+}
@@ -1,52 +1,565 @@
 // crates/zettel-cli/src/commands/search.rs - Note Search Command
 //
-// This command implements simple text-based search across all notes in the vault.
-// It searches both filenames and file content using case-insensitive matching.
+// This command implements search across all notes in the vault. Queries use
+// the language parsed by `zettel_core::query` (words, phrases, field filters
+// such as `tag:` or `under:`, boolean operators) and run on whichever
+// backend is compiled in:
+//
+// - With the `search` feature (the default): the tantivy index in
+//   `.zettel/index/`, updated first, with results ranked by relevance. The
+//   update needs the vault lock; while another process holds it, the index
+//   is searched as it stands, or the notes are scanned when there is none
+// - Without it: a scan reading every note, with results in ID order
+//
+// `--regex` skips the query language and scans every note for a regular
+// expression instead.
+//
+// SAVED SEARCHES:
+// `@name` runs the query saved under that name in `[searches]`. With
+// `--into <ID>` the results are written as links into the managed block
+// `search:<name>` of a note instead of printed; `zettel sync searches`
+// refreshes those blocks (see `update_saved_block`).
+//
+// OUTPUT:
+// Each note is followed by its matching lines, grep style: `N:` marks a
+// matching line, `N-` a context line (`-A`/`-B`/`-C`) and `--` separates
+// excerpts. Matches are highlighted according to `[output] color`.
+// `--json`, `--ndjson` and `--csv` print one record per matching line with
+// file, line, column and snippet (notes matched only by filters get one
+// record without a line).
 
 use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use zettel_core::block::{BlockPosition, ManagedBlock};
+use zettel_core::id::Id;
+use zettel_core::link::LinkService;
+use zettel_core::note::Note;
+use zettel_core::query::{self, NoteFields, Query};
+use zettel_core::snippet::{self, ExcerptLine, LineMatch};
+use zettel_core::stats;
 
+use crate::commands::stats::created_date;
 use crate::context::Context;
 
-/// Search notes by content or filename
-///
-/// This implements simple text-based search across all notes in the vault.
-/// It searches both filenames and file content using case-insensitive matching.
-pub fn handle(ctx: &Context, query: Option<String>) -> Result<()> {
+/// Prefix of the names of blocks holding saved search results
+pub const SAVED_BLOCK_PREFIX: &str = "search:";
+
+/// ANSI sequences around highlighted matches
+const HIGHLIGHT_START: &str = "\x1b[1;31m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+/// How search results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+    Ndjson,
+    Csv,
+}
+
+/// Options of the search command besides the query
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// Context lines before each matching line
+    pub before: usize,
+    /// Context lines after each matching line
+    pub after: usize,
+    /// Treat the query as a regular expression
+    pub regex: bool,
+    pub case_sensitive: bool,
+    pub format: OutputFormat,
+    /// Note to write the results of a saved search into
+    pub into: Option<String>,
+}
+
+/// A note matched by a query, before its lines are looked up
+struct Hit {
+    id: String,
+    title: String,
+    path: PathBuf,
+    /// Relevance, when the backend ranks results
+    score: Option<f32>,
+}
+
+/// A note found by a search, with its matching lines
+struct Match {
+    id: String,
+    title: String,
+    path: PathBuf,
+    /// Relevance, when the backend ranks results
+    score: Option<f32>,
+    content: String,
+    lines: Vec<LineMatch>,
+}
+
+/// Search notes with a query
+pub fn handle(ctx: &Context, query: Option<String>, options: SearchOptions) -> Result<()> {
     // Get query from argument or stdin
     let query_str = crate::stdin::read_input_or_stdin(query.as_deref())?;
+    let saved_name = query_str.strip_prefix('@').map(str::to_string);
+    let query_str = match &saved_name {
+        Some(name) => saved_query(ctx, name),
+        None => query_str,
+    };
+
+    if let Some(into) = &options.into {
+        let Some(name) = &saved_name else {
+            eprintln!("❌ --into needs a saved search, given as @name");
+            std::process::exit(1);
+        };
+        parse_or_exit(&query_str);
+        let tree = ctx.note_tree();
+        let parsed_id = Id::parse(into)?;
+        let Some(note) = ctx.lookup(&tree, &parsed_id) else {
+            eprintln!("❌ No note found with ID: {}", into);
+            std::process::exit(1);
+        };
+        let count = update_saved_block(ctx, note, name)?.unwrap_or_default();
+        println!("✅ Wrote {} result(s) of @{} to {}", count, name, note.id);
+        return Ok(());
+    }
+
+    let matches = if options.regex {
+        let pattern = match RegexBuilder::new(&query_str)
+            .case_insensitive(!options.case_sensitive)
+            .build()
+        {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                eprintln!("❌ Invalid regular expression: {}", e);
+                std::process::exit(1);
+            }
+        };
+        scan_regex(ctx, &pattern)
+    } else {
+        let query = parse_or_exit(&query_str);
+        let pattern = snippet::query_pattern(&query, options.case_sensitive);
+
+        let mut matches = Vec::new();
+        for hit in find(ctx, &query)? {
+            let content = ctx.vault_service.read_file(&hit.path).unwrap_or_default();
+            let lines = pattern
+                .as_ref()
+                .map(|p| snippet::matching_lines(&content, p))
+                .unwrap_or_default();
+            // Backends match case-insensitively; only exact-case lines count here
+            if options.case_sensitive && pattern.is_some() && lines.is_empty() {
+                continue;
+            }
+            matches.push(Match {
+                id: hit.id,
+                title: hit.title,
+                path: hit.path,
+                score: hit.score,
+                content,
+                lines,
+            });
+        }
+        matches
+    };
 
-    let files = ctx.vault_service.get_vault_files();
+    match options.format {
+        OutputFormat::Human => {
+            let shown = match &saved_name {
+                Some(name) => format!("@{} ({})", name, query_str),
+                None => query_str,
+            };
+            print_human(ctx, &shown, &matches, &options)
+        }
+        format => print_records(ctx, &matches, &options, format)?,
+    }
+    Ok(())
+}
+
+/// List the saved searches of the configuration
+pub fn list_saved(ctx: &Context, json: bool) -> Result<()> {
+    let searches = &ctx.config().searches;
+    if json {
+        let json_searches: Vec<_> = searches
+            .iter()
+            .map(|(name, query)| serde_json::json!({ "name": name, "query": query }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json_searches)?);
+    } else if searches.is_empty() {
+        println!("No saved searches. Add them to [searches] in .zettel/config.toml");
+    } else {
+        for (name, query) in searches {
+            println!("@{}\t{}", name, query);
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite the `search:<name>` block of `note` with the saved search's results
+///
+/// Results are linked in search order, leaving out the note itself. Returns
+/// the number of results, or `None` when no search has that name (the block
+/// is then left alone) or its query is invalid.
+pub fn update_saved_block(ctx: &Context, note: &Note, name: &str) -> Result<Option<usize>> {
+    let Some(query_str) = ctx.config().searches.get(name) else {
+        return Ok(None);
+    };
+    let Ok(query) = query::parse(query_str) else {
+        return Ok(None);
+    };
+
+    let hits: Vec<Hit> = find(ctx, &query)?
+        .into_iter()
+        .filter(|hit| hit.path != note.path)
+        .collect();
+    let body: Vec<String> = hits
+        .iter()
+        .map(|hit| {
+            let filename = hit
+                .path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or(&hit.id);
+            let title = (!hit.title.is_empty()).then_some(hit.title.as_str());
+            format!(
+                "- {}",
+                LinkService::format_link(&ctx.config().linking, &hit.id, filename, title)
+            )
+        })
+        .collect();
+
+    let content = ctx.vault_service.read_file(&note.path)?;
+    let block = ManagedBlock::new(format!("{}{}", SAVED_BLOCK_PREFIX, name));
+    let new_content = block.upsert(&content, &body.join("\n"), BlockPosition::Bottom);
+    if new_content != content {
+        ctx.vault_service.write_file(&note.path, &new_content)?;
+    }
+    Ok(Some(hits.len()))
+}
+
+/// The query of a saved search, exiting with the known names if missing
+fn saved_query(ctx: &Context, name: &str) -> String {
+    let searches = &ctx.config().searches;
+    match searches.get(name) {
+        Some(query) => query.clone(),
+        None => {
+            eprintln!("❌ No saved search named: {}", name);
+            if searches.is_empty() {
+                eprintln!("Add it to [searches] in .zettel/config.toml");
+            } else {
+                let names: Vec<String> = searches.keys().map(|n| format!("@{}", n)).collect();
+                eprintln!("Saved searches: {}", names.join(", "));
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Parse a query, exiting with the problem pointed out if it's invalid
+fn parse_or_exit(query_str: &str) -> Query {
+    match query::parse(query_str) {
+        Ok(query) => query,
+        Err(e) => {
+            eprintln!("❌ Invalid query: {}", e.message);
+            eprintln!("   {}", query_str);
+            eprintln!("   {}", e.caret());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The searchable fields of a note, as both backends see them
+///
+/// Links count towards `links-to:` when their target names a note ID; the
+/// creation date is the one `zettel stats` uses.
+pub fn note_fields(ctx: &Context, note: &Note, content: String) -> NoteFields {
     let id_manager = ctx.get_id_manager();
-    let query_lower = query_str.to_lowercase();
+    let mut links = Vec::new();
+    for link in LinkService::find_links(&content) {
+        if let Some(id) = id_manager.extract_from_filename(&link.target)
+            && !links.contains(&id)
+        {
+            links.push(id);
+        }
+    }
+
+    NoteFields {
+        id: note.id.clone(),
+        title: note.title.clone().unwrap_or_default(),
+        tags: stats::tags(&content),
+        links,
+        created: created_date(&content, &note.path),
+        body: content,
+    }
+}
+
+/// Notes matching the query, best matches first
+#[cfg(feature = "search")]
+fn find(ctx: &Context, query: &Query) -> Result<Vec<Hit>> {
+    use crate::services::{IndexService, VaultLock};
+
+    let notes = ctx.load_notes();
+    let service = IndexService::new(ctx.vault_path());
+    let synced = match VaultLock::try_acquire(ctx.vault_path()) {
+        Ok(Some(_lock)) => {
+            match service.sync(&ctx.vault_service, &notes, |note, content| {
+                note_fields(ctx, note, content)
+            }) {
+                Ok((index, _)) => Some(index),
+                Err(e) => {
+                    eprintln!("⚠️  Could not update the search index: {:#}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let Some(index) = synced.or_else(|| service.open()) else {
+        return Ok(scan(ctx, query));
+    };
+
+    Ok(index
+        .search(query, notes.len().max(1))?
+        .into_iter()
+        .map(|hit| {
+            // Index paths are relative to the vault
+            Hit {
+                id: hit.id,
+                title: hit.title,
+                path: ctx.vault_path().join(&hit.path),
+                score: Some(hit.score),
+            }
+        })
+        .collect())
+}
+
+/// Notes matching the query, in ID order
+#[cfg(not(feature = "search"))]
+fn find(ctx: &Context, query: &Query) -> Result<Vec<Hit>> {
+    Ok(scan(ctx, query))
+}
+
+/// Notes matching the query found by reading every note, in ID order
+fn scan(ctx: &Context, query: &Query) -> Vec<Hit> {
+    let mut found = Vec::new();
+    for note in ctx.note_tree().notes() {
+        let Ok(content) = ctx.vault_service.read_file(&note.path) else {
+            continue;
+        };
+        let fields = note_fields(ctx, note, content);
+        if query.matches(&fields) {
+            found.push(Hit {
+                id: fields.id.to_string(),
+                title: fields.title,
+                path: note.path.clone(),
+                score: None,
+            });
+        }
+    }
+    found
+}
+
+/// Notes with lines matching a regular expression, in ID order
+fn scan_regex(ctx: &Context, pattern: &Regex) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for note in ctx.note_tree().notes() {
+        let Ok(content) = ctx.vault_service.read_file(&note.path) else {
+            continue;
+        };
+        let lines = snippet::matching_lines(&content, pattern);
+        if lines.is_empty() {
+            continue;
+        }
+        matches.push(Match {
+            id: note.id.to_string(),
+            title: note.title.clone().unwrap_or_default(),
+            path: note.path.clone(),
+            score: None,
+            content,
+            lines,
+        });
+    }
+    matches
+}
+
+/// Print notes with their matching lines and context
+fn print_human(ctx: &Context, query_str: &str, matches: &[Match], options: &SearchOptions) {
+    let color = ctx
+        .config()
+        .output
+        .use_color(std::io::stdout().is_terminal());
 
     println!("🔍 Searching for: {}", query_str);
     println!();
 
-    for file in files {
-        if let Some(filename) = file.file_name().and_then(|n| n.to_str()) {
-            if let Some(id) = id_manager.extract_from_filename(filename) {
-                // Search in filename first
-                if filename.to_lowercase().contains(&query_lower) {
-                    println!("📄 {} (filename match)", id);
-                    continue;
-                }
+    if matches.is_empty() {
+        println!("No matching notes");
+    }
+    for found in matches {
+        let title = if found.title.is_empty() {
+            "No title"
+        } else {
+            &found.title
+        };
+        match found.score {
+            Some(score) => println!("📄 {}: {} ({:.2})", found.id, title, score),
+            None => println!("📄 {}: {}", found.id, title),
+        }
 
-                // Search in content
-                if let Ok(content) = ctx.vault_service.read_file(&file) {
-                    if content.to_lowercase().contains(&query_lower) {
-                        let title = content
-                            .lines()
-                            .next()
-                            .unwrap_or("")
-                            .strip_prefix("# ")
-                            .unwrap_or("No title");
-
-                        println!("📄 {}: {}", id, title);
-                    }
-                }
+        let groups = snippet::excerpts(&found.content, &found.lines, options.before, options.after);
+        for (i, group) in groups.iter().enumerate() {
+            if i > 0 {
+                println!("   --");
+            }
+            for line in group {
+                let marker = if line.is_match { ':' } else { '-' };
+                println!("   {}{}{}", line.line, marker, highlight(line, color));
             }
         }
     }
+}
+
+/// A line's text with its match spans highlighted
+fn highlight(line: &ExcerptLine, color: bool) -> String {
+    if !color || line.spans.is_empty() {
+        return line.text.clone();
+    }
+
+    let mut out = String::new();
+    let mut last = 0;
+    for span in &line.spans {
+        out.push_str(&line.text[last..span.start]);
+        out.push_str(HIGHLIGHT_START);
+        out.push_str(&line.text[span.clone()]);
+        out.push_str(HIGHLIGHT_END);
+        last = span.end;
+    }
+    out.push_str(&line.text[last..]);
+    out
+}
 
+/// Print one record per matching line as JSON, NDJSON or CSV
+fn print_records(
+    ctx: &Context,
+    matches: &[Match],
+    options: &SearchOptions,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut records = Vec::new();
+    for found in matches {
+        let file = found
+            .path
+            .strip_prefix(ctx.vault_path())
+            .unwrap_or(&found.path)
+            .display()
+            .to_string();
+        let record = |line: Option<&LineMatch>| {
+            let lines: Vec<&str> = found.content.lines().collect();
+            let context = |range: std::ops::Range<usize>| -> Vec<&str> {
+                lines[range.start.min(lines.len())..range.end.min(lines.len())].to_vec()
+            };
+            serde_json::json!({
+                "id": found.id,
+                "title": found.title,
+                "file": file,
+                "line": line.map(|l| l.line),
+                "column": line.map(|l| l.column),
+                "snippet": line.map(|l| l.text.clone()),
+                "score": found.score,
+                "before": line.map(|l| context(l.line - 1 - options.before.min(l.line - 1)..l.line - 1)),
+                "after": line.map(|l| context(l.line..l.line + options.after)),
+            })
+        };
+
+        if found.lines.is_empty() {
+            records.push(record(None));
+        }
+        for line in &found.lines {
+            records.push(record(Some(line)));
+        }
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&records)?),
+        OutputFormat::Ndjson => {
+            for record in &records {
+                println!("{}", serde_json::to_string(record)?);
+            }
+        }
+        OutputFormat::Csv => {
+            println!("id,title,file,line,column,snippet,score");
+            for record in &records {
+                let fields: Vec<String> =
+                    ["id", "title", "file", "line", "column", "snippet", "score"]
+                        .iter()
+                        .map(|key| match &record[*key] {
+                            serde_json::Value::Null => String::new(),
+                            serde_json::Value::String(text) => csv_field(text),
+                            other => other.to_string(),
+                        })
+                        .collect();
+                println!("{}", fields.join(","));
+            }
+        }
+        OutputFormat::Human => unreachable!("human output is printed by print_human"),
+    }
     Ok(())
 }
+
+/// Quote a CSV field when it contains separators, quotes or line breaks
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn ids(hits: &[Hit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.id.as_str()).collect()
+    }
+
+    #[cfg(feature = "search")]
+    #[test]
+    fn test_search_while_vault_is_locked() {
+        use crate::services::VaultLock;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("1.md"), "# Apples\n\nRed fruit\n").unwrap();
+        let ctx = Context::new(Some(dir.path().to_path_buf())).unwrap();
+        let query = query::parse("fruit").unwrap();
+        let index_dir = dir.path().join(".zettel/index");
+
+        // Without an index, a locked vault is scanned and left untouched
+        let lock = VaultLock::acquire(dir.path()).unwrap();
+        assert_eq!(ids(&find(&ctx, &query).unwrap()), ["1"]);
+        assert!(!index_dir.exists());
+        drop(lock);
+
+        assert_eq!(ids(&find(&ctx, &query).unwrap()), ["1"]);
+        assert!(index_dir.exists());
+
+        // With one, the index is searched as it stands
+        fs::write(dir.path().join("2.md"), "# Pears\n\nGreen fruit\n").unwrap();
+        let lock = VaultLock::acquire(dir.path()).unwrap();
+        assert_eq!(ids(&find(&ctx, &query).unwrap()), ["1"]);
+        drop(lock);
+
+        let mut found = find(&ctx, &query).unwrap();
+        found.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(ids(&found), ["1", "2"]);
+    }
+
+    #[test]
+    fn test_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("1.md"), "# Apples\n\nRed fruit\n").unwrap();
+        fs::write(dir.path().join("2.md"), "# Stones\n").unwrap();
+        let ctx = Context::new(Some(dir.path().to_path_buf())).unwrap();
+
+        let hits = scan(&ctx, &query::parse("fruit").unwrap());
+        assert_eq!(ids(&hits), ["1"]);
+    }
+}
@@ -0,0 +1,140 @@
+// crates/zettel-cli/src/commands/stats.rs - Vault Statistics
+//
+// `zettel stats` reports how big the vault is, how well it is linked, what
+// shape its tree has and how it grew. The numbers are computed by
+// `zettel_core::stats`; this module reads notes, resolves links against the
+// vault and prints the result.
+//
+// CREATION DATE:
+// Taken from a `created` or `date` frontmatter field ("2024-03-15..."),
+// falling back to the file's creation (or modification) time. `zettel search`
+// uses the same date for `created:` queries.
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use std::path::Path;
+use zettel_core::frontmatter;
+use zettel_core::query::Date;
+use zettel_core::stats::{LinkTarget, NoteInput, VaultStats};
+
+use crate::commands::note::LinkResolver;
+use crate::context::Context;
+
+/// Handle the stats command
+pub fn handle(ctx: &Context, json: bool) -> Result<()> {
+    let tree = ctx.note_tree();
+
+    let contents: Vec<String> = tree
+        .notes()
+        .iter()
+        .map(|note| ctx.vault_service.read_file(&note.path).unwrap_or_default())
+        .collect();
+    let inputs: Vec<NoteInput> = tree
+        .notes()
+        .iter()
+        .zip(&contents)
+        .map(|(note, content)| NoteInput {
+            note,
+            content,
+            created: created_date(content, &note.path)
+                .map(|date| format!("{:04}-{:02}", date.year, date.month)),
+        })
+        .collect();
+
+    let resolver = LinkResolver::new(ctx, &tree);
+    let resolve = |target: &str| {
+        if let Some(note) = resolver.resolve(target) {
+            LinkTarget::Note(note.id.clone())
+        } else if ctx.vault_path().join(target).exists() {
+            LinkTarget::File
+        } else {
+            LinkTarget::Broken
+        }
+    };
+
+    let stats = VaultStats::compute(&tree, &inputs, resolve);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print_stats(&stats);
+    }
+    Ok(())
+}
+
+/// The date a note was created, from frontmatter or the filesystem
+pub fn created_date(content: &str, path: &Path) -> Option<Date> {
+    if let (Some(yaml), _) = frontmatter::split(content)
+        && let Ok(map) = frontmatter::parse(yaml)
+    {
+        let date = ["created", "date"]
+            .iter()
+            .filter_map(|key| map.get(*key).and_then(|v| v.as_str()))
+            .find_map(Date::parse_prefix);
+        if date.is_some() {
+            return date;
+        }
+    }
+
+    let metadata = std::fs::metadata(path).ok()?;
+    let time = metadata.created().or_else(|_| metadata.modified()).ok()?;
+    Date::parse_prefix(&DateTime::<Local>::from(time).format("%Y-%m-%d").to_string())
+}
+
+/// Print statistics for humans
+fn print_stats(stats: &VaultStats) {
+    println!("📊 Vault statistics");
+    println!("Notes:          {}", stats.total_notes);
+    println!("Orphans:        {}", stats.orphaned_notes);
+    println!(
+        "Links:          {} ({} broken)",
+        stats.total_links, stats.broken_links
+    );
+    println!("Deepest level:  {}", stats.deepest_level);
+    println!(
+        "Words:          {} total, {} per note",
+        stats.total_words, stats.average_words
+    );
+
+    if !stats.branching.is_empty() {
+        println!("\nBranching by depth:");
+        for depth in &stats.branching {
+            println!(
+                "  {}: {} note(s), {:.2} children each",
+                depth.depth, depth.notes, depth.branching_factor
+            );
+        }
+    }
+
+    let ranked = |heading: &str, notes: &[zettel_core::stats::RankedNote], unit: &str| {
+        if notes.is_empty() {
+            return;
+        }
+        println!("\n{}:", heading);
+        for note in notes {
+            println!(
+                "  {}  {}  ({} {})",
+                note.id,
+                note.title.as_deref().unwrap_or(""),
+                note.count,
+                unit
+            );
+        }
+    };
+    ranked("Largest subtrees", &stats.largest_subtrees, "notes");
+    ranked("Most linked", &stats.most_linked, "links");
+
+    if !stats.tags.is_empty() {
+        println!("\nTop tags:");
+        for tag in &stats.tags {
+            println!("  #{}  {}", tag.tag, tag.count);
+        }
+    }
+
+    if !stats.notes_per_month.is_empty() {
+        println!("\nNotes per month:");
+        for (month, count) in &stats.notes_per_month {
+            println!("  {}  {}", month, count);
+        }
+    }
+}
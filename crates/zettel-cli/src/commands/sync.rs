@@ -0,0 +1,270 @@
+// crates/zettel-cli/src/commands/sync.rs - Managed Block Synchronization
+//
+// These commands regenerate the managed blocks zettel maintains inside notes.
+// Blocks are rebuilt from the actual state of the vault, so running a sync
+// after moving, renaming or deleting notes brings every block back in line.
+//
+// MANAGED BLOCKS:
+// - children: Links to a note's existing children, in Folgezettel order
+// - nav: Breadcrumb of ancestors and previous/next sibling links
+// - search:<name>: Results of a saved search, written by `search --into`
+
+use anyhow::Result;
+use zettel_core::block::{self, BlockPosition, ManagedBlock};
+use zettel_core::id::Id;
+use zettel_core::link::LinkService;
+use zettel_core::note::{Note, NoteTree};
+
+use crate::cli::SyncCommands;
+use crate::commands::search;
+use crate::context::Context;
+
+/// Name of the block holding a parent's child links
+pub const CHILDREN_BLOCK: &str = "children";
+
+/// Name of the block holding breadcrumb and sibling navigation
+pub const NAV_BLOCK: &str = "nav";
+
+/// Handle managed block synchronization commands
+pub fn handle(ctx: &Context, cmd: SyncCommands) -> Result<()> {
+    match cmd {
+        SyncCommands::Children { id } => handle_children(ctx, id.as_deref()),
+        SyncCommands::Nav { id } => handle_nav(ctx, id.as_deref()),
+        SyncCommands::Searches { id } => handle_searches(ctx, id.as_deref()),
+    }
+}
+
+/// Rebuild the children block of one note, or of every note in the vault
+///
+/// Vault-wide, a block is written for every note that has children and
+/// refreshed (possibly to empty) in every note that already contains one.
+/// Notes without children and without a block are left alone.
+fn handle_children(ctx: &Context, id: Option<&str>) -> Result<()> {
+    let tree = ctx.note_tree();
+
+    let mut updated = 0;
+    for note in select_targets(ctx, &tree, id)? {
+        if update_children_block(ctx, &tree, note, id.is_some())? {
+            println!("🔗 Updated children of {}", note.id);
+            updated += 1;
+        }
+    }
+
+    println!("✅ {} note(s) updated", updated);
+    Ok(())
+}
+
+/// Rebuild the navigation block of one note, or of every note in the vault
+///
+/// With both breadcrumbs and siblings disabled this removes existing blocks,
+/// so turning the feature off and syncing cleans the vault up.
+fn handle_nav(ctx: &Context, id: Option<&str>) -> Result<()> {
+    let tree = ctx.note_tree();
+
+    let mut updated = 0;
+    for note in select_targets(ctx, &tree, id)? {
+        if update_nav_block(ctx, &tree, note)? {
+            println!("🧭 Updated navigation of {}", note.id);
+            updated += 1;
+        }
+    }
+
+    println!("✅ {} note(s) updated", updated);
+    Ok(())
+}
+
+/// Refresh the saved search blocks of one note, or of every note in the vault
+///
+/// Blocks of searches no longer in the configuration are left as they are,
+/// with a warning.
+fn handle_searches(ctx: &Context, id: Option<&str>) -> Result<()> {
+    let tree = ctx.note_tree();
+
+    let mut updated = 0;
+    for note in select_targets(ctx, &tree, id)? {
+        let content = ctx.vault_service.read_file(&note.path)?;
+        for block in block::names_with_prefix(&content, search::SAVED_BLOCK_PREFIX) {
+            let name = &block[search::SAVED_BLOCK_PREFIX.len()..];
+            match search::update_saved_block(ctx, note, name)? {
+                Some(count) => {
+                    println!("🔍 Updated @{} in {} ({} result(s))", name, note.id, count);
+                    updated += 1;
+                }
+                None => eprintln!(
+                    "⚠️  Skipped @{} in {}: no valid saved search by that name",
+                    name, note.id
+                ),
+            }
+        }
+    }
+
+    println!("✅ {} block(s) updated", updated);
+    Ok(())
+}
+
+/// Resolve the notes a sync command applies to: one note, or all of them
+fn select_targets<'a>(
+    ctx: &Context,
+    tree: &'a NoteTree,
+    id: Option<&str>,
+) -> Result<Vec<&'a Note>> {
+    let Some(id_str) = id else {
+        return Ok(tree.notes().iter().collect());
+    };
+
+    let parsed_id = Id::parse(id_str)?;
+    match ctx.lookup(tree, &parsed_id) {
+        Some(note) => Ok(vec![note]),
+        None => {
+            eprintln!("❌ No note found with ID: {}", id_str);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Regenerate the children block of `parent` from the current tree
+///
+/// Returns whether the file was changed. A note that has no children and no
+/// existing block is only touched when `force` is set, so a vault-wide sync
+/// doesn't sprinkle empty blocks into leaf notes.
+pub fn update_children_block(
+    ctx: &Context,
+    tree: &NoteTree,
+    parent: &Note,
+    force: bool,
+) -> Result<bool> {
+    let block = ManagedBlock::new(CHILDREN_BLOCK);
+    let content = ctx.vault_service.read_file(&parent.path)?;
+    let children = tree.children(&parent.id);
+
+    if children.is_empty() && !block.is_present(&content) && !force {
+        return Ok(false);
+    }
+
+    let body = render_children(ctx, &children);
+    let position = match ctx.config().linking.insertion_point.as_str() {
+        "after_title" => BlockPosition::AfterTitle,
+        _ => BlockPosition::Bottom,
+    };
+
+    let new_content = block.upsert(&content, &body, position);
+    if new_content == content {
+        return Ok(false);
+    }
+
+    ctx.vault_service.write_file(&parent.path, &new_content)?;
+    Ok(true)
+}
+
+/// Regenerate the navigation block of `note` from the current tree
+///
+/// Returns whether the file was changed. An empty navigation (a lone root
+/// note, or navigation disabled) removes the block instead of leaving empty
+/// markers behind.
+pub fn update_nav_block(ctx: &Context, tree: &NoteTree, note: &Note) -> Result<bool> {
+    let block = ManagedBlock::new(NAV_BLOCK);
+    let content = ctx.vault_service.read_file(&note.path)?;
+    let body = render_nav(ctx, tree, note);
+
+    let new_content = if body.is_empty() {
+        block.remove(&content)
+    } else {
+        let position = match ctx.config().linking.navigation.position.as_str() {
+            "bottom" => BlockPosition::Bottom,
+            _ => BlockPosition::Top,
+        };
+        block.upsert(&content, &body, position)
+    };
+
+    if new_content == content {
+        return Ok(false);
+    }
+
+    ctx.vault_service.write_file(&note.path, &new_content)?;
+    Ok(true)
+}
+
+/// Refresh navigation blocks affected by a newly created note
+///
+/// A new note changes the prev/next links of its siblings and, when it fills
+/// a gap in the tree, the breadcrumbs of its existing descendants.
+pub fn refresh_nav_around(ctx: &Context, id: &Id) -> Result<()> {
+    let nav = &ctx.config().linking.navigation;
+    if !nav.breadcrumbs && !nav.siblings {
+        return Ok(());
+    }
+
+    let tree = ctx.note_tree();
+    for note in tree.notes() {
+        if note.id.is_sibling_of(id) || note.id.is_descendant_of(id) {
+            update_nav_block(ctx, &tree, note)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Render child links as a markdown list using the linking configuration
+fn render_children(ctx: &Context, children: &[&Note]) -> String {
+    children
+        .iter()
+        .map(|child| format!("- {}", note_link(ctx, child)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the breadcrumb and sibling lines of a note's navigation block
+///
+/// ```text
+/// [[1]] > [[1a]]
+/// ← [[1a1]] | [[1a3]] →
+/// ```
+///
+/// Ancestors that don't exist as notes are shown as their bare ID.
+fn render_nav(ctx: &Context, tree: &NoteTree, note: &Note) -> String {
+    let nav = &ctx.config().linking.navigation;
+    let mut lines = Vec::new();
+
+    if nav.breadcrumbs {
+        let crumbs: Vec<String> = note
+            .id
+            .ancestors()
+            .iter()
+            .map(|ancestor| match tree.get(ancestor) {
+                Some(existing) => note_link(ctx, existing),
+                None => ancestor.to_string(),
+            })
+            .collect();
+
+        if !crumbs.is_empty() {
+            lines.push(crumbs.join(&nav.separator));
+        }
+    }
+
+    if nav.siblings {
+        let previous = tree.previous_sibling(&note.id);
+        let next = tree.next_sibling(&note.id);
+
+        let line = match (previous, next) {
+            (Some(p), Some(n)) => {
+                Some(format!("← {} | {} →", note_link(ctx, p), note_link(ctx, n)))
+            }
+            (Some(p), None) => Some(format!("← {}", note_link(ctx, p))),
+            (None, Some(n)) => Some(format!("{} →", note_link(ctx, n))),
+            (None, None) => None,
+        };
+        lines.extend(line);
+    }
+
+    lines.join("\n")
+}
+
+/// Format a link to an existing note using the linking configuration
+fn note_link(ctx: &Context, note: &Note) -> String {
+    LinkService::format_link(
+        &ctx.config().linking,
+        &note.id.to_string(),
+        &note.filename(),
+        note.title.as_deref(),
+    )
+}
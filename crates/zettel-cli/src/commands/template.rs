@@ -12,9 +12,14 @@
 
 use anyhow::Result;
 use serde_json;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{self, Read};
-use zettel_core::template::TemplateService;
+use std::path::{Path, PathBuf};
+use zettel_core::config::TemplateRule;
+use zettel_core::id::Id;
+use zettel_core::link::LinkService;
+use zettel_core::template::{Template, TemplateContext, TemplateService};
 
 use crate::cli::TemplateCommands;
 use crate::context::Context;
@@ -26,8 +31,15 @@ use crate::context::Context;
 pub fn handle(ctx: &Context, cmd: TemplateCommands) -> Result<()> {
     match cmd {
         TemplateCommands::Validate { file } => handle_validate(ctx, file),
-        TemplateCommands::Test { title, link, file } => handle_test(ctx, title, link, file),
+        TemplateCommands::Test {
+            title,
+            link,
+            file,
+            id,
+            vars,
+        } => handle_test(ctx, title, link, file, id, vars),
         TemplateCommands::Placeholders { file, json } => handle_placeholders(ctx, file, json),
+        TemplateCommands::List { json } => handle_list(ctx, json),
         TemplateCommands::Example {
             template_type,
             output,
@@ -48,6 +60,23 @@ fn handle_validate(ctx: &Context, file: Option<String>) -> Result<()> {
     let config = &ctx.config().template;
     let validation_result = TemplateService::validate_template(&template_content, config);
 
+    // Included partials must exist; their own syntax is checked as well
+    let missing_partials = match Template::parse(&template_content) {
+        Ok(template) => template
+            .partials()
+            .into_iter()
+            .filter(|name| read_partial(ctx, file.as_deref(), name).is_none())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    if validation_result.valid && !missing_partials.is_empty() {
+        eprintln!("❌ Template validation failed");
+        eprintln!("   Unknown partial(s): {}", missing_partials.join(", "));
+        eprintln!();
+        eprintln!("💡 Partials are read from the template directory, as <name>.md");
+        std::process::exit(1);
+    }
+
     if validation_result.valid {
         println!("✅ Template validation passed");
 
@@ -114,7 +143,14 @@ fn handle_validate(ctx: &Context, file: Option<String>) -> Result<()> {
 ///
 /// This is like a "dry run" of note creation - shows what content would
 /// be generated without actually creating a file.
-fn handle_test(ctx: &Context, title: String, link: String, file: Option<String>) -> Result<()> {
+fn handle_test(
+    ctx: &Context,
+    title: String,
+    link: String,
+    file: Option<String>,
+    id: String,
+    vars: Vec<(String, String)>,
+) -> Result<()> {
     // Read template content from file or stdin
     let template_content = read_template_input(ctx, file.as_deref())?;
 
@@ -134,8 +170,17 @@ fn handle_test(ctx: &Context, title: String, link: String, file: Option<String>)
     }
 
     // Generate content with provided values
+    let id = Id::parse(&id).ok();
+    let context = template_context(ctx, &title, &link, id.as_ref(), &vars);
+    let partials = |name: &str| read_partial(ctx, file.as_deref(), name);
     let generated_content =
-        TemplateService::generate_content(Some(&template_content), &title, &link);
+        match TemplateService::generate_content(Some(&template_content), &context, &partials) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        };
 
     // Output with clear separation
     println!("📄 Template test output:");
@@ -186,6 +231,13 @@ fn handle_placeholders(ctx: &Context, file: Option<String>, json: bool) -> Resul
                 let description = match placeholder.as_str() {
                     "title" => "Note title",
                     "link" => "Backlink to parent note",
+                    "id" => "Note ID",
+                    "parent_id" => "Parent note ID (empty for roots)",
+                    "date" => "Creation date",
+                    "time" => "Creation time",
+                    "author" => "Configured author or $USER",
+                    "parent" => "Parent note title",
+                    name if config.variables.contains_key(name) => "Custom variable",
                     _ => "Unknown placeholder",
                 };
                 println!("     • {{{{{}}}}} - {}", placeholder, description);
@@ -212,6 +264,130 @@ fn handle_placeholders(ctx: &Context, file: Option<String>, json: bool) -> Resul
     Ok(())
 }
 
+/// List the named templates of the template directory
+///
+/// Every `.md` file in the directory is a template named after the file.
+/// Rules naming templates that don't exist are reported too, since note
+/// creation would fail on them.
+fn handle_list(ctx: &Context, json: bool) -> Result<()> {
+    let config = &ctx.config().template;
+    let directory = config.directory.trim();
+
+    let mut names: Vec<String> = Vec::new();
+    if !directory.is_empty()
+        && let Ok(entries) = fs::read_dir(ctx.vault_path().join(directory))
+    {
+        names = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+            .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+            .collect();
+        names.sort();
+    }
+
+    // The directory default only applies when no single file is configured
+    let default = (config.file.trim().is_empty() && !directory.is_empty())
+        .then(|| config.default_template.trim());
+    let rules_for = |name: &str| -> Vec<&TemplateRule> {
+        config
+            .rules
+            .iter()
+            .filter(|rule| rule.template.trim().trim_end_matches(".md") == name)
+            .collect()
+    };
+    let missing: Vec<&str> = config
+        .rules
+        .iter()
+        .map(|rule| rule.template.trim().trim_end_matches(".md"))
+        .filter(|name| !names.iter().any(|n| n == name))
+        .collect();
+
+    if json {
+        let templates: Vec<_> = names
+            .iter()
+            .map(|name| {
+                serde_json::json!({
+                    "name": name,
+                    "path": TemplateService::template_in_directory(directory, name),
+                    "default": default == Some(name.as_str()),
+                    "rules": rules_for(name),
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "directory": directory,
+            "file": config.file.trim(),
+            "templates": templates,
+            "missing": missing,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if directory.is_empty() {
+        println!("No template directory configured");
+        if !config.file.trim().is_empty() {
+            println!("   Template file: {}", config.file.trim());
+        }
+        println!("💡 Set [template] directory to use named templates");
+        return Ok(());
+    }
+
+    if names.is_empty() {
+        println!("No templates found in {}", directory);
+    } else {
+        println!("📋 Templates in {}:", directory);
+        let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+        for name in &names {
+            let mut notes: Vec<String> = rules_for(name).into_iter().map(describe_rule).collect();
+            if default == Some(name.as_str()) {
+                notes.insert(0, "default".to_string());
+            }
+            let notes = if notes.is_empty() {
+                String::new()
+            } else {
+                format!("  ({})", notes.join("; "))
+            };
+            println!(
+                "   {:width$}  {}{}",
+                name,
+                TemplateService::template_in_directory(directory, name),
+                notes,
+                width = width
+            );
+        }
+    }
+    if !config.file.trim().is_empty() {
+        println!("   Default template file: {}", config.file.trim());
+    }
+
+    for name in missing {
+        eprintln!("⚠️  A template rule names '{}', which doesn't exist", name);
+    }
+
+    Ok(())
+}
+
+/// The conditions of a rule, as in the configuration
+fn describe_rule(rule: &TemplateRule) -> String {
+    let mut conditions = Vec::new();
+    if let Some(depth) = &rule.depth {
+        conditions.push(format!("depth = {}", depth));
+    }
+    if let Some(under) = &rule.under {
+        conditions.push(format!("under = {}", under));
+    }
+    if let Some(note_type) = &rule.note_type {
+        conditions.push(format!("type = {}", note_type));
+    }
+    if conditions.is_empty() {
+        "any note".to_string()
+    } else {
+        conditions.join(", ")
+    }
+}
+
 /// Generate example template files
 ///
 /// Creates sample templates to help users get started with customization.
@@ -245,6 +421,105 @@ fn handle_example(template_type: String, output: Option<String>) -> Result<()> {
     Ok(())
 }
 
+/// Build the values a template is filled in with
+///
+/// Later sources override earlier ones: built-in values, then the vault's
+/// `[template.variables]`, then `vars` from the command line. With an ID,
+/// {{parent}} is the parent note's title and the `ancestors` list holds
+/// the existing notes above it, root first, with `id`, `title` and `link`.
+pub fn template_context(
+    ctx: &Context,
+    title: &str,
+    link: &str,
+    id: Option<&Id>,
+    vars: &[(String, String)],
+) -> TemplateContext {
+    let config = &ctx.config().template;
+    let mut context = TemplateContext::new(title, link);
+    context.set_time(chrono::Local::now().naive_local());
+
+    if let Some(id) = id {
+        context.set("id", id.to_string());
+        let parent = match id.parent() {
+            Ok(Some(parent)) => parent.to_string(),
+            _ => String::new(),
+        };
+        context.set("parent_id", parent);
+
+        let tree = ctx.note_tree();
+        let mut ancestors = Vec::new();
+        let mut current = id.parent().ok().flatten();
+        while let Some(ancestor_id) = current {
+            if let Some(note) = ctx.lookup(&tree, &ancestor_id) {
+                let title = note
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| ancestor_id.to_string());
+                let link = LinkService::format_link(
+                    &ctx.config().linking,
+                    &ancestor_id.to_string(),
+                    &note.filename(),
+                    note.title.as_deref(),
+                );
+                ancestors.push(BTreeMap::from([
+                    ("id".to_string(), ancestor_id.to_string()),
+                    ("title".to_string(), title),
+                    ("link".to_string(), link),
+                ]));
+            }
+            current = ancestor_id.parent().ok().flatten();
+        }
+        ancestors.reverse();
+        if let Some(parent) = ancestors.last() {
+            context.set("parent", parent["title"].as_str());
+        }
+        context.set_list("ancestors", ancestors);
+    }
+
+    let author = config
+        .author
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok());
+    if let Some(author) = author {
+        context.set("author", author);
+    }
+
+    for (name, value) in config
+        .variables
+        .iter()
+        .chain(vars.iter().map(|(k, v)| (k, v)))
+    {
+        context.set(name.as_str(), value.as_str());
+    }
+    context
+}
+
+/// Read a partial included with {{> name}}
+///
+/// Partials live in the configured template directory or, without one, next
+/// to the including template; the `.md` extension may be left out.
+pub fn read_partial(ctx: &Context, template_path: Option<&str>, name: &str) -> Option<String> {
+    let directory = ctx.config().template.directory.trim();
+    let directory = if directory.is_empty() {
+        template_path
+            .and_then(|path| Path::new(path).parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    } else {
+        PathBuf::from(directory)
+    };
+
+    [format!("{}.md", name), name.to_string()]
+        .iter()
+        .find_map(|file| {
+            let path = directory.join(file);
+            fs::read_to_string(ctx.vault_path().join(&path))
+                .or_else(|_| fs::read_to_string(&path))
+                .ok()
+        })
+}
+
 /// Read template content from file or stdin
 ///
 /// Handles the common pattern of reading template content either from
@@ -0,0 +1,76 @@
+// crates/zettel-cli/src/commands/trash.rs - Trash Commands
+//
+// Inspect, restore and empty the vault trash filled by `zettel note delete`
+// and `zettel note merge`.
+
+use anyhow::Result;
+use zettel_core::id::Id;
+
+use crate::cli::TrashCommands;
+use crate::commands::sync;
+use crate::context::Context;
+use crate::services::{TrashService, VaultLock};
+
+/// Handle trash commands
+pub fn handle(ctx: &Context, cmd: TrashCommands) -> Result<()> {
+    let trash = TrashService::new(ctx.vault_path());
+
+    match cmd {
+        TrashCommands::List { json } => {
+            let entries = trash.list();
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else if entries.is_empty() {
+                println!("🗑️  Trash is empty");
+            } else {
+                for entry in entries {
+                    println!(
+                        "{}  {} ({}, {} file(s))",
+                        entry.name,
+                        entry.id,
+                        entry.reason,
+                        entry.files.len()
+                    );
+                }
+            }
+        }
+
+        TrashCommands::Restore { entry } => {
+            let lock = VaultLock::acquire(ctx.vault_path())?;
+
+            let Some(found) = trash.find(&entry) else {
+                lock.abort(&format!("No trash entry found for: {}", entry));
+            };
+
+            for path in trash.restore(&found)? {
+                println!("♻️  Restored {}", path.display());
+            }
+
+            // Bring the restored note back into its parent's managed children block
+            if ctx.config().linking.managed_children
+                && let Ok(id) = Id::parse(&found.id)
+                && let Ok(Some(parent_id)) = id.parent()
+            {
+                let tree = ctx.note_tree();
+                if let Some(parent) = tree.get(&parent_id) {
+                    sync::update_children_block(ctx, &tree, parent, false)?;
+                }
+            }
+            sync::refresh_nav_around(ctx, &Id::parse(&found.id)?)?;
+
+            println!("ℹ️  Links removed or marked at deletion time were not restored");
+        }
+
+        TrashCommands::Empty => {
+            let _lock = VaultLock::acquire(ctx.vault_path())?;
+            let count = trash.empty()?;
+            println!(
+                "✅ Trash emptied ({} deletion(s) permanently removed)",
+                count
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -20,8 +20,9 @@
 use anyhow::{Context as AnyhowContext, Result};
 use std::env;
 use std::path::{Path, PathBuf};
-use zettel_core::config::{ConfigManager, ZettelConfig};
-use zettel_core::id::IdManager;
+use zettel_core::config::{ConfigManager, ConfigResult, ZettelConfig};
+use zettel_core::id::{Id, IdManager};
+use zettel_core::note::{self, Note, NoteTree};
 
 use crate::services::VaultService;
 
@@ -78,6 +79,24 @@ impl Context {
     /// Now loads configuration from the full hierarchy:
     /// defaults -> global config -> vault config -> env vars
     pub fn new(vault_path: Option<PathBuf>) -> Result<Self> {
+        Self::load(vault_path, ConfigManager::load_config)
+    }
+
+    /// Create a context without validating the configuration
+    ///
+    /// For `zettel doctor`, which reports configuration problems instead of
+    /// refusing to run. A config that doesn't parse falls back to the
+    /// defaults, leaving the error for doctor to report.
+    pub fn unvalidated(vault_path: Option<PathBuf>) -> Result<Self> {
+        Self::load(vault_path, |path| {
+            Ok(ConfigManager::load_config_unvalidated(path).unwrap_or_default())
+        })
+    }
+
+    fn load(
+        vault_path: Option<PathBuf>,
+        load_config: fn(Option<&Path>) -> ConfigResult<ZettelConfig>,
+    ) -> Result<Self> {
         // Determine vault path using configuration hierarchy
         let vault_path = vault_path
             .or_else(|| env::var("ZETTEL_VAULT").ok().map(PathBuf::from))
@@ -100,7 +119,7 @@ impl Context {
         }
 
         // Load complete configuration from all sources
-        let config = ConfigManager::load_config(Some(&vault_path)).with_context(|| {
+        let config = load_config(Some(&vault_path)).with_context(|| {
             format!("Failed to load configuration from {}", vault_path.display())
         })?;
 
@@ -123,6 +142,69 @@ impl Context {
         })
     }
 
+    /// Load every note in the vault with its parsed ID and title
+    ///
+    /// Files whose names don't contain a valid ID are skipped. Titles come from
+    /// the first `# ` heading, falling back to the text after the ID in the
+    /// filename when the file has no heading or can't be read.
+    pub fn load_notes(&self) -> Vec<Note> {
+        let id_manager = self.get_id_manager();
+
+        self.vault_service
+            .get_vault_files()
+            .into_iter()
+            .filter_map(|path| {
+                let filename = path.file_name()?.to_str()?;
+                let id = id_manager.extract_from_filename(filename)?;
+                let stem = path.file_stem()?.to_str()?;
+
+                let title = self
+                    .vault_service
+                    .read_file(&path)
+                    .ok()
+                    .and_then(|content| note::title_from_content(&content))
+                    .or_else(|| note::title_from_filename(stem, &id));
+
+                Some(Note::new(id, path, title))
+            })
+            .collect()
+    }
+
+    /// Load the vault's notes arranged as a Folgezettel tree
+    pub fn note_tree(&self) -> NoteTree {
+        NoteTree::new(self.load_notes())
+    }
+
+    /// Look up a note the user named by ID, warning when the ID is ambiguous
+    ///
+    /// Several files can share an ID (`1a.md` next to `1a - Title.md`). The
+    /// first one is used, and a warning on stderr names the others and how
+    /// to fix the vault, so stdout stays clean for scripts.
+    pub fn lookup<'a>(&self, tree: &'a NoteTree, id: &Id) -> Option<&'a Note> {
+        let matches = tree.get_all(id);
+        if matches.len() > 1 {
+            let names: Vec<String> = matches
+                .iter()
+                .map(|note| {
+                    note.path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect();
+            eprintln!(
+                "⚠️  ID {} is used by {} files ({}); using {}. Fix with: zettel id dedupe {}",
+                id,
+                matches.len(),
+                names.join(", "),
+                names[0],
+                id
+            );
+        }
+        matches.first().copied()
+    }
+
     /// Get vault path for commands that need filesystem operations
     pub fn vault_path(&self) -> &Path {
         &self.vault_path
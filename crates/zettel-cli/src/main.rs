@@ -73,7 +73,12 @@ fn main() -> Result<()> {
     // Set up application context with vault and configuration
     // Context implements dependency injection - it provides services to commands
     // This ensures commands don't directly depend on file system or configuration
-    let ctx = Context::new(cli.vault)?;
+    // Doctor reports configuration problems, so it can't require a valid config
+    let ctx = if matches!(cli.command, cli::Commands::Doctor { .. }) {
+        Context::unvalidated(cli.vault)?
+    } else {
+        Context::new(cli.vault)?
+    };
 
     // Dispatch to appropriate command handler using pattern matching
     // Each command family is implemented in its own module for maintainability
@@ -83,7 +88,76 @@ fn main() -> Result<()> {
         cli::Commands::Note(cmd) => commands::note::handle(&ctx, cmd),
         cli::Commands::Template(cmd) => commands::template::handle(&ctx, cmd),
         cli::Commands::List { full_paths, json } => commands::list::handle(&ctx, full_paths, json),
-        cli::Commands::Search { query } => commands::search::handle(&ctx, query),
+        cli::Commands::Find { query, limit, json } => {
+            commands::find::handle(&ctx, query, limit, json)
+        }
+        cli::Commands::Related {
+            id,
+            stdin,
+            limit,
+            json,
+        } => commands::related::handle(&ctx, id, stdin, limit, json),
+        cli::Commands::SuggestParent { file, limit, json } => {
+            commands::related::suggest_parent(&ctx, file, limit, json)
+        }
+        cli::Commands::Mentions {
+            id,
+            all,
+            sources,
+            link,
+            json,
+        } => commands::mentions::handle(&ctx, id, all, sources, link, json),
+        cli::Commands::Search {
+            query,
+            context,
+            after_context,
+            before_context,
+            regex,
+            case_sensitive,
+            json,
+            ndjson,
+            csv,
+            saved,
+            into,
+        } => {
+            use commands::search::{OutputFormat, SearchOptions};
+            if saved {
+                return commands::search::list_saved(&ctx, json);
+            }
+            let format = if json {
+                OutputFormat::Json
+            } else if ndjson {
+                OutputFormat::Ndjson
+            } else if csv {
+                OutputFormat::Csv
+            } else {
+                OutputFormat::Human
+            };
+            let options = SearchOptions {
+                before: before_context.unwrap_or(context),
+                after: after_context.unwrap_or(context),
+                regex,
+                case_sensitive,
+                format,
+                into,
+            };
+            commands::search::handle(&ctx, query, options)
+        }
+        #[cfg(feature = "search")]
+        cli::Commands::Index(cmd) => commands::index::handle(&ctx, cmd),
+        cli::Commands::Sync(cmd) => commands::sync::handle(&ctx, cmd),
+        cli::Commands::Link(cmd) => commands::link::handle(&ctx, cmd),
+        cli::Commands::Trash(cmd) => commands::trash::handle(&ctx, cmd),
+        cli::Commands::Doctor {
+            missing_parents,
+            json,
+        } => commands::doctor::handle(&ctx, missing_parents, json),
+        cli::Commands::Stats { json } => commands::stats::handle(&ctx, json),
+        cli::Commands::Nav {
+            target,
+            title,
+            json,
+        } => commands::nav::handle(&ctx, target, title, json),
         cli::Commands::Init { .. } => unreachable!(), // Already handled above
     }
 }
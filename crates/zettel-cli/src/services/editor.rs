@@ -61,10 +61,10 @@ impl EditorService {
     /// ```
     pub fn get_editor_command(config: Option<&EditorConfig>) -> String {
         // 1. Check config file first
-        if let Some(config) = config {
-            if let Some(command) = &config.command {
-                return command.clone();
-            }
+        if let Some(config) = config
+            && let Some(command) = &config.command
+        {
+            return command.clone();
         }
 
         // 2. Check environment variables
@@ -0,0 +1,128 @@
+// crates/zettel-cli/src/services/index.rs - Search Index Maintenance
+//
+// Keeps the full-text index in `.zettel/index/` in step with the vault.
+// The index itself (schema, queries) lives in `zettel_core::search`; this
+// service decides which files to (re)index, and callers extract each note's
+// searchable fields (`commands::search::note_fields`).
+//
+// INCREMENTAL UPDATES:
+// Every search compares the modification time of each note file with the
+// time recorded when it was indexed. Only new or changed files are read and
+// re-indexed, and files that disappeared are dropped, so searching a large
+// vault after editing one note costs one file read.
+//
+// Only one process may write the index at a time, so updates run under the
+// vault lock. Searches that find it taken read the index as it stands
+// (`IndexService::open`) rather than wait.
+//
+// `zettel index rebuild` deletes the directory and indexes every note again,
+// for when the index is corrupted or was built by another zettel version.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use zettel_core::note::Note;
+use zettel_core::query::NoteFields;
+use zettel_core::search::{self, IndexDocument, SearchIndex};
+
+use crate::services::VaultService;
+
+/// Maintains the vault's on-disk search index
+pub struct IndexService {
+    index_dir: PathBuf,
+}
+
+impl IndexService {
+    /// Create an index service for the given vault
+    pub fn new(vault_path: &Path) -> Self {
+        Self {
+            index_dir: vault_path.join(".zettel").join("index"),
+        }
+    }
+
+    /// Open the index and bring it up to date with `notes`
+    ///
+    /// `fields` turns a note and its content into what gets indexed. Returns
+    /// the index and how many notes were (re)indexed.
+    pub fn sync(
+        &self,
+        vault: &VaultService,
+        notes: &[Note],
+        fields: impl Fn(&Note, String) -> NoteFields,
+    ) -> Result<(SearchIndex, usize)> {
+        let index = SearchIndex::open_or_create(&self.index_dir)
+            .with_context(|| format!("Failed to open index: {}", self.index_dir.display()))?;
+
+        let by_path: HashMap<String, &Note> = notes
+            .iter()
+            .map(|note| (self.key(vault, &note.path), note))
+            .collect();
+        let current: HashMap<String, u64> = by_path
+            .iter()
+            .map(|(key, note)| (key.clone(), modified_nanos(&note.path)))
+            .collect();
+
+        let (changed, removed) = search::plan_update(&index.indexed_files()?, &current);
+
+        let mut documents = Vec::new();
+        for key in &changed {
+            let note = by_path[key];
+            // A file that vanished or became unreadable since listing is skipped
+            let Ok(content) = vault.read_file(&note.path) else {
+                continue;
+            };
+            documents.push(IndexDocument {
+                path: key.clone(),
+                mtime: current[key],
+                fields: fields(note, content),
+            });
+        }
+
+        let indexed = documents.len();
+        index.update(documents, &removed)?;
+        Ok((index, indexed))
+    }
+
+    /// Open the index as it stands, `None` when there is none yet
+    pub fn open(&self) -> Option<SearchIndex> {
+        SearchIndex::open(&self.index_dir).ok().flatten()
+    }
+
+    /// Delete the index and build it again from `notes`, returning the note count
+    pub fn rebuild(
+        &self,
+        vault: &VaultService,
+        notes: &[Note],
+        fields: impl Fn(&Note, String) -> NoteFields,
+    ) -> Result<usize> {
+        if self.index_dir.exists() {
+            fs::remove_dir_all(&self.index_dir)
+                .with_context(|| format!("Failed to remove index: {}", self.index_dir.display()))?;
+        }
+        let (_, indexed) = self.sync(vault, notes, fields)?;
+        Ok(indexed)
+    }
+
+    /// Index key for a note: its path relative to the vault
+    fn key(&self, vault: &VaultService, path: &Path) -> String {
+        path.strip_prefix(vault.vault_path())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// File modification time in nanoseconds, 0 when unknown
+///
+/// Nanoseconds rather than seconds so an edit made within a second of
+/// indexing is still noticed.
+fn modified_nanos(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,177 @@
+// crates/zettel-cli/src/services/lock.rs - Vault Lock
+//
+// Operations that allocate IDs must not race: two people running
+// `zettel note create --child-of 1a` at the same time on a shared vault would
+// otherwise both compute 1a3 and one would fail (or worse, overwrite).
+//
+// LOCKING STRATEGY:
+// The lock is the file `.zettel/lock`, created with `create_new` so that only
+// one process can succeed. It holds the owner's PID for debugging and is
+// removed when the guard is dropped. Waiting processes retry for a few
+// seconds before giving up with a message pointing at the stale lock file.
+// Work that can be skipped, such as updating the search index, uses
+// `VaultLock::try_acquire` instead of waiting.
+//
+// `std::process::exit` skips destructors, so commands that bail out with an
+// error message while holding the lock must use `VaultLock::abort`.
+//
+// STALE LOCKS:
+// A lock left behind by a crashed or killed process is never removed
+// automatically. Deciding that a lock is abandoned and then removing it is
+// not atomic: another waiter could remove a lock that was just taken, and a
+// slow operation would lose its lock while still working. The timeout error
+// names the owner's PID and the lock file so the user can remove it.
+
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for another process to release the lock
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delay between attempts to take the lock
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Exclusive lock on a vault, released on drop
+#[derive(Debug)]
+pub struct VaultLock {
+    path: PathBuf,
+}
+
+impl VaultLock {
+    /// Take the vault lock, waiting briefly if another process holds it
+    pub fn acquire(vault_path: &Path) -> Result<Self> {
+        let started = Instant::now();
+
+        loop {
+            if let Some(lock) = Self::try_acquire(vault_path)? {
+                return Ok(lock);
+            }
+            if started.elapsed() >= LOCK_TIMEOUT {
+                let path = vault_path.join(".zettel").join("lock");
+                let owner = fs::read_to_string(&path)
+                    .ok()
+                    .map(|pid| format!(" (PID {})", pid.trim()))
+                    .filter(|owner| owner != " (PID )")
+                    .unwrap_or_default();
+                return Err(anyhow::anyhow!(
+                    "Vault is locked by another zettel process{}.\n\
+                     If no zettel process is running, remove the stale lock: {}",
+                    owner,
+                    path.display()
+                ));
+            }
+            thread::sleep(RETRY_INTERVAL);
+        }
+    }
+
+    /// Take the vault lock if it is free, without waiting
+    ///
+    /// `None` when another process holds it.
+    pub fn try_acquire(vault_path: &Path) -> Result<Option<Self>> {
+        let zettel_dir = vault_path.join(".zettel");
+        fs::create_dir_all(&zettel_dir).with_context(|| {
+            format!(
+                "Failed to create .zettel directory: {}",
+                zettel_dir.display()
+            )
+        })?;
+
+        let path = zettel_dir.join("lock");
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                // PID is informational only; failing to write it is harmless
+                let _ = writeln!(file, "{}", std::process::id());
+                Ok(Some(Self { path }))
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(None),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to create lock file: {}", path.display()))
+            }
+        }
+    }
+}
+
+impl VaultLock {
+    /// Release the lock, print an error and exit
+    ///
+    /// Use instead of `std::process::exit` while the lock is held, which would
+    /// leave the lock file behind.
+    pub fn abort(self, message: &str) -> ! {
+        drop(self);
+        eprintln!("❌ {}", message);
+        std::process::exit(1);
+    }
+}
+
+impl Drop for VaultLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[test]
+    fn test_concurrent_acquirers_take_turns() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = Arc::new(dir.path().to_path_buf());
+        let inside = Arc::new(AtomicBool::new(false));
+        let done = Arc::new(AtomicUsize::new(0));
+
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                let (vault, inside, done) = (vault.clone(), inside.clone(), done.clone());
+                thread::spawn(move || {
+                    for _ in 0..5 {
+                        let lock = VaultLock::acquire(&vault).unwrap();
+                        assert!(!inside.swap(true, Ordering::SeqCst), "lock held twice");
+                        thread::sleep(Duration::from_millis(20));
+                        inside.store(false, Ordering::SeqCst);
+                        drop(lock);
+                        done.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+        assert!(!vault.join(".zettel/lock").exists());
+    }
+
+    #[test]
+    fn test_old_lock_is_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = VaultLock::acquire(dir.path()).unwrap();
+        let path = dir.path().join(".zettel/lock");
+        let old = std::time::SystemTime::now() - Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        // A long-running holder keeps its lock however old the file is
+        let error = VaultLock::acquire(dir.path()).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(&format!("PID {}", std::process::id()))
+        );
+        assert!(path.exists());
+        assert!(VaultLock::try_acquire(dir.path()).unwrap().is_none());
+        drop(lock);
+        assert!(!path.exists());
+    }
+}
@@ -10,7 +10,15 @@
 // - Reusability: Services can be shared across different commands
 
 pub mod editor;
+#[cfg(feature = "search")]
+pub mod index;
+pub mod lock;
+pub mod trash;
 pub mod vault;
 
 pub use editor::EditorService;
+#[cfg(feature = "search")]
+pub use index::IndexService;
+pub use lock::VaultLock;
+pub use trash::TrashService;
 pub use vault::VaultService;
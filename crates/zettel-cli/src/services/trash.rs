@@ -0,0 +1,223 @@
+// crates/zettel-cli/src/services/trash.rs - Vault Trash
+//
+// Deleted notes are never removed outright. They are moved into
+// `.zettel/trash/`, one directory per deletion, together with a metadata
+// file recording where each file came from so it can be put back.
+//
+// LAYOUT:
+// Files keep their vault-relative paths inside the entry, so files with the
+// same name from different directories don't overwrite each other.
+// ```
+// .zettel/trash/
+// └── 20250821-143012-1a2/
+//     ├── entry.json        # TrashEntry metadata
+//     ├── 1a2 - Note.md
+//     └── literature/
+//         └── 1a2a - Child.md
+// ```
+//
+// Restoring moves the files back to their recorded paths. Links that were
+// removed or marked when the note was deleted are not restored.
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the metadata file inside each trash entry
+const ENTRY_FILE: &str = "entry.json";
+
+/// Metadata describing one trashed deletion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Directory name of the entry inside the trash, used to restore it
+    pub name: String,
+    /// ID of the note the deletion was performed on
+    pub id: String,
+    /// Why the files were trashed ("delete", "merge")
+    pub reason: String,
+    /// When the files were trashed (RFC 3339)
+    pub deleted_at: String,
+    /// Original paths of the trashed files, relative to the vault root
+    pub files: Vec<PathBuf>,
+}
+
+/// File operations on the vault trash
+pub struct TrashService {
+    vault_path: PathBuf,
+    trash_dir: PathBuf,
+}
+
+impl TrashService {
+    /// Create a trash service for the given vault
+    pub fn new(vault_path: &Path) -> Self {
+        Self {
+            vault_path: vault_path.to_path_buf(),
+            trash_dir: vault_path.join(".zettel").join("trash"),
+        }
+    }
+
+    /// Move files into a new trash entry
+    ///
+    /// `files` are absolute paths inside the vault. They are moved, not
+    /// copied, so the vault no longer sees them as notes.
+    pub fn trash_files(&self, id: &str, reason: &str, files: &[PathBuf]) -> Result<TrashEntry> {
+        let now = Local::now();
+        let base_name = format!("{}-{}", now.format("%Y%m%d-%H%M%S"), id);
+
+        // Two deletions of the same ID within a second still get distinct entries
+        let mut name = base_name.clone();
+        let mut counter = 2;
+        while self.trash_dir.join(&name).exists() {
+            name = format!("{}-{}", base_name, counter);
+            counter += 1;
+        }
+
+        let entry_dir = self.trash_dir.join(&name);
+        fs::create_dir_all(&entry_dir)
+            .with_context(|| format!("Failed to create trash entry: {}", entry_dir.display()))?;
+
+        let mut relative_files = Vec::new();
+        for file in files {
+            let relative = file
+                .strip_prefix(&self.vault_path)
+                .map_err(|_| anyhow::anyhow!("File is outside the vault: {}", file.display()))?;
+
+            let trashed = entry_dir.join(relative);
+            if let Some(parent) = trashed.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(file, &trashed)
+                .with_context(|| format!("Failed to move {} to trash", file.display()))?;
+            relative_files.push(relative.to_path_buf());
+        }
+
+        let entry = TrashEntry {
+            name,
+            id: id.to_string(),
+            reason: reason.to_string(),
+            deleted_at: now.to_rfc3339(),
+            files: relative_files,
+        };
+
+        let metadata = serde_json::to_string_pretty(&entry)?;
+        fs::write(entry_dir.join(ENTRY_FILE), metadata)
+            .with_context(|| "Failed to write trash metadata")?;
+
+        Ok(entry)
+    }
+
+    /// All trash entries, oldest first
+    ///
+    /// Entries with missing or unreadable metadata are skipped.
+    pub fn list(&self) -> Vec<TrashEntry> {
+        let Ok(dirs) = fs::read_dir(&self.trash_dir) else {
+            return Vec::new();
+        };
+
+        let mut entries: Vec<TrashEntry> = dirs
+            .flatten()
+            .filter_map(|dir| fs::read_to_string(dir.path().join(ENTRY_FILE)).ok())
+            .filter_map(|metadata| serde_json::from_str(&metadata).ok())
+            .collect();
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    /// Find an entry by its full name, or by note ID (most recent wins)
+    pub fn find(&self, name_or_id: &str) -> Option<TrashEntry> {
+        let entries = self.list();
+        if let Some(entry) = entries.iter().find(|e| e.name == name_or_id) {
+            return Some(entry.clone());
+        }
+        entries.into_iter().rev().find(|e| e.id == name_or_id)
+    }
+
+    /// Move an entry's files back to their original locations
+    ///
+    /// Fails without moving anything if any original path is taken again.
+    pub fn restore(&self, entry: &TrashEntry) -> Result<Vec<PathBuf>> {
+        let entry_dir = self.trash_dir.join(&entry.name);
+
+        let targets: Vec<PathBuf> = entry
+            .files
+            .iter()
+            .map(|relative| self.vault_path.join(relative))
+            .collect();
+
+        if let Some(taken) = targets.iter().find(|target| target.exists()) {
+            return Err(anyhow::anyhow!(
+                "Cannot restore: {} already exists",
+                taken.display()
+            ));
+        }
+
+        for (relative, target) in entry.files.iter().zip(&targets) {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(entry_dir.join(relative), target)
+                .with_context(|| format!("Failed to restore {}", target.display()))?;
+        }
+
+        fs::remove_dir_all(&entry_dir)
+            .with_context(|| format!("Failed to remove trash entry: {}", entry_dir.display()))?;
+
+        Ok(targets)
+    }
+
+    /// Permanently delete everything in the trash, returning the entry count
+    pub fn empty(&self) -> Result<usize> {
+        let count = self.list().len();
+        if self.trash_dir.exists() {
+            fs::remove_dir_all(&self.trash_dir)
+                .with_context(|| format!("Failed to empty trash: {}", self.trash_dir.display()))?;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_keeps_files_with_the_same_name_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        fs::create_dir(vault.join("literature")).unwrap();
+        fs::write(vault.join("1a.md"), "root copy").unwrap();
+        fs::write(vault.join("literature/1a.md"), "literature copy").unwrap();
+
+        let trash = TrashService::new(vault);
+        let files = [vault.join("1a.md"), vault.join("literature/1a.md")];
+        let entry = trash.trash_files("1a", "delete", &files).unwrap();
+        assert!(!files[0].exists() && !files[1].exists());
+        assert_eq!(trash.find("1a").unwrap().name, entry.name);
+
+        let restored = trash.restore(&entry).unwrap();
+        assert_eq!(restored, files);
+        assert_eq!(fs::read_to_string(&files[0]).unwrap(), "root copy");
+        assert_eq!(fs::read_to_string(&files[1]).unwrap(), "literature copy");
+        assert!(trash.list().is_empty());
+    }
+
+    #[test]
+    fn test_restore_refuses_taken_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        fs::write(vault.join("1.md"), "old").unwrap();
+
+        let trash = TrashService::new(vault);
+        let entry = trash
+            .trash_files("1", "delete", &[vault.join("1.md")])
+            .unwrap();
+        fs::write(vault.join("1.md"), "new").unwrap();
+
+        assert!(trash.restore(&entry).is_err());
+        assert_eq!(fs::read_to_string(vault.join("1.md")).unwrap(), "new");
+        assert_eq!(trash.list().len(), 1);
+    }
+}
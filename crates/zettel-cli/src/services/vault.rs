@@ -75,7 +75,7 @@ impl VaultService {
     /// to see if any have a filename that starts with the given ID.
     ///
     /// ALGORITHM:
-    /// 1. List all files in vault directory (ignoring errors for non-existent vault)
+    /// 1. List all files in the vault and its subdirectories (ignoring errors for non-existent vault)
     /// 2. Filter to only .md files
     /// 3. Extract basename without extension
     /// 4. Check if basename starts with the ID
@@ -87,6 +87,7 @@ impl VaultService {
     /// - "1a2.md" (exact match)
     /// - "1a2 - Title.md" (ID + separator)
     /// - "1a2_note.md" (ID + underscore)
+    ///
     /// But not false positives like "1a23.md" when looking for "1a2"
     ///
     /// PERFORMANCE NOTE:
@@ -102,16 +103,12 @@ impl VaultService {
             return false;
         }
 
-        // Try to read directory - if it fails, assume ID doesn't exist
-        // This is conservative: better to allow potential duplicates than fail
-        let Ok(entries) = fs::read_dir(&self.vault_path) else {
-            return false;
-        };
-
-        // Search through all directory entries for matching markdown files
-        for entry in entries.flatten() {
+        // Search through all markdown files, unreadable directories count
+        // as empty. This is conservative: better to allow potential
+        // duplicates than fail
+        for path in self.get_vault_files() {
             // Only process files we can get names for
-            if let Some(filename) = entry.file_name().to_str() {
+            if let Some(filename) = path.file_name().and_then(|name| name.to_str()) {
                 // Only consider markdown files as potential zettel notes
                 if filename.ends_with(".md") {
                     // Remove .md extension to get the base filename
@@ -146,14 +143,13 @@ impl VaultService {
     /// - Returns full PathBuf objects (commands can extract what they need)
     /// - Sorts by filename for predictable output
     /// - Handles missing vault gracefully (returns empty list)
+    /// - Recurses into subdirectories, where templates may place notes,
+    ///   skipping hidden ones (`.zettel`, `.git`) and symlinked ones
     ///
     /// CURRENT LIMITATIONS:
-    /// - Only scans vault root (doesn't recurse into subdirectories)
-    /// - No filtering for system files or directories
     /// - Loads all files into memory (not lazy)
     ///
     /// FUTURE ENHANCEMENTS:
-    /// - Support subdirectories with recursive scanning
     /// - Respect .zettelignore files
     /// - Lazy iteration for very large vaults
     /// - Filter out non-zettel markdown files
@@ -163,21 +159,7 @@ impl VaultService {
     /// Individual file access errors are ignored (skip unreadable files).
     pub fn get_vault_files(&self) -> Vec<PathBuf> {
         let mut files = Vec::new();
-
-        // Try to read vault directory - return empty list if it fails
-        let Ok(entries) = fs::read_dir(&self.vault_path) else {
-            return files;
-        };
-
-        // Process all readable directory entries
-        for entry in entries.flatten() {
-            let path = entry.path();
-
-            // Only include markdown files in the result
-            if path.extension().map_or(false, |ext| ext == "md") {
-                files.push(path);
-            }
-        }
+        collect_markdown_files(&self.vault_path, &mut files);
 
         // Sort for consistent, predictable output
         // This ensures commands always show files in the same order
@@ -247,6 +229,15 @@ impl VaultService {
         fs::read_to_string(path).with_context(|| format!("Failed to read file: {}", path.display()))
     }
 
+    /// Overwrite an existing file with new content
+    ///
+    /// Counterpart to `read_file` for commands that rewrite notes in place,
+    /// such as regenerating managed blocks.
+    pub fn write_file(&self, path: &Path, content: &str) -> Result<()> {
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write file: {}", path.display()))
+    }
+
     /// Get the directory where new files should be created
     ///
     /// This implements the business rule for where new notes should be placed.
@@ -263,6 +254,7 @@ impl VaultService {
     /// - Support year/month subdirectory organization
     /// - Respect user-configured default directory
     /// - Handle special cases (daily notes, reference notes, etc.)
+    #[allow(dead_code)]
     pub fn get_new_file_directory(&self, _current_file_path: &str) -> &Path {
         // For now, just use vault root for all new files
         // TODO: Could be enhanced to support subdirectory organization
@@ -350,12 +342,35 @@ enabled = false
 # Path to template file (relative to vault root)
 # file = "templates/note.md"
 
+# Template for stubs of missing parent notes ({{id}}, {{title}}, {{link}})
+# stub_file = "templates/stub.md"
+
 # Require {{title}} placeholder in templates
 require_title = true
 
 # Require {{link}} placeholder in templates  
 require_link = true
 
+# Value of {{author}} (defaults to $USER)
+# author = "Niklas Luhmann"
+
+# Directory of named templates, chosen with `note create --template <name>`
+# directory = "templates"
+# default_template = "default"
+
+# Constant variables, used as {{name}} in templates
+# [template.variables]
+# project = "Thesis"
+
+# Templates chosen by depth ("root" or "child"), subtree or --type; first match wins
+# [[template.rules]]
+# template = "topic"
+# depth = "root"
+#
+# [[template.rules]]
+# template = "literature"
+# under = "3"
+
 [linking]
 # Insert link to child in parent when creating children
 insert_in_parent = true
@@ -372,6 +387,25 @@ insertion_point = "end"
 # Create dedicated ## Links section when inserting
 create_links_section = false
 
+# Keep child links in a managed block, sorted by ID and regenerated on change
+managed_children = false
+
+# What to do with links to a deleted note: "remove" or "mark" (~~[[1a]]~~)
+on_delete = "remove"
+
+[linking.navigation]
+# Show the ancestor chain at the top of each note: [[1]] > [[1a]]
+breadcrumbs = false
+
+# Show links to the previous and next sibling notes
+siblings = false
+
+# Where the navigation block goes: "top" or "bottom"
+position = "top"
+
+# Text placed between breadcrumb links
+separator = " > "
+
 [editor]
 # Editor command (overrides ZETTEL_EDITOR and EDITOR env vars)
 # command = "helix"
@@ -386,8 +420,13 @@ default_format = "human"
 # Color output: "auto", "always", "never"
 color = "auto"
 
-# Use pager for long output: "auto", "always", "never"  
-pager = "auto"#;
+# Use pager for long output: "auto", "always", "never"
+pager = "auto"
+
+[searches]
+# Saved searches, run with `zettel search @name`
+# inbox = "tag:inbox -tag:done"
+"#;
 
         fs::write(zettel_dir.join("config.toml"), config_content)
             .with_context(|| "Failed to create config.toml")?;
@@ -436,6 +475,7 @@ pager = "auto"#;
     /// DESIGN: Controlled access to internal state
     /// Rather than making vault_path public, we provide this accessor method.
     /// This maintains encapsulation while allowing necessary access.
+    #[allow(dead_code)]
     pub fn vault_path(&self) -> &Path {
         &self.vault_path
     }
@@ -481,6 +521,40 @@ pager = "auto"#;
         Ok(())
     }
 
+    /// Rename a file, refusing to overwrite an existing one
+    pub fn rename_file(&self, from: &Path, to: &Path) -> Result<()> {
+        if to.exists() {
+            return Err(anyhow::anyhow!(
+                "Cannot rename {} to {}: target already exists",
+                from.display(),
+                to.display()
+            ));
+        }
+
+        fs::rename(from, to)
+            .with_context(|| format!("Failed to rename {} to {}", from.display(), to.display()))
+    }
+
+    /// Insert content at an exact byte offset in a file
+    ///
+    /// Used for cursor-position insertion from editors. The offset must lie on
+    /// a character boundary; `zettel_core::position::byte_offset` guarantees
+    /// that when converting an editor's (line, column).
+    pub fn insert_content_at(&self, file_path: &Path, offset: usize, content: &str) -> Result<()> {
+        let mut existing_content = self.read_file(file_path)?;
+
+        if !existing_content.is_char_boundary(offset) {
+            return Err(anyhow::anyhow!(
+                "Offset {} is not a valid position in {}",
+                offset,
+                file_path.display()
+            ));
+        }
+
+        existing_content.insert_str(offset, content);
+        self.write_file(file_path, &existing_content)
+    }
+
     /// Insert content at the end of file with proper spacing
     fn insert_at_end(&self, existing_content: &str, new_content: &str) -> String {
         let trimmed = existing_content.trim_end();
@@ -566,6 +640,34 @@ pager = "auto"#;
     }
 }
 
+/// Add the markdown files below `dir` to `files`, skipping hidden directories
+///
+/// Unreadable directories are skipped rather than failing the whole scan.
+/// Symlinked directories are not followed: a link back up the tree would
+/// list every note again, and the copies would look like duplicate IDs.
+/// Symlinked files are still included.
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        // file_type() describes the entry itself, not what a symlink points to
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !hidden {
+                collect_markdown_files(&path, files);
+            }
+        } else if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+}
+
 //
 //
 // SERVICE PATTERN BENEFITS:
@@ -589,3 +691,38 @@ pager = "auto"#;
 // 5. PORTABILITY:
 //    Path handling differences between Windows/Unix are isolated here.
 //    Commands don't need to worry about platform-specific file operations.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_files_skip_hidden_and_symlinked_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        fs::create_dir_all(vault.join("literature")).unwrap();
+        fs::create_dir_all(vault.join(".zettel")).unwrap();
+        fs::write(vault.join("1.md"), "# One").unwrap();
+        fs::write(vault.join("literature/1a.md"), "# Child").unwrap();
+        fs::write(vault.join(".zettel/2.md"), "# Hidden").unwrap();
+        fs::write(vault.join("notes.txt"), "not markdown").unwrap();
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(vault, vault.join("literature/loop")).unwrap();
+            std::os::unix::fs::symlink(vault.join("1.md"), vault.join("3.md")).unwrap();
+        }
+
+        let files = VaultService::new(vault.to_path_buf()).get_vault_files();
+        let mut names: Vec<String> = files
+            .iter()
+            .map(|f| f.strip_prefix(vault).unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        let mut expected = vec!["1.md", "literature/1a.md"];
+        if cfg!(unix) {
+            expected.push("3.md");
+        }
+        expected.sort();
+        assert_eq!(names, expected);
+    }
+}
@@ -0,0 +1,286 @@
+// crates/zettel-core/src/block.rs - Managed Content Blocks
+//
+// A managed block is a region of a note that zettel owns and regenerates on
+// demand. It is delimited by HTML comment markers, which are invisible in
+// rendered markdown but easy to find in plain text:
+//
+// ```markdown
+// <!-- zettel:children -->
+// - [[1a1]]
+// - [[1a2]]
+// <!-- /zettel:children -->
+// ```
+//
+// Everything between the markers is replaced wholesale on every update, so
+// the block always reflects the current state of the vault. Everything
+// outside the markers is left untouched.
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: Take note content, return new note content
+// - Idempotent: Updating a block with the same body yields identical content
+// - Non-destructive: Text outside the markers is never modified
+
+use crate::note::body_start;
+
+/// Where a managed block is placed when a note doesn't contain it yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockPosition {
+    /// Before any other content (after frontmatter, if present)
+    Top,
+    /// Directly below the first `# ` heading
+    AfterTitle,
+    /// At the end of the note
+    Bottom,
+}
+
+/// A named, marker-delimited region of note content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedBlock {
+    name: String,
+}
+
+impl ManagedBlock {
+    /// Create a block handle for the given name (e.g. "children")
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self { name: name.into() }
+    }
+
+    /// Opening marker line, e.g. `<!-- zettel:children -->`
+    pub fn start_marker(&self) -> String {
+        format!("<!-- zettel:{} -->", self.name)
+    }
+
+    /// Closing marker line, e.g. `<!-- /zettel:children -->`
+    pub fn end_marker(&self) -> String {
+        format!("<!-- /zettel:{} -->", self.name)
+    }
+
+    /// Locate the block, returning the byte range from the start of the
+    /// opening marker to the end of the closing marker
+    pub fn find(&self, content: &str) -> Option<(usize, usize)> {
+        let start_marker = self.start_marker();
+        let end_marker = self.end_marker();
+
+        let start = content.find(&start_marker)?;
+        let end = content[start..].find(&end_marker)? + start + end_marker.len();
+        Some((start, end))
+    }
+
+    /// Whether the note already contains this block
+    pub fn is_present(&self, content: &str) -> bool {
+        self.find(content).is_some()
+    }
+
+    /// Get the text between the markers, without surrounding newlines
+    pub fn body<'a>(&self, content: &'a str) -> Option<&'a str> {
+        let (start, end) = self.find(content)?;
+        let inner_start = start + self.start_marker().len();
+        let inner_end = end - self.end_marker().len();
+        Some(content[inner_start..inner_end].trim_matches('\n'))
+    }
+
+    /// Render the complete block (markers included) around the given body
+    pub fn render(&self, body: &str) -> String {
+        let body = body.trim_matches('\n');
+        if body.is_empty() {
+            format!("{}\n{}", self.start_marker(), self.end_marker())
+        } else {
+            format!("{}\n{}\n{}", self.start_marker(), body, self.end_marker())
+        }
+    }
+
+    /// Replace the block's body, inserting the block at `position` if missing
+    pub fn upsert(&self, content: &str, body: &str, position: BlockPosition) -> String {
+        let rendered = self.render(body);
+
+        if let Some((start, end)) = self.find(content) {
+            return format!("{}{}{}", &content[..start], rendered, &content[end..]);
+        }
+
+        match position {
+            BlockPosition::Top => insert_at_top(content, &rendered),
+            BlockPosition::AfterTitle => insert_after_title(content, &rendered),
+            BlockPosition::Bottom => insert_at_bottom(content, &rendered),
+        }
+    }
+
+    /// Remove the block and the blank line it leaves behind
+    pub fn remove(&self, content: &str) -> String {
+        let Some((start, end)) = self.find(content) else {
+            return content.to_string();
+        };
+
+        let before = content[..start].trim_end_matches('\n');
+        let after = content[end..].trim_start_matches('\n');
+
+        match (before.is_empty(), after.is_empty()) {
+            (true, _) => after.to_string(),
+            (false, true) => format!("{}\n", before),
+            (false, false) => format!("{}\n\n{}", before, after),
+        }
+    }
+}
+
+/// Names of the blocks in `content` starting with `prefix`, in order
+///
+/// Used for families of blocks such as `search:<name>`, where the names
+/// present in a note aren't known in advance.
+pub fn names_with_prefix(content: &str, prefix: &str) -> Vec<String> {
+    let opening = format!("<!-- zettel:{}", prefix);
+    let mut names = Vec::new();
+    for (start, _) in content.match_indices(&opening) {
+        let rest = &content[start + "<!-- zettel:".len()..];
+        if let Some(end) = rest.find(" -->") {
+            let name = &rest[..end];
+            if !name.contains(char::is_whitespace)
+                && !names.iter().any(|n| n == name)
+                && ManagedBlock::new(name).is_present(content)
+            {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+fn insert_at_top(content: &str, block: &str) -> String {
+    let split = body_start(content);
+    let (frontmatter, body) = content.split_at(split);
+    let body = body.trim_start_matches('\n');
+
+    if body.is_empty() {
+        format!("{}{}\n", frontmatter, block)
+    } else {
+        format!("{}{}\n\n{}", frontmatter, block, body)
+    }
+}
+
+fn insert_after_title(content: &str, block: &str) -> String {
+    let mut offset = body_start(content);
+
+    for line in content[offset..].split_inclusive('\n') {
+        offset += line.len();
+        if line.starts_with("# ") {
+            let (head, rest) = content.split_at(offset);
+            let head = head.trim_end_matches('\n');
+            let rest = rest.trim_start_matches('\n');
+            return if rest.is_empty() {
+                format!("{}\n\n{}\n", head, block)
+            } else {
+                format!("{}\n\n{}\n\n{}", head, block, rest)
+            };
+        }
+    }
+
+    // No title to anchor to
+    insert_at_top(content, block)
+}
+
+fn insert_at_bottom(content: &str, block: &str) -> String {
+    let trimmed = content.trim_end();
+    if trimmed.is_empty() {
+        format!("{}\n", block)
+    } else {
+        format!("{}\n\n{}\n", trimmed, block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_body() {
+        let block = ManagedBlock::new("children");
+        let rendered = block.render("- [[1a]]");
+        assert_eq!(
+            rendered,
+            "<!-- zettel:children -->\n- [[1a]]\n<!-- /zettel:children -->"
+        );
+        assert_eq!(block.body(&rendered), Some("- [[1a]]"));
+    }
+
+    #[test]
+    fn test_upsert_inserts_at_bottom() {
+        let block = ManagedBlock::new("children");
+        let content = "# Title\n\nBody text\n";
+        let updated = block.upsert(content, "- [[1a]]", BlockPosition::Bottom);
+        assert_eq!(
+            updated,
+            "# Title\n\nBody text\n\n<!-- zettel:children -->\n- [[1a]]\n<!-- /zettel:children -->\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_replaces_existing_block() {
+        let block = ManagedBlock::new("children");
+        let content =
+            "# Title\n\n<!-- zettel:children -->\n- [[old]]\n<!-- /zettel:children -->\n\nAfter\n";
+        let updated = block.upsert(content, "- [[new]]", BlockPosition::Bottom);
+        assert_eq!(
+            updated,
+            "# Title\n\n<!-- zettel:children -->\n- [[new]]\n<!-- /zettel:children -->\n\nAfter\n"
+        );
+
+        // Idempotent
+        assert_eq!(
+            block.upsert(&updated, "- [[new]]", BlockPosition::Bottom),
+            updated
+        );
+    }
+
+    #[test]
+    fn test_upsert_top_skips_frontmatter() {
+        let block = ManagedBlock::new("nav");
+        let content = "---\ntags: [a]\n---\n# Title\n";
+        let updated = block.upsert(content, "up", BlockPosition::Top);
+        assert_eq!(
+            updated,
+            "---\ntags: [a]\n---\n<!-- zettel:nav -->\nup\n<!-- /zettel:nav -->\n\n# Title\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_after_title() {
+        let block = ManagedBlock::new("nav");
+        let content = "# Title\nBody\n";
+        let updated = block.upsert(content, "up", BlockPosition::AfterTitle);
+        assert_eq!(
+            updated,
+            "# Title\n\n<!-- zettel:nav -->\nup\n<!-- /zettel:nav -->\n\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_remove_block() {
+        let block = ManagedBlock::new("children");
+        let content =
+            "# Title\n\n<!-- zettel:children -->\n- [[1a]]\n<!-- /zettel:children -->\n\nAfter\n";
+        assert_eq!(block.remove(content), "# Title\n\nAfter\n");
+
+        let content = "# Title\n\n<!-- zettel:children -->\n<!-- /zettel:children -->\n";
+        assert_eq!(block.remove(content), "# Title\n");
+
+        // Missing block is a no-op
+        assert_eq!(block.remove("# Title\n"), "# Title\n");
+    }
+
+    #[test]
+    fn test_names_with_prefix() {
+        let content = "<!-- zettel:search:inbox -->\n- [[1]]\n<!-- /zettel:search:inbox -->\n\
+            <!-- zettel:children -->\n<!-- /zettel:children -->\n\
+            <!-- zettel:search:open -->\n";
+        // Unclosed blocks don't count
+        assert_eq!(names_with_prefix(content, "search:"), vec!["search:inbox"]);
+        assert!(names_with_prefix(content, "nav").is_empty());
+    }
+
+    #[test]
+    fn test_blocks_with_different_names_are_independent() {
+        let children = ManagedBlock::new("children");
+        let nav = ManagedBlock::new("nav");
+        let content = children.upsert("# T\n", "- [[1a]]", BlockPosition::Bottom);
+        assert!(!nav.is_present(&content));
+        assert!(children.is_present(&content));
+    }
+}
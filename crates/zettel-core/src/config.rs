@@ -19,9 +19,12 @@
 // - Self-documenting: Generated config files include explanatory comments
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 use thiserror::Error;
 
+use crate::id::Id;
+
 /// Errors that can occur during configuration loading and validation
 #[derive(Error, Debug)]
 pub enum ConfigError {
@@ -88,6 +91,13 @@ pub struct ZettelConfig {
     /// Performance and caching settings
     #[serde(default)]
     pub performance: PerformanceConfig,
+
+    /// Saved searches: query language strings by name (`[searches]`)
+    ///
+    /// Run with `zettel search @name`. Names may contain letters, digits,
+    /// `-` and `_`.
+    #[serde(default)]
+    pub searches: BTreeMap<String, String>,
 }
 
 /// Vault-level configuration
@@ -201,20 +211,29 @@ pub struct TemplateConfig {
 
     /// Path to template file (relative to vault root)
     ///
-    /// Template supports {{title}} and {{link}} placeholders.
+    /// Template supports {{title}}, {{link}}, {{id}}, {{parent_id}},
+    /// {{date}}, {{time}}, {{author}} and the custom `variables`.
     #[serde(default)]
     pub file: String,
 
     /// Directory containing multiple template files
     ///
-    /// If specified, users can choose from multiple templates.
+    /// If specified, users can choose from multiple templates by name
+    /// (`note create --template academic` reads `<directory>/academic.md`).
     #[serde(default)]
     pub directory: String,
 
-    /// Default template name when directory is used
+    /// Default template name when directory is used and no file is set
     #[serde(default = "default_template_name")]
     pub default_template: String,
 
+    /// Template for stub notes created by `zettel note create-stubs`
+    ///
+    /// Path relative to vault root. Supports {{id}}, {{title}} (the stub's
+    /// ID) and {{link}}. Empty uses the built-in stub text.
+    #[serde(default)]
+    pub stub_file: String,
+
     /// Whether template validation requires {{title}} placeholder
     #[serde(default = "default_true")]
     pub require_title: bool,
@@ -222,6 +241,46 @@ pub struct TemplateConfig {
     /// Whether template validation requires {{link}} placeholder
     #[serde(default = "default_true")]
     pub require_link: bool,
+
+    /// Value of {{author}} (defaults to the USER environment variable)
+    #[serde(default)]
+    pub author: Option<String>,
+
+    /// Constant template variables (`[template.variables]`)
+    ///
+    /// Each entry becomes a {{name}} placeholder. They override built-in
+    /// variables of the same name and are overridden by `--var`.
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+
+    /// Rules choosing a template from the directory (`[[template.rules]]`)
+    ///
+    /// Checked in order when no `--template` is given; the first matching
+    /// rule wins, otherwise `file` or `default_template` is used.
+    #[serde(default)]
+    pub rules: Vec<TemplateRule>,
+}
+
+/// A rule selecting a named template for new notes
+///
+/// A rule matches when all of its conditions hold; a rule without
+/// conditions matches every note.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateRule {
+    /// Template name in the template directory, without `.md`
+    pub template: String,
+
+    /// "root" matches top-level notes, "child" all others
+    #[serde(default)]
+    pub depth: Option<String>,
+
+    /// Matches notes anywhere below this note ID
+    #[serde(default)]
+    pub under: Option<String>,
+
+    /// Matches notes created with `--type <type>`
+    #[serde(default, rename = "type")]
+    pub note_type: Option<String>,
 }
 
 /// Bidirectional linking configuration
@@ -261,6 +320,48 @@ pub struct LinkingConfig {
     /// Whether to create a dedicated links section when inserting
     #[serde(default = "default_false")]
     pub create_links_section: bool,
+
+    /// Whether parent notes keep a managed children block
+    ///
+    /// When true, child links live between `<!-- zettel:children -->` markers
+    /// and the whole block is regenerated (sorted by ID) whenever a child is
+    /// created or `zettel sync children` runs, instead of appending one link.
+    #[serde(default = "default_false")]
+    pub managed_children: bool,
+
+    /// What happens to links pointing at a deleted note
+    ///
+    /// Options: "remove" (delete the link), "mark" (strike it through: ~~[[1a]]~~)
+    #[serde(default = "default_on_delete")]
+    pub on_delete: String,
+
+    /// Breadcrumb and sibling navigation blocks
+    #[serde(default)]
+    pub navigation: NavigationConfig,
+}
+
+/// Navigation block configuration (`[linking.navigation]`)
+///
+/// Controls the managed `<!-- zettel:nav -->` block that shows where a note
+/// sits in the tree: its ancestor chain and its previous/next siblings.
+/// Links are formatted with the same rules as every other generated link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavigationConfig {
+    /// Show the ancestor chain: [[1]] > [[1a]] > [[1a2]]
+    #[serde(default = "default_false")]
+    pub breadcrumbs: bool,
+
+    /// Show links to the previous and next existing siblings
+    #[serde(default = "default_false")]
+    pub siblings: bool,
+
+    /// Where the block goes in the note: "top" or "bottom"
+    #[serde(default = "default_nav_position")]
+    pub position: String,
+
+    /// Text placed between breadcrumb links
+    #[serde(default = "default_breadcrumb_separator")]
+    pub separator: String,
 }
 /// Editor integration configuration
 ///
@@ -313,6 +414,19 @@ pub struct OutputConfig {
     pub relative_dates: bool,
 }
 
+impl OutputConfig {
+    /// Whether to color output, given whether it goes to a terminal
+    ///
+    /// "auto" colors terminals only; unknown values behave like "auto".
+    pub fn use_color(&self, is_terminal: bool) -> bool {
+        match self.color.as_str() {
+            "always" => true,
+            "never" => false,
+            _ => is_terminal,
+        }
+    }
+}
+
 /// Performance and caching configuration
 ///
 /// Controls optimizations for large vaults and resource usage.
@@ -363,6 +477,18 @@ impl ConfigManager {
     /// - Invalid TOML syntax is an error with helpful context
     /// - Validation errors include suggestions for fixes
     pub fn load_config(vault_path: Option<&Path>) -> ConfigResult<ZettelConfig> {
+        // Load defaults, config files and environment overrides, then validate
+        let config = Self::load_config_unvalidated(vault_path)?;
+        Self::validate_config(&config)?;
+
+        Ok(config)
+    }
+
+    /// Load configuration from all sources without validating it
+    ///
+    /// Only TOML syntax errors are reported. `zettel doctor` uses this to
+    /// inspect a vault whose configuration other commands would refuse.
+    pub fn load_config_unvalidated(vault_path: Option<&Path>) -> ConfigResult<ZettelConfig> {
         // Start with sensible defaults
         let mut config = ZettelConfig::default();
 
@@ -372,18 +498,15 @@ impl ConfigManager {
         }
 
         // Try to load vault-specific config file
-        if let Some(vault_path) = vault_path {
-            if let Some(vault_config) = Self::try_load_vault_config(vault_path)? {
-                config = Self::merge_configs(config, vault_config);
-            }
+        if let Some(vault_path) = vault_path
+            && let Some(vault_config) = Self::try_load_vault_config(vault_path)?
+        {
+            config = Self::merge_configs(config, vault_config);
         }
 
         // Apply environment variable overrides
         Self::apply_env_overrides(&mut config);
 
-        // Validate the final configuration
-        Self::validate_config(&config)?;
-
         Ok(config)
     }
 
@@ -436,6 +559,29 @@ enabled = false
 # Path to template file (relative to vault root)
 # file = "templates/note.md"
 
+# Template for stubs of missing parent notes ({{id}}, {{title}}, {{link}})
+# stub_file = "templates/stub.md"
+
+# Value of {{author}} (defaults to $USER)
+# author = "Niklas Luhmann"
+
+# Directory of named templates, chosen with `note create --template <name>`
+# directory = "templates"
+# default_template = "default"
+
+# Constant variables, used as {{name}} in templates
+# [template.variables]
+# project = "Thesis"
+
+# Templates chosen by depth ("root" or "child"), subtree or --type; first match wins
+# [[template.rules]]
+# template = "topic"
+# depth = "root"
+#
+# [[template.rules]]
+# template = "literature"
+# under = "3"
+
 [linking]
 # Insert link to child in parent when creating children
 insert_in_parent = true
@@ -446,6 +592,20 @@ insert_in_child = true
 # Use title as display text in links
 use_title_alias = false
 
+# Keep child links in a managed block regenerated by `zettel sync children`
+managed_children = false
+
+# Links to deleted notes: "remove" or "mark" (strike through)
+on_delete = "remove"
+
+[linking.navigation]
+# Ancestor chain and previous/next sibling links, regenerated by `zettel sync nav`
+breadcrumbs = false
+siblings = false
+
+# Where the navigation block goes: "top" or "bottom"
+position = "top"
+
 [editor]
 # Editor command (overrides ZETTEL_EDITOR and EDITOR env vars)
 # command = "helix"
@@ -472,6 +632,10 @@ cache_max_age = 3600
 
 # Use parallel processing for file operations
 parallel_processing = true
+
+[searches]
+# Saved searches, run with `zettel search @name`
+# inbox = "tag:inbox -tag:done"
 "#
         .to_string()
     }
@@ -499,8 +663,7 @@ parallel_processing = true
             return Ok(None);
         }
 
-        let config_content =
-            std::fs::read_to_string(&config_path).map_err(|e| ConfigError::IoError(e))?;
+        let config_content = std::fs::read_to_string(&config_path).map_err(ConfigError::IoError)?;
 
         let config: ZettelConfig =
             toml::from_str(&config_content).map_err(|e| ConfigError::ParseError {
@@ -551,48 +714,125 @@ parallel_processing = true
     /// Catches configuration errors that would cause runtime failures
     /// and provides helpful error messages with suggestions for fixes.
     fn validate_config(config: &ZettelConfig) -> ConfigResult<()> {
+        match Self::validation_errors(config).into_iter().next() {
+            Some(error) => Err(ConfigError::ValidationError(error)),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem `validate_config` would reject, in checking order
+    ///
+    /// Lets `zettel doctor` report all of them instead of stopping at the
+    /// first one.
+    pub fn validation_errors(config: &ZettelConfig) -> Vec<String> {
+        let mut errors = Vec::new();
+
         // Validate match rule
         match config.id.match_rule.as_str() {
             "strict" | "separator" | "fuzzy" => {}
             _ => {
-                return Err(ConfigError::ValidationError(format!(
+                errors.push(format!(
                     "Invalid match_rule '{}'. Must be one of: strict, separator, fuzzy",
                     config.id.match_rule
-                )));
+                ));
             }
         }
 
         // Validate separator is not empty when required
         if config.id.match_rule == "separator" && config.id.separator.is_empty() {
-            return Err(ConfigError::ValidationError(
-                "Separator cannot be empty when match_rule is 'separator'".to_string(),
-            ));
+            errors.push("Separator cannot be empty when match_rule is 'separator'".to_string());
         }
 
         // Validate template configuration
-        if config.template.enabled {
-            if config.template.file.is_empty() && config.template.directory.is_empty() {
-                return Err(ConfigError::ValidationError(
-                    "Template file or directory must be specified when templates are enabled"
-                        .to_string(),
-                ));
-            }
+        if config.template.enabled
+            && config.template.file.is_empty()
+            && config.template.directory.is_empty()
+        {
+            errors.push(
+                "Template file or directory must be specified when templates are enabled"
+                    .to_string(),
+            );
         }
 
         // Validate output format
         match config.output.default_format.as_str() {
             "human" | "json" | "csv" | "xml" => {}
             _ => {
-                return Err(ConfigError::ValidationError(format!(
+                errors.push(format!(
                     "Invalid output format '{}'. Must be one of: human, json, csv, xml",
                     config.output.default_format
-                )));
+                ));
+            }
+        }
+
+        // Validate dead link handling
+        match config.linking.on_delete.as_str() {
+            "remove" | "mark" => {}
+            _ => {
+                errors.push(format!(
+                    "Invalid on_delete '{}'. Must be one of: remove, mark",
+                    config.linking.on_delete
+                ));
+            }
+        }
+
+        // Validate navigation block position
+        match config.linking.navigation.position.as_str() {
+            "top" | "bottom" => {}
+            _ => {
+                errors.push(format!(
+                    "Invalid navigation position '{}'. Must be one of: top, bottom",
+                    config.linking.navigation.position
+                ));
+            }
+        }
+
+        // Validate template rules
+        for rule in &config.template.rules {
+            if rule.template.trim().is_empty() {
+                errors.push("Template rule without a template name".to_string());
+            }
+            if config.template.directory.trim().is_empty() {
+                errors.push(format!(
+                    "Template rule for '{}' needs a template directory",
+                    rule.template
+                ));
+            }
+            if let Some(depth) = rule.depth.as_deref()
+                && !matches!(depth, "root" | "child")
+            {
+                errors.push(format!(
+                    "Invalid template rule depth '{}'. Must be one of: root, child",
+                    depth
+                ));
+            }
+            if let Some(under) = rule.under.as_deref()
+                && Id::parse(under).is_err()
+            {
+                errors.push(format!(
+                    "Invalid note ID '{}' in template rule for '{}'",
+                    under, rule.template
+                ));
+            }
+        }
+
+        // Validate saved search names, which appear in `@name` and block markers
+        for name in config.searches.keys() {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                errors.push(format!(
+                    "Invalid saved search name '{}'. Use letters, digits, '-' and '_'",
+                    name
+                ));
             }
         }
 
         // Add more validation rules as needed
 
-        Ok(())
+        errors
     }
 }
 
@@ -601,11 +841,20 @@ parallel_processing = true
 /// These functions provide the default values used when config fields
 /// are missing from TOML files. They're separate functions so they can
 /// be used both for serde defaults and for documentation.
-
 fn default_link_insertion_point() -> String {
     "end".to_string()
 }
 
+fn default_on_delete() -> String {
+    "remove".to_string()
+}
+fn default_nav_position() -> String {
+    "top".to_string()
+}
+fn default_breadcrumb_separator() -> String {
+    " > ".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -660,6 +909,7 @@ impl Default for ZettelConfig {
             editor: EditorConfig::default(),
             output: OutputConfig::default(),
             performance: PerformanceConfig::default(),
+            searches: BTreeMap::new(),
         }
     }
 }
@@ -712,8 +962,12 @@ impl Default for TemplateConfig {
             file: String::new(),
             directory: String::new(),
             default_template: default_template_name(),
+            stub_file: String::new(),
             require_title: true,
             require_link: true,
+            author: None,
+            variables: BTreeMap::new(),
+            rules: Vec::new(),
         }
     }
 }
@@ -727,6 +981,20 @@ impl Default for LinkingConfig {
             format: None,
             insertion_point: default_link_insertion_point(),
             create_links_section: false,
+            managed_children: false,
+            on_delete: default_on_delete(),
+            navigation: NavigationConfig::default(),
+        }
+    }
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            breadcrumbs: false,
+            siblings: false,
+            position: default_nav_position(),
+            separator: default_breadcrumb_separator(),
         }
     }
 }
@@ -789,6 +1057,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_saved_search_validation() {
+        let mut config: ZettelConfig =
+            toml::from_str("[searches]\ninbox = \"tag:inbox -tag:done\"\n").unwrap();
+        assert_eq!(config.searches["inbox"], "tag:inbox -tag:done");
+        assert!(ConfigManager::validate_config(&config).is_ok());
+
+        config
+            .searches
+            .insert("my inbox".to_string(), "tag:inbox".to_string());
+        assert!(ConfigManager::validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_template_rules_validation() {
+        let toml = "[template]\ndirectory = \"templates\"\n\n\
+            [[template.rules]]\ntemplate = \"topic\"\ndepth = \"root\"\n\n\
+            [[template.rules]]\ntemplate = \"paper\"\nunder = \"3a\"\ntype = \"literature\"\n";
+        let mut config: ZettelConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.template.rules.len(), 2);
+        assert_eq!(
+            config.template.rules[1].note_type.as_deref(),
+            Some("literature")
+        );
+        assert!(ConfigManager::validate_config(&config).is_ok());
+
+        config.template.rules[0].depth = Some("leaf".to_string());
+        assert!(ConfigManager::validate_config(&config).is_err());
+        config.template.rules[0].depth = None;
+        config.template.rules[1].under = Some("A1".to_string());
+        assert!(ConfigManager::validate_config(&config).is_err());
+        config.template.rules[1].under = None;
+        config.template.directory.clear();
+        assert!(ConfigManager::validate_config(&config).is_err());
+    }
+
     #[test]
     fn test_invalid_match_rule_validation() {
         let mut config = ZettelConfig::default();
@@ -803,4 +1107,34 @@ mod tests {
         config.id.separator = "".to_string();
         assert!(ConfigManager::validate_config(&config).is_err());
     }
+
+    #[test]
+    fn test_navigation_table_parsing() {
+        let toml = "[linking.navigation]\nbreadcrumbs = true\nposition = \"bottom\"\n";
+        let config: ZettelConfig = toml::from_str(toml).unwrap();
+        assert!(config.linking.navigation.breadcrumbs);
+        assert!(!config.linking.navigation.siblings);
+        assert_eq!(config.linking.navigation.position, "bottom");
+        assert_eq!(config.linking.navigation.separator, " > ");
+    }
+
+    #[test]
+    fn test_validation_errors_lists_every_problem() {
+        let mut config = ZettelConfig::default();
+        assert!(ConfigManager::validation_errors(&config).is_empty());
+
+        config.id.match_rule = "loose".to_string();
+        config.linking.navigation.position = "middle".to_string();
+        let errors = ConfigManager::validation_errors(&config);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("match_rule"));
+        assert!(errors[1].contains("middle"));
+    }
+
+    #[test]
+    fn test_invalid_navigation_position() {
+        let mut config = ZettelConfig::default();
+        config.linking.navigation.position = "middle".to_string();
+        assert!(ConfigManager::validate_config(&config).is_err());
+    }
 }
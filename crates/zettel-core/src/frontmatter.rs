@@ -0,0 +1,192 @@
+// crates/zettel-core/src/frontmatter.rs - YAML Frontmatter
+//
+// Notes may start with a YAML block delimited by `---` lines:
+//
+// ```markdown
+// ---
+// tags: [physics, entropy]
+// aliases: [Second Law]
+// ---
+// # Thermodynamics
+// ```
+//
+// This module splits that block from the body and merges two blocks when
+// notes are combined.
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: No I/O, only string processing
+// - Conservative merging: Existing values are never overwritten
+// - Untouched when possible: Content without frontmatter changes is returned
+//   byte-for-byte, so YAML formatting is only normalised when merging
+
+use serde_yaml::{Mapping, Value};
+use thiserror::Error;
+
+use crate::note::body_start;
+
+/// Errors when reading frontmatter
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum FrontmatterError {
+    #[error("Invalid YAML frontmatter: {0}")]
+    InvalidYaml(String),
+
+    #[error("Frontmatter must be a mapping of keys to values")]
+    NotAMapping,
+}
+
+/// Split content into its frontmatter YAML (without delimiters) and body
+pub fn split(content: &str) -> (Option<&str>, &str) {
+    let start = body_start(content);
+    if start == 0 {
+        return (None, content);
+    }
+
+    // Strip the opening "---\n" and the closing "---" line
+    let block = &content[4..start];
+    let yaml = block
+        .trim_end_matches('\n')
+        .trim_end_matches('\r')
+        .strip_suffix("---")
+        .unwrap_or(block);
+    (Some(yaml), &content[start..])
+}
+
+/// Parse a frontmatter block into a mapping (empty YAML yields an empty map)
+pub fn parse(yaml: &str) -> Result<Mapping, FrontmatterError> {
+    if yaml.trim().is_empty() {
+        return Ok(Mapping::new());
+    }
+
+    match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Mapping(mapping)) => Ok(mapping),
+        Ok(Value::Null) => Ok(Mapping::new()),
+        Ok(_) => Err(FrontmatterError::NotAMapping),
+        Err(e) => Err(FrontmatterError::InvalidYaml(e.to_string())),
+    }
+}
+
+/// Alternative titles listed under `aliases` (or `alias`)
+///
+/// Accepts a YAML list or a comma separated string; notes without
+/// frontmatter or with invalid YAML have no aliases.
+pub fn aliases(content: &str) -> Vec<String> {
+    let Some(map) = split(content).0.and_then(|yaml| parse(yaml).ok()) else {
+        return Vec::new();
+    };
+
+    let value = map.get("aliases").or_else(|| map.get("alias"));
+    let aliases: Vec<&str> = match value {
+        Some(Value::Sequence(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+        Some(Value::String(list)) => list.split(',').collect(),
+        _ => Vec::new(),
+    };
+    aliases
+        .into_iter()
+        .map(str::trim)
+        .filter(|alias| !alias.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Merge the frontmatter of `other` into that of `content`
+///
+/// MERGE RULES:
+/// - Keys only present in `other` are added
+/// - Lists present in both are combined, without duplicates
+/// - Scalars present in both keep the value from `content`
+///
+/// `content` is returned unchanged when `other` contributes nothing.
+pub fn merge_into(content: &str, other: &str) -> Result<String, FrontmatterError> {
+    let (other_yaml, _) = split(other);
+    let other_map = match other_yaml {
+        Some(yaml) => parse(yaml)?,
+        None => return Ok(content.to_string()),
+    };
+
+    let (own_yaml, body) = split(content);
+    let mut merged = match own_yaml {
+        Some(yaml) => parse(yaml)?,
+        None => Mapping::new(),
+    };
+
+    let mut changed = false;
+    for (key, value) in other_map {
+        match merged.get_mut(&key) {
+            None => {
+                merged.insert(key, value);
+                changed = true;
+            }
+            Some(Value::Sequence(existing)) => {
+                let additions = match value {
+                    Value::Sequence(items) => items,
+                    single => vec![single],
+                };
+                for item in additions {
+                    if !existing.contains(&item) {
+                        existing.push(item);
+                        changed = true;
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    if !changed {
+        return Ok(content.to_string());
+    }
+
+    let yaml =
+        serde_yaml::to_string(&merged).map_err(|e| FrontmatterError::InvalidYaml(e.to_string()))?;
+    Ok(format!("---\n{}---\n{}", yaml, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let content = "---\ntags: [a]\n---\n# Title\n";
+        assert_eq!(split(content), (Some("tags: [a]\n"), "# Title\n"));
+        assert_eq!(split("# Title\n"), (None, "# Title\n"));
+    }
+
+    #[test]
+    fn test_aliases() {
+        let content = "---\naliases: [Second Law, \"Entropy law\"]\n---\n# Entropy\n";
+        assert_eq!(aliases(content), vec!["Second Law", "Entropy law"]);
+        assert_eq!(aliases("---\nalias: One, Two\n---\n"), vec!["One", "Two"]);
+        assert!(aliases("# No frontmatter\n").is_empty());
+    }
+
+    #[test]
+    fn test_merge_adds_keys_and_unions_lists() {
+        let keep = "---\ntags: [a, b]\nstatus: draft\n---\n# Keep\n";
+        let absorb = "---\ntags: [b, c]\nstatus: final\nsource: book\n---\n# Absorb\n";
+        let merged = merge_into(keep, absorb).unwrap();
+
+        let (yaml, body) = split(&merged);
+        let map = parse(yaml.unwrap()).unwrap();
+        assert_eq!(body, "# Keep\n");
+        assert_eq!(
+            map.get("tags"),
+            Some(&serde_yaml::from_str::<Value>("[a, b, c]").unwrap())
+        );
+        assert_eq!(map.get("status"), Some(&Value::from("draft")));
+        assert_eq!(map.get("source"), Some(&Value::from("book")));
+    }
+
+    #[test]
+    fn test_merge_without_changes_is_identity() {
+        let keep = "---\ntags:   [a]\n---\n# Keep\n";
+        assert_eq!(merge_into(keep, "# No frontmatter\n").unwrap(), keep);
+        assert_eq!(merge_into(keep, "---\ntags: [a]\n---\n").unwrap(), keep);
+    }
+
+    #[test]
+    fn test_merge_into_note_without_frontmatter() {
+        let merged = merge_into("# Keep\n", "---\ntags: [x]\n---\n# Other\n").unwrap();
+        assert!(merged.starts_with("---\ntags:\n- x\n---\n# Keep"));
+    }
+}
@@ -0,0 +1,218 @@
+// crates/zettel-core/src/fuzzy.rs - Fuzzy Note Lookup
+//
+// Finds notes from an approximate name: "entrpy" finds "Entropy", "scnd law"
+// finds a note aliased "Second Law". Used by `zettel find` to list matches
+// and by `--title` options to pick the one note a command should act on.
+//
+// MATCHING:
+// Each note is scored against its title, its frontmatter aliases and its ID
+// with the skim algorithm (case-insensitive subsequence matching that
+// favours consecutive and word-initial characters); the best of the three
+// counts. A case-insensitive exact match on any of them beats every fuzzy
+// match.
+//
+// RESOLVING TO ONE NOTE:
+// - A single exact match wins; several exact matches are ambiguous
+// - Otherwise the best fuzzy match wins when the runner-up scores clearly
+//   lower (below `AMBIGUITY_RATIO` of the best); if not, the close
+//   candidates are returned so the user can pick
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::note::Note;
+
+/// A runner-up scoring at least this fraction of the best match makes a
+/// lookup ambiguous
+pub const AMBIGUITY_RATIO: f64 = 0.8;
+
+/// Most candidates reported for an ambiguous lookup
+pub const MAX_CANDIDATES: usize = 10;
+
+/// A note that can be found, with its aliases from frontmatter
+#[derive(Debug, Clone)]
+pub struct Candidate<'a> {
+    pub note: &'a Note,
+    pub aliases: Vec<String>,
+}
+
+/// Which name of a note matched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchedOn {
+    Title,
+    Alias(String),
+    Id,
+}
+
+/// A note matching a fuzzy query
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch<'a> {
+    pub note: &'a Note,
+    pub score: i64,
+    /// Whether the query equals the matched name, ignoring case
+    pub exact: bool,
+    pub matched: MatchedOn,
+}
+
+/// Outcome of resolving a query to a single note
+#[derive(Debug, Clone)]
+pub enum Resolution<'a> {
+    Found(&'a Note),
+    /// Several notes match about equally well, best first
+    Ambiguous(Vec<FuzzyMatch<'a>>),
+    NotFound,
+}
+
+/// Notes matching `query`, best first
+///
+/// Exact matches come first, then higher scores; ties keep the order of
+/// `candidates`.
+pub fn rank<'a>(query: &str, candidates: &[Candidate<'a>]) -> Vec<FuzzyMatch<'a>> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let query = query.trim();
+
+    let mut matches: Vec<FuzzyMatch> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let note = candidate.note;
+            let mut names: Vec<(&str, MatchedOn)> = Vec::new();
+            if let Some(title) = &note.title {
+                names.push((title, MatchedOn::Title));
+            }
+            for alias in &candidate.aliases {
+                names.push((alias, MatchedOn::Alias(alias.clone())));
+            }
+            let id = note.id.to_string();
+            names.push((&id, MatchedOn::Id));
+
+            names
+                .into_iter()
+                .filter_map(|(name, matched)| {
+                    let score = matcher.fuzzy_match(name, query)?;
+                    Some(FuzzyMatch {
+                        note,
+                        score,
+                        exact: name.to_lowercase() == query.to_lowercase(),
+                        matched,
+                    })
+                })
+                .max_by_key(|m| (m.exact, m.score))
+        })
+        .collect();
+
+    matches.sort_by_key(|m| std::cmp::Reverse((m.exact, m.score)));
+    matches
+}
+
+/// Resolve `query` to the one note it most likely means
+pub fn resolve<'a>(query: &str, candidates: &[Candidate<'a>]) -> Resolution<'a> {
+    let mut matches = rank(query, candidates);
+    let Some(best) = matches.first() else {
+        return Resolution::NotFound;
+    };
+
+    if best.exact {
+        matches.retain(|m| m.exact);
+    } else {
+        let threshold = best.score as f64 * AMBIGUITY_RATIO;
+        matches.retain(|m| m.score as f64 >= threshold);
+    }
+
+    if matches.len() == 1 {
+        Resolution::Found(matches[0].note)
+    } else {
+        matches.truncate(MAX_CANDIDATES);
+        Resolution::Ambiguous(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::Id;
+    use std::path::PathBuf;
+
+    fn note(id: &str, title: &str) -> Note {
+        Note::new(
+            Id::parse(id).unwrap(),
+            PathBuf::from(format!("{}.md", id)),
+            Some(title.to_string()),
+        )
+    }
+
+    fn candidates(notes: &[Note]) -> Vec<Candidate<'_>> {
+        notes
+            .iter()
+            .map(|note| Candidate {
+                note,
+                aliases: if note.id.to_string() == "1a" {
+                    vec!["Second Law".to_string()]
+                } else {
+                    Vec::new()
+                },
+            })
+            .collect()
+    }
+
+    fn found_id(resolution: Resolution) -> Option<String> {
+        match resolution {
+            Resolution::Found(note) => Some(note.id.to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_rank() {
+        let notes = vec![
+            note("1", "Thermodynamics"),
+            note("1a", "Entropy"),
+            note("2", "Entropic time"),
+            note("3", "Cooking"),
+        ];
+        let candidates = candidates(&notes);
+
+        let ranked = rank("entrpy", &candidates);
+        assert_eq!(ranked[0].note.id.to_string(), "1a");
+        assert_eq!(ranked[0].matched, MatchedOn::Title);
+
+        let ranked = rank("second law", &candidates);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(
+            ranked[0].matched,
+            MatchedOn::Alias("Second Law".to_string())
+        );
+        assert!(ranked[0].exact);
+
+        assert!(rank("zzz", &candidates).is_empty());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let notes = vec![
+            note("1", "Thermodynamics"),
+            note("1a", "Entropy"),
+            note("2", "Entropy"),
+            note("3", "Cooking"),
+        ];
+        let candidates = candidates(&notes);
+
+        assert_eq!(
+            found_id(resolve("thermo", &candidates)),
+            Some("1".to_string())
+        );
+        assert_eq!(
+            found_id(resolve("COOKING", &candidates)),
+            Some("3".to_string())
+        );
+        assert_eq!(found_id(resolve("1a", &candidates)), Some("1a".to_string()));
+        assert!(matches!(
+            resolve("nothing like it", &candidates),
+            Resolution::NotFound
+        ));
+
+        let Resolution::Ambiguous(options) = resolve("entropy", &candidates) else {
+            panic!("expected an ambiguous lookup");
+        };
+        assert_eq!(options.len(), 2);
+    }
+}
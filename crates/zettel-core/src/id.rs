@@ -2,6 +2,7 @@
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
@@ -32,7 +33,10 @@ pub enum IdError {
 pub type IdResult<T> = Result<T, IdError>;
 
 /// Represents a single component of an ID (either numeric or alphabetic)
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+///
+/// Components are ordered the way Luhmann filed his slips (Folgezettel order):
+/// numbers by value and letters by length first, so "z" sorts before "aa".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IdComponent {
     /// Numeric component (e.g., "1", "42", "123")
     Numeric(u32),
@@ -92,6 +96,44 @@ impl IdComponent {
         matches!(self, Self::Alpha(_))
     }
 
+    /// Position of this component in its sequence, starting at 1
+    ///
+    /// "1" and "a" are both 1, "26" and "z" are 26, "aa" is 27.
+    pub fn ordinal(&self) -> u64 {
+        match self {
+            Self::Numeric(n) => u64::from(*n),
+            Self::Alpha(s) => s
+                .bytes()
+                .fold(0, |acc, b| acc * 26 + u64::from(b - b'a' + 1)),
+        }
+    }
+
+    /// Build the component of the given kind at a 1-based sequence position
+    pub fn from_ordinal(ordinal: u64, numeric: bool) -> IdResult<Self> {
+        if ordinal == 0 {
+            return Err(IdError::InvalidComponent(
+                "Component ordinals start at 1".to_string(),
+            ));
+        }
+
+        if numeric {
+            return u32::try_from(ordinal)
+                .map(Self::Numeric)
+                .map_err(|_| IdError::Overflow(format!("Numeric component: {}", ordinal)));
+        }
+
+        // Bijective base-26: 1 -> a, 26 -> z, 27 -> aa
+        let mut letters = Vec::new();
+        let mut n = ordinal;
+        while n > 0 {
+            n -= 1;
+            letters.push(b'a' + (n % 26) as u8);
+            n /= 26;
+        }
+        letters.reverse();
+        Ok(Self::Alpha(String::from_utf8(letters).unwrap_or_default()))
+    }
+
     /// Get the value as a string
     pub fn as_str(&self) -> String {
         match self {
@@ -101,6 +143,24 @@ impl IdComponent {
     }
 }
 
+impl Ord for IdComponent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            // "z" -> "aa" is an increment, so shorter sequences come first
+            (Self::Alpha(a), Self::Alpha(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            (Self::Numeric(_), Self::Alpha(_)) => Ordering::Less,
+            (Self::Alpha(_), Self::Numeric(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for IdComponent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for IdComponent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -230,7 +290,7 @@ impl Id {
         let mut child_components = self.components.clone();
 
         // Luhmann pattern: numeric -> alpha -> numeric -> alpha...
-        let next_component = if self.components.len() % 2 == 0 {
+        let next_component = if self.components.len().is_multiple_of(2) {
             // Even length means last is alpha, next should be numeric
             IdComponent::Numeric(1)
         } else {
@@ -279,6 +339,26 @@ impl Id {
             .all(|(a, b)| a == b)
     }
 
+    /// Move this ID from the subtree rooted at `from` to the one rooted at `to`
+    ///
+    /// Returns `None` when this ID is not `from` or one of its descendants.
+    /// Components below the new root keep their sequence positions but switch
+    /// between numbers and letters as needed, so moving 1a under 2 as 2b
+    /// turns 1a3 into 2b3, and moving 1a to the root as 3 turns 1a3 into 3c.
+    pub fn rebase(&self, from: &Id, to: &Id) -> Option<Id> {
+        if self != from && !from.is_ancestor_of(self) {
+            return None;
+        }
+
+        let mut components = to.components.clone();
+        for component in &self.components[from.components.len()..] {
+            let numeric = components.len().is_multiple_of(2);
+            components.push(IdComponent::from_ordinal(component.ordinal(), numeric).ok()?);
+        }
+
+        Id::new(components).ok()
+    }
+
     /// Get all ancestor IDs from root to direct parent
     pub fn ancestors(&self) -> Vec<Id> {
         let mut ancestors = Vec::new();
@@ -292,20 +372,15 @@ impl Id {
 
         ancestors
     }
-
-    /// Convert to string representation
-    pub fn to_string(&self) -> String {
-        self.components
-            .iter()
-            .map(|c| c.as_str())
-            .collect::<Vec<_>>()
-            .join("")
-    }
 }
 
 impl fmt::Display for Id {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        // String representation is the concatenation of all components
+        for component in &self.components {
+            write!(f, "{}", component)?;
+        }
+        Ok(())
     }
 }
 
@@ -346,12 +421,11 @@ where
         let patterns = self.get_filename_patterns();
 
         for pattern in patterns {
-            if let Some(captures) = pattern.captures(filename) {
-                if let Some(id_match) = captures.get(1) {
-                    if let Ok(id) = Id::parse(id_match.as_str()) {
-                        return Some(id);
-                    }
-                }
+            if let Some(captures) = pattern.captures(filename)
+                && let Some(id_match) = captures.get(1)
+                && let Ok(id) = Id::parse(id_match.as_str())
+            {
+                return Some(id);
             }
         }
 
@@ -474,7 +548,11 @@ fn parse_id_string(s: &str) -> IdResult<Vec<IdComponent>> {
 
     // Handle the last component
     if !current.is_empty() {
-        if expecting_numeric {
+        if expecting_numeric && current.chars().all(|c| c.is_ascii_lowercase()) {
+            // A lone alphabetic run (e.g. "z") is a valid component on its own;
+            // Id::new is responsible for rejecting it as a full ID
+            components.push(IdComponent::alpha(current)?);
+        } else if expecting_numeric {
             let num: u32 = current
                 .parse()
                 .map_err(|_| IdError::ParseError(format!("Invalid number: {}", current)))?;
@@ -605,11 +683,12 @@ mod tests {
 
         // Complex IDs
         let id = Id::parse("42z123a5").unwrap();
-        assert_eq!(id.components().len(), 4);
+        assert_eq!(id.components().len(), 5);
         assert_eq!(id.components()[0], IdComponent::numeric(42));
         assert_eq!(id.components()[1], IdComponent::alpha("z").unwrap());
         assert_eq!(id.components()[2], IdComponent::numeric(123));
         assert_eq!(id.components()[3], IdComponent::alpha("a").unwrap());
+        assert_eq!(id.components()[4], IdComponent::numeric(5));
 
         // Invalid cases
         assert!(Id::parse("").is_err());
@@ -703,6 +782,21 @@ mod tests {
         assert_eq!(root_child.to_string(), "1a"); // After number comes alpha
     }
 
+    #[test]
+    fn test_folgezettel_ordering() {
+        let mut ids: Vec<Id> = ["2", "1b", "1aa", "1z", "10", "1a2", "1a10", "1", "1a"]
+            .iter()
+            .map(|s| Id::parse(s).unwrap())
+            .collect();
+        ids.sort();
+
+        let sorted: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        assert_eq!(
+            sorted,
+            vec!["1", "1a", "1a2", "1a10", "1b", "1z", "1aa", "2", "10"]
+        );
+    }
+
     #[test]
     fn test_id_from_number() {
         let id = Id::from_number(42);
@@ -798,7 +892,7 @@ mod tests {
         existing_ids.insert("3".to_string());
 
         let config = IdConfig::default();
-        let manager = IdManager::new(config, |id: &str| existing_ids.contains(id));
+        let manager = IdManager::new(config.clone(), |id: &str| existing_ids.contains(id));
 
         // Next available sibling of "1" should be "4" (since "2" and "3" exist)
         let current = Id::parse("1").unwrap();
@@ -812,6 +906,7 @@ mod tests {
         // Test with alpha components
         existing_ids.insert("1b".to_string());
         existing_ids.insert("1c".to_string());
+        let manager = IdManager::new(config, |id: &str| existing_ids.contains(id));
         let next_child = manager.next_available_child(&current);
         assert_eq!(next_child.to_string(), "1d");
     }
@@ -961,6 +1056,39 @@ mod tests {
         assert!(Id::new(vec![]).is_err());
     }
 
+    #[test]
+    fn test_component_ordinals() {
+        for (text, ordinal) in [("a", 1), ("z", 26), ("aa", 27), ("az", 52), ("ba", 53)] {
+            let component = IdComponent::alpha(text).unwrap();
+            assert_eq!(component.ordinal(), ordinal);
+            assert_eq!(
+                IdComponent::from_ordinal(ordinal, false).unwrap(),
+                component
+            );
+        }
+        assert_eq!(
+            IdComponent::from_ordinal(7, true).unwrap(),
+            IdComponent::numeric(7)
+        );
+        assert!(IdComponent::from_ordinal(0, true).is_err());
+    }
+
+    #[test]
+    fn test_id_rebase() {
+        let id = |s: &str| Id::parse(s).unwrap();
+
+        // Same depth: components are kept as-is
+        assert_eq!(id("1a2b").rebase(&id("1a2"), &id("1a5")), Some(id("1a5b")));
+        assert_eq!(id("1a2").rebase(&id("1a2"), &id("1a5")), Some(id("1a5")));
+
+        // Different depth parity: components switch kind
+        assert_eq!(id("1a3").rebase(&id("1a"), &id("3")), Some(id("3c")));
+        assert_eq!(id("1a3b").rebase(&id("1a"), &id("2b")), Some(id("2b3b")));
+
+        // Outside the subtree
+        assert_eq!(id("1b").rebase(&id("1a"), &id("3")), None);
+    }
+
     #[test]
     fn test_overflow_handling() {
         // Test numeric overflow
@@ -1,6 +1,20 @@
+pub mod block;
 pub mod config;
+pub mod frontmatter;
+pub mod fuzzy;
 pub mod id;
+pub mod link;
+pub mod mention;
+pub mod note;
+pub mod position;
+pub mod query;
+#[cfg(feature = "search")]
+pub mod search;
+pub mod similarity;
+pub mod snippet;
+pub mod stats;
 pub mod template;
+pub mod validate;
 
 pub use id::*;
 pub use template::*;
@@ -0,0 +1,489 @@
+// crates/zettel-core/src/link.rs - Link Formatting
+//
+// This module owns the rules for turning a note reference into link text.
+// Every feature that writes a link into a note (parent/child linking, managed
+// blocks, editor integrations) goes through here so that the user's
+// `[linking]` configuration is honoured consistently.
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: No I/O, only string processing
+// - Configuration driven: `LinkingConfig` decides the final shape of a link
+// - Single source of truth: CLI commands never build link strings by hand
+//
+// LINK FORMATS:
+// - Default:           [[1a2 - My Note]]
+// - use_title_alias:   [[1a2 - My Note|My Note]]
+// - format template:   any string using {id}, {filename}, {title}
+//
+// LINK RECOGNITION:
+// Reading links back recognises both wiki links ([[target]], [[target|alias]],
+// [[target#heading]]) and markdown links ([text](target.md)), so links written
+// with any configured format can be found and removed again.
+
+use crate::config::LinkingConfig;
+
+/// Core link processing service
+///
+/// Like `TemplateService`, all methods are associated functions without
+/// side effects, which keeps them trivial to test and reuse.
+pub struct LinkService;
+
+impl LinkService {
+    /// Generate link text for a note based on configuration
+    ///
+    /// RESOLUTION ORDER:
+    /// 1. If `format` is configured, substitute {id}, {filename} and {title}
+    ///    (a missing title falls back to the filename)
+    /// 2. If `use_title_alias` is set and a title is known: [[filename|title]]
+    /// 3. Otherwise: [[filename]]
+    ///
+    /// `filename` is the note's file stem, without the extension.
+    pub fn format_link(
+        config: &LinkingConfig,
+        id: &str,
+        filename: &str,
+        title: Option<&str>,
+    ) -> String {
+        if let Some(format_template) = &config.format {
+            return format_template
+                .replace("{id}", id)
+                .replace("{filename}", filename)
+                .replace("{title}", title.unwrap_or(filename));
+        }
+
+        match title {
+            Some(title) if config.use_title_alias => format!("[[{}|{}]]", filename, title),
+            _ => format!("[[{}]]", filename),
+        }
+    }
+}
+
+/// A link found in note content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRef {
+    /// Byte offset of the opening bracket
+    pub start: usize,
+    /// Byte offset just past the closing bracket or parenthesis
+    pub end: usize,
+    /// Link target without alias, heading anchor or `.md` extension
+    pub target: String,
+    /// Byte range of the target name as written (possibly URL-encoded)
+    pub target_range: (usize, usize),
+}
+
+/// What to do with links to a note that no longer exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLinkAction {
+    /// Delete the link (and a line it leaves empty)
+    Remove,
+    /// Keep the link text but strike it through: ~~[[1a]]~~
+    Mark,
+}
+
+impl LinkService {
+    /// Find every wiki link and local markdown link in the content
+    pub fn find_links(content: &str) -> Vec<LinkRef> {
+        let mut links = Vec::new();
+        let mut pos = 0;
+
+        while let Some(found) = content[pos..].find('[') {
+            let start = pos + found;
+            let rest = &content[start..];
+
+            if let Some(inner) = rest.strip_prefix("[[") {
+                if let Some(close) = inner.find("]]") {
+                    let raw = &inner[..close];
+                    if !raw.contains('\n') {
+                        let end = start + 2 + close + 2;
+                        let name = raw.split('|').next().unwrap_or(raw);
+                        let (from, to) = target_span(name);
+                        links.push(LinkRef {
+                            start,
+                            end,
+                            target: name[from..to].to_string(),
+                            target_range: (start + 2 + from, start + 2 + to),
+                        });
+                        pos = end;
+                        continue;
+                    }
+                }
+            } else if let Some(text_end) = rest.find("](") {
+                let text = &rest[1..text_end];
+                let after = &rest[text_end + 2..];
+                if !text.contains('\n')
+                    && let Some(close) = after.find(')')
+                {
+                    let raw = &after[..close];
+                    if !raw.contains("://") && !raw.contains('\n') {
+                        let end = start + text_end + 2 + close + 1;
+                        let raw_start = start + text_end + 2;
+                        let (from, to) = target_span(raw);
+                        links.push(LinkRef {
+                            start,
+                            end,
+                            target: raw[from..to].replace("%20", " "),
+                            target_range: (raw_start + from, raw_start + to),
+                        });
+                        pos = end;
+                        continue;
+                    }
+                }
+            }
+
+            pos = start + 1;
+        }
+
+        links
+    }
+
+    /// Remove every link whose target satisfies `matches`
+    ///
+    /// A line left holding nothing but a list marker is removed entirely,
+    /// along with the blank line that separated it from surrounding content.
+    /// A `## Links` section emptied by the removal is removed too; sections
+    /// that were already empty are left alone. Returns the new content and
+    /// the number of links removed.
+    pub fn remove_links<F>(content: &str, matches: F) -> (String, usize)
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut lines: Vec<Line> = Vec::new();
+        let mut removed = 0;
+        let mut gap = false;
+
+        for text in content.lines() {
+            let targets: Vec<LinkRef> = Self::find_links(text)
+                .into_iter()
+                .filter(|link| matches(&link.target))
+                .collect();
+
+            if targets.is_empty() {
+                lines.push(Line::new(text, gap));
+                gap = false;
+                continue;
+            }
+
+            removed += targets.len();
+            let remaining = cut_links(text, &targets);
+            let bare = remaining.trim();
+            if bare.is_empty() || matches!(bare, "-" | "*" | "+") {
+                gap = true;
+            } else {
+                lines.push(Line::new(&remaining, gap));
+                gap = false;
+            }
+        }
+
+        if removed == 0 {
+            return (content.to_string(), 0);
+        }
+
+        remove_emptied_links_sections(&mut lines, gap);
+
+        // Drop blank lines doubled up by removals, then trailing blanks
+        let mut output: Vec<&str> = Vec::new();
+        for line in &lines {
+            let previous_blank = output.last().is_none_or(|l| l.trim().is_empty());
+            if line.gap_before && line.text.trim().is_empty() && previous_blank {
+                continue;
+            }
+            output.push(&line.text);
+        }
+        while output.last().is_some_and(|l| l.trim().is_empty()) {
+            output.pop();
+        }
+
+        let mut result = output.join("\n");
+        if content.ends_with('\n') && !result.is_empty() {
+            result.push('\n');
+        }
+        (result, removed)
+    }
+}
+
+impl LinkService {
+    /// Point links at a new target name
+    ///
+    /// `rename` maps a link's current target to its new one, or `None` to
+    /// leave the link alone. Aliases, anchors, directories and extensions are
+    /// preserved; markdown link targets stay URL-encoded if they were.
+    /// Returns the new content and the number of links changed.
+    pub fn retarget_links<F>(content: &str, rename: F) -> (String, usize)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut result = String::with_capacity(content.len());
+        let mut pos = 0;
+        let mut changed = 0;
+
+        for link in Self::find_links(content) {
+            let Some(new_target) = rename(&link.target) else {
+                continue;
+            };
+
+            let (from, to) = link.target_range;
+            let encoded = content[from..to].contains("%20");
+            result.push_str(&content[pos..from]);
+            if encoded {
+                result.push_str(&new_target.replace(' ', "%20"));
+            } else {
+                result.push_str(&new_target);
+            }
+            pos = to;
+            changed += 1;
+        }
+
+        result.push_str(&content[pos..]);
+        (result, changed)
+    }
+
+    /// Apply a dead-link action to every link whose target satisfies `matches`
+    pub fn handle_dead_links<F>(
+        content: &str,
+        action: DeadLinkAction,
+        matches: F,
+    ) -> (String, usize)
+    where
+        F: Fn(&str) -> bool,
+    {
+        match action {
+            DeadLinkAction::Remove => Self::remove_links(content, matches),
+            DeadLinkAction::Mark => Self::mark_links(content, matches),
+        }
+    }
+
+    /// Strike through every link whose target satisfies `matches`
+    ///
+    /// Links that are already struck through are left as they are, so marking
+    /// twice is harmless.
+    pub fn mark_links<F>(content: &str, matches: F) -> (String, usize)
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut result = String::with_capacity(content.len());
+        let mut pos = 0;
+        let mut marked = 0;
+
+        for link in Self::find_links(content) {
+            let already_marked =
+                content[..link.start].ends_with("~~") && content[link.end..].starts_with("~~");
+            if already_marked || !matches(&link.target) {
+                continue;
+            }
+
+            result.push_str(&content[pos..link.start]);
+            result.push_str("~~");
+            result.push_str(&content[link.start..link.end]);
+            result.push_str("~~");
+            pos = link.end;
+            marked += 1;
+        }
+
+        result.push_str(&content[pos..]);
+        (result, marked)
+    }
+}
+
+/// A line being rebuilt by `remove_links`
+struct Line {
+    text: String,
+    /// Whether a removed line used to sit directly before this one
+    gap_before: bool,
+}
+
+impl Line {
+    fn new(text: &str, gap_before: bool) -> Self {
+        Self {
+            text: text.to_string(),
+            gap_before,
+        }
+    }
+}
+
+/// Byte range of the note name inside a raw link target, skipping angle
+/// brackets, directories, the heading anchor and the `.md` extension
+fn target_span(raw: &str) -> (usize, usize) {
+    let mut end = raw.find('#').unwrap_or(raw.len());
+    let mut start = 0;
+
+    let trimmed = raw[..end].trim_end_matches(|c: char| c == '>' || c.is_whitespace());
+    end = trimmed.len();
+    start += trimmed.len()
+        - trimmed
+            .trim_start_matches(|c: char| c == '<' || c.is_whitespace())
+            .len();
+
+    if let Some(slash) = raw[start..end].rfind('/') {
+        start += slash + 1;
+    }
+    if raw[start..end].ends_with(".md") {
+        end -= 3;
+    }
+
+    (start, end)
+}
+
+/// Remove link ranges from a line, taking one adjacent space with each
+fn cut_links(text: &str, links: &[LinkRef]) -> String {
+    let mut result = String::new();
+    let mut pos = 0;
+
+    for link in links {
+        let mut start = link.start;
+        if text[..start].ends_with(' ') && text[link.end..].starts_with(' ') {
+            start -= 1;
+        }
+        result.push_str(&text[pos..start]);
+        pos = link.end;
+    }
+    result.push_str(&text[pos..]);
+    result.trim_end().to_string()
+}
+
+/// Remove `## Links` headings whose section became empty through removals
+fn remove_emptied_links_sections(lines: &mut Vec<Line>, trailing_gap: bool) {
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].text.trim_end() != "## Links" {
+            index += 1;
+            continue;
+        }
+
+        let end = lines[index + 1..]
+            .iter()
+            .position(|line| line.text.starts_with('#'))
+            .map(|offset| index + 1 + offset)
+            .unwrap_or(lines.len());
+
+        let section = &lines[index + 1..end];
+        let is_empty = section.iter().all(|line| line.text.trim().is_empty());
+        let had_removal = section.iter().any(|line| line.gap_before)
+            || lines.get(end).is_some_and(|line| line.gap_before)
+            || (end == lines.len() && trailing_gap);
+
+        if is_empty && had_removal {
+            lines.drain(index..end);
+            if let Some(next) = lines.get_mut(index) {
+                next.gap_before = true;
+            }
+        } else {
+            index = end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_link_format() {
+        let config = LinkingConfig::default();
+        assert_eq!(
+            LinkService::format_link(&config, "1a", "1a - Note", Some("Note")),
+            "[[1a - Note]]"
+        );
+    }
+
+    #[test]
+    fn test_title_alias_link_format() {
+        let config = LinkingConfig {
+            use_title_alias: true,
+            ..LinkingConfig::default()
+        };
+        assert_eq!(
+            LinkService::format_link(&config, "1a", "1a", Some("Note")),
+            "[[1a|Note]]"
+        );
+        // Without a title there is nothing to alias
+        assert_eq!(
+            LinkService::format_link(&config, "1a", "1a", None),
+            "[[1a]]"
+        );
+    }
+
+    #[test]
+    fn test_custom_link_format() {
+        let config = LinkingConfig {
+            format: Some("[{title}]({filename}.md) ({id})".to_string()),
+            ..LinkingConfig::default()
+        };
+        assert_eq!(
+            LinkService::format_link(&config, "1a", "1a - Note", Some("Note")),
+            "[Note](1a - Note.md) (1a)"
+        );
+        assert_eq!(
+            LinkService::format_link(&config, "1a", "1a", None),
+            "[1a](1a.md) (1a)"
+        );
+    }
+
+    #[test]
+    fn test_find_links() {
+        let content = "See [[1a - Note|Note]], [[1b#Part]] and [text](sub/1c%20x.md). \
+                       Ignore [web](https://example.com) and [brackets].";
+        let targets: Vec<String> = LinkService::find_links(content)
+            .into_iter()
+            .map(|l| l.target)
+            .collect();
+        assert_eq!(targets, vec!["1a - Note", "1b", "1c x"]);
+    }
+
+    #[test]
+    fn test_retarget_links() {
+        let content = "[[1a - Old|Alias]], [[1a - Old#Part]], [x](dir/1a%20-%20Old.md) [[1b]]";
+        let (result, changed) = LinkService::retarget_links(content, |target| {
+            (target == "1a - Old").then(|| "2c - Old".to_string())
+        });
+        assert_eq!(changed, 3);
+        assert_eq!(
+            result,
+            "[[2c - Old|Alias]], [[2c - Old#Part]], [x](dir/2c%20-%20Old.md) [[1b]]"
+        );
+    }
+
+    #[test]
+    fn test_mark_links() {
+        let content = "See [[1a]] and [[1b]]\n";
+        let (result, marked) = LinkService::mark_links(content, |t| t == "1a");
+        assert_eq!(marked, 1);
+        assert_eq!(result, "See ~~[[1a]]~~ and [[1b]]\n");
+
+        // Idempotent
+        assert_eq!(LinkService::mark_links(&result, |t| t == "1a").1, 0);
+    }
+
+    #[test]
+    fn test_remove_links_on_own_line() {
+        let content = "# Note\n\n[[1a]]\n\n[[1b]]\n\nEnd\n";
+        let (result, removed) = LinkService::remove_links(content, |t| t == "1a");
+        assert_eq!(removed, 1);
+        assert_eq!(result, "# Note\n\n[[1b]]\n\nEnd\n");
+    }
+
+    #[test]
+    fn test_remove_inline_link() {
+        let content = "Related to [[1a]] and more\n";
+        let (result, _) = LinkService::remove_links(content, |t| t == "1a");
+        assert_eq!(result, "Related to and more\n");
+    }
+
+    #[test]
+    fn test_remove_links_section_emptied() {
+        let content = "# Note\n\nBody\n\n## Links\n\n- [[1a]]\n";
+        let (result, _) = LinkService::remove_links(content, |t| t == "1a");
+        assert_eq!(result, "# Note\n\nBody\n");
+
+        // Section still has other links
+        let content = "# Note\n\n## Links\n\n[[1a]]\n\n[[1b]]\n\n## Next\n";
+        let (result, _) = LinkService::remove_links(content, |t| t == "1a");
+        assert_eq!(result, "# Note\n\n## Links\n\n[[1b]]\n\n## Next\n");
+
+        // Nothing removed, nothing touched
+        let content = "## Links\n\n";
+        assert_eq!(
+            LinkService::remove_links(content, |t| t == "1a"),
+            (content.to_string(), 0)
+        );
+    }
+}
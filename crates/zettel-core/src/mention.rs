@@ -0,0 +1,216 @@
+// crates/zettel-core/src/mention.rs - Unlinked Mentions
+//
+// A mention is a note's title or alias appearing as plain text in another
+// note ("... raises entropy ..." in a note that doesn't link to the
+// "Entropy" note). Mentions are candidate links: `zettel mentions` reports
+// them and can turn them into links.
+//
+// MATCHING:
+// Names match case-insensitively on word boundaries, longest name first, so
+// "Second Law" wins over "Law" at the same place. Names shorter than
+// `MIN_NAME_LENGTH` characters are ignored, being too likely to match by
+// accident. Text that can't hold a link is skipped:
+// - Frontmatter, headings and fenced code blocks
+// - Inline `code` spans and existing links
+//
+// Whether a note already links to the mentioned note is for the caller to
+// decide, since resolving link targets to notes needs the ID configuration.
+
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+use crate::link::LinkService;
+use crate::note;
+
+/// Shortest title or alias, in characters, that is looked for
+pub const MIN_NAME_LENGTH: usize = 3;
+
+/// A plain-text occurrence of a note name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// Byte range of the mention in the note content
+    pub range: Range<usize>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based character column
+    pub column: usize,
+    /// The mention as written
+    pub text: String,
+    /// The whole line containing the mention
+    pub line_text: String,
+}
+
+/// Plain-text mentions of any of `names` in `content`, in order
+///
+/// Builds a `NameMatcher` for one search; build the matcher once instead when
+/// looking for the same names in many notes.
+pub fn find(content: &str, names: &[String]) -> Vec<Mention> {
+    NameMatcher::new(names).map_or_else(Vec::new, |matcher| matcher.find(content))
+}
+
+/// Compiled pattern for the names of one note
+#[derive(Debug, Clone)]
+pub struct NameMatcher {
+    pattern: Regex,
+}
+
+impl NameMatcher {
+    /// Matcher for `names`, or `None` when none is long enough to look for
+    pub fn new(names: &[String]) -> Option<Self> {
+        let mut names: Vec<&str> = names
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| name.chars().count() >= MIN_NAME_LENGTH)
+            .collect();
+        if names.is_empty() {
+            return None;
+        }
+        // Longest first, so the regex prefers "Second Law" over "Law"
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let alternatives: Vec<String> = names.iter().map(|name| regex::escape(name)).collect();
+        let pattern = RegexBuilder::new(&format!(r"\b(?:{})\b", alternatives.join("|")))
+            .case_insensitive(true)
+            .build()
+            .ok()?;
+        Some(Self { pattern })
+    }
+
+    /// Plain-text mentions of the names in `content`, in order
+    pub fn find(&self, content: &str) -> Vec<Mention> {
+        if !self.pattern.is_match(content) {
+            return Vec::new();
+        }
+
+        let links = LinkService::find_links(content);
+        let body_start = note::body_start(content);
+        let mut mentions = Vec::new();
+        let mut offset = 0;
+        let mut in_fence = false;
+
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            let line_start = offset;
+            offset += line.len();
+            let text = line.trim_end_matches(['\n', '\r']);
+            let trimmed = text.trim_start();
+
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if line_start < body_start || in_fence || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let code = code_spans(text);
+            for found in self.pattern.find_iter(text) {
+                let range = line_start + found.start()..line_start + found.end();
+                let in_code = code
+                    .iter()
+                    .any(|span| span.start < found.end() && found.start() < span.end);
+                let in_link = links
+                    .iter()
+                    .any(|link| link.start < range.end && range.start < link.end);
+                if in_code || in_link {
+                    continue;
+                }
+                mentions.push(Mention {
+                    range,
+                    line: index + 1,
+                    column: text[..found.start()].chars().count() + 1,
+                    text: found.as_str().to_string(),
+                    line_text: text.to_string(),
+                });
+            }
+        }
+
+        mentions
+    }
+}
+
+/// Replace mentions with links, `link` giving the text for each
+///
+/// Mentions must be in content order and not overlap, as `find` returns
+/// them.
+pub fn link<F>(content: &str, mentions: &[Mention], link: F) -> String
+where
+    F: Fn(&Mention) -> String,
+{
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    for mention in mentions {
+        out.push_str(&content[last..mention.range.start]);
+        out.push_str(&link(mention));
+        last = mention.range.end;
+    }
+    out.push_str(&content[last..]);
+    out
+}
+
+/// Byte ranges of inline `code` spans in a line, backticks included
+fn code_spans(line: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut open = None;
+    for (i, c) in line.char_indices() {
+        if c == '`' {
+            match open.take() {
+                Some(start) => spans.push(start..i + 1),
+                None => open = Some(i),
+            }
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find() {
+        let content = "---\ntitle: Entropy\n---\n# Entropy notes\n\
+            Entropy and the second law.\n\
+            See [[1a]] on entropy, not `entropy` or entropic.\n\
+            ```\nentropy\n```\n";
+
+        let found = find(content, &names(&["Entropy", "Second Law", "law", "of"]));
+        let spots: Vec<(usize, usize, &str)> = found
+            .iter()
+            .map(|m| (m.line, m.column, m.text.as_str()))
+            .collect();
+        assert_eq!(
+            spots,
+            vec![(5, 1, "Entropy"), (5, 17, "second law"), (6, 15, "entropy")]
+        );
+        assert_eq!(&content[found[2].range.clone()], "entropy");
+        assert_eq!(
+            found[2].line_text,
+            "See [[1a]] on entropy, not `entropy` or entropic."
+        );
+
+        assert!(find(content, &names(&["ab"])).is_empty());
+    }
+
+    #[test]
+    fn test_name_matcher() {
+        assert!(NameMatcher::new(&names(&["ab", "  "])).is_none());
+
+        let matcher = NameMatcher::new(&names(&["Entropy"])).unwrap();
+        assert_eq!(matcher.find("Entropy grows.\n")[0].range, 0..7);
+        assert_eq!(matcher.find("See entropy.\n")[0].column, 5);
+        assert!(matcher.find("Nothing here.\n").is_empty());
+    }
+
+    #[test]
+    fn test_link() {
+        let content = "Entropy grows. So does entropy.\n";
+        let found = find(content, &names(&["entropy"]));
+        assert_eq!(
+            link(content, &found, |m| format!("[[1a|{}]]", m.text)),
+            "[[1a|Entropy]] grows. So does [[1a|entropy]].\n"
+        );
+    }
+}
@@ -0,0 +1,719 @@
+// crates/zettel-core/src/note.rs - Notes and the Note Hierarchy
+//
+// This module models the notes that exist in a vault and the Folgezettel tree
+// their IDs describe. The CLI is responsible for discovering files and reading
+// them; this module only works with the resulting data.
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: No I/O, callers provide already-loaded notes
+// - Existence aware: Tree queries only return notes that actually exist,
+//   unlike `Id::parent`/`Id::first_child` which compute hypothetical IDs
+// - Folgezettel order: Notes are always kept sorted by ID (1, 1a, 1a1, 1b, 2)
+
+use std::path::PathBuf;
+
+use crate::id::Id;
+use crate::link::LinkService;
+
+/// A note that exists in the vault
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    /// The note's Luhmann ID, parsed from its filename
+    pub id: Id,
+    /// Full path to the note file
+    pub path: PathBuf,
+    /// Title from the first `# ` heading, or from the filename as fallback
+    pub title: Option<String>,
+}
+
+impl Note {
+    /// Create a new note record
+    pub fn new(id: Id, path: PathBuf, title: Option<String>) -> Self {
+        Self { id, path, title }
+    }
+
+    /// File name without extension, as used in wiki links ("1a - Title")
+    pub fn filename(&self) -> String {
+        self.path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| self.id.to_string())
+    }
+}
+
+/// Byte offset where the note body starts, skipping YAML frontmatter
+///
+/// Frontmatter is only recognised at the very start of the file:
+/// ```text
+/// ---
+/// tags: [physics]
+/// ---
+/// # Title
+/// ```
+pub fn body_start(content: &str) -> usize {
+    if !content.starts_with("---\n") {
+        return 0;
+    }
+
+    let mut offset = 4;
+    for line in content[4..].split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return offset;
+        }
+    }
+
+    // Unterminated frontmatter: treat everything as body
+    0
+}
+
+/// Extract the note title from the first `# ` heading near the top of the body
+pub fn title_from_content(content: &str) -> Option<String> {
+    content[body_start(content)..]
+        .lines()
+        .take(5)
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// The note body without frontmatter and without its `# ` title heading
+///
+/// Only the heading line that `title_from_content` would find is removed;
+/// text before and after it is kept, so the rest of the body keeps its
+/// structure when it's appended to another note.
+pub fn strip_title(content: &str) -> String {
+    let body = &content[body_start(content)..];
+
+    let mut offset = 0;
+    for line in body.split_inclusive('\n').take(5) {
+        if line.starts_with("# ") {
+            let before = &body[..offset];
+            let after = body[offset + line.len()..].trim_start_matches('\n');
+            return if before.trim().is_empty() {
+                after.to_string()
+            } else if after.is_empty() {
+                before.to_string()
+            } else {
+                format!("{}\n\n{}", before.trim_end_matches('\n'), after)
+            };
+        }
+        offset += line.len();
+    }
+    body.to_string()
+}
+
+/// Whether a line holds only links, possibly as list items
+fn is_link_line(line: &str) -> bool {
+    let links = LinkService::find_links(line);
+    let mut rest = line.to_string();
+    for link in links.iter().rev() {
+        rest.replace_range(link.start..link.end, "");
+    }
+    !links.is_empty()
+        && rest
+            .chars()
+            .all(|c| c.is_whitespace() || matches!(c, '-' | '*' | '+' | ','))
+}
+
+/// Byte range covering lines `start..=end` (1-based), including the final
+/// line's newline
+///
+/// Returns `None` when the range is empty or extends past the end of the
+/// content.
+pub fn line_range(content: &str, start: usize, end: usize) -> Option<(usize, usize)> {
+    if start == 0 || end < start {
+        return None;
+    }
+
+    let mut offset = 0;
+    let mut range_start = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let number = index + 1;
+        if number == start {
+            range_start = Some(offset);
+        }
+        offset += line.len();
+        if number == end {
+            return range_start.map(|s| (s, offset));
+        }
+    }
+
+    None
+}
+
+/// Split selected text into a title and the remaining body
+///
+/// The first markdown heading (any level) becomes the title; without a
+/// heading, the first non-blank line does. The title line is removed from
+/// the body, and the body is trimmed of surrounding blank lines.
+///
+/// EXAMPLES:
+/// - "## Idea\nDetails"   -> (Some("Idea"), "Details")
+/// - "First line\nMore"   -> (Some("First line"), "More")
+pub fn split_title(text: &str) -> (Option<String>, String) {
+    let lines: Vec<&str> = text.lines().collect();
+
+    let heading = lines.iter().position(|line| {
+        let trimmed = line.trim_start_matches('#');
+        trimmed.len() < line.len() && trimmed.starts_with(' ')
+    });
+    let title_index = heading.or_else(|| lines.iter().position(|line| !line.trim().is_empty()));
+
+    let Some(index) = title_index else {
+        return (None, String::new());
+    };
+
+    let title = if heading.is_some() {
+        lines[index].trim_start_matches('#').trim().to_string()
+    } else {
+        lines[index].trim().to_string()
+    };
+    let body = lines
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .map(|(_, line)| *line)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let title = if title.is_empty() { None } else { Some(title) };
+    (title, body.trim_matches('\n').to_string())
+}
+
+/// A heading section of a note, as found by `sections`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// Heading text without the leading `#`s
+    pub title: String,
+    /// Byte offset of the heading line
+    pub start: usize,
+    /// Byte offset just past the section's last non-blank line
+    pub end: usize,
+    /// Section text below the heading, without surrounding blank lines
+    pub body: String,
+}
+
+/// Find every section headed at exactly `level` (`##` is level 2)
+///
+/// A section runs until the next heading of the same or a higher level, so
+/// deeper headings stay inside it. Headings in frontmatter and fenced code
+/// blocks are ignored. Blank lines after a section are left out of its range.
+///
+/// Managed blocks and the links appended at the end of a note belong to the
+/// note, not to its last section: a section also ends at a managed block, and
+/// the last one stops before trailing paragraphs that hold nothing but links.
+pub fn sections(content: &str, level: usize) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut open: Option<(String, usize)> = None;
+    let mut in_fence = false;
+    let mut last_text_end = 0;
+    // End of the text before a run of link-only paragraphs, if one is running
+    let mut links_from: Option<usize> = None;
+    let mut after_blank = false;
+    let mut has_text = false;
+
+    let mut close = |open: &mut Option<(String, usize)>, end: usize| {
+        if let Some((title, start)) = open.take() {
+            let heading_end = content[start..end]
+                .find('\n')
+                .map_or(end, |i| (start + i + 1).min(end));
+            sections.push(Section {
+                title,
+                start,
+                end,
+                body: content[heading_end..end].trim_matches('\n').to_string(),
+            });
+        }
+    };
+
+    let mut offset = body_start(content);
+    for line in content[offset..].split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        let hashes = trimmed.len() - trimmed.trim_start_matches('#').len();
+        let is_heading = !in_fence && hashes > 0 && trimmed[hashes..].starts_with(' ');
+        if is_heading && hashes <= level {
+            close(&mut open, last_text_end);
+            if hashes == level {
+                open = Some((trimmed[hashes..].trim().to_string(), offset));
+            }
+            links_from = None;
+            has_text = false;
+        } else if !in_fence && trimmed.starts_with("<!-- zettel:") {
+            close(&mut open, last_text_end);
+        } else if !trimmed.is_empty() {
+            let trailing_links = !in_fence
+                && has_text
+                && (after_blank || links_from.is_some())
+                && is_link_line(trimmed);
+            if trailing_links {
+                links_from.get_or_insert(last_text_end);
+            } else {
+                links_from = None;
+            }
+            has_text = true;
+        }
+        after_blank = trimmed.is_empty();
+
+        offset += line.len();
+        if !trimmed.is_empty() {
+            last_text_end = offset;
+        }
+    }
+    close(&mut open, links_from.unwrap_or(last_text_end));
+
+    sections
+}
+
+/// Derive a title from a filename stem by stripping the leading ID
+///
+/// EXAMPLES:
+/// - "1a2 - My Note" -> Some("My Note")
+/// - "1a2_my-note"   -> Some("my-note")
+/// - "1a2"           -> None
+pub fn title_from_filename(stem: &str, id: &Id) -> Option<String> {
+    let id_str = id.to_string();
+    let rest = stem.strip_prefix(&id_str).unwrap_or(stem);
+    let title = rest.trim_start_matches(|c: char| !c.is_alphanumeric());
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.to_string())
+    }
+}
+
+/// Replace the leading ID in a filename stem, keeping the rest
+///
+/// EXAMPLES:
+/// - ("1a2 - My Note", 1a2 -> 1b) -> "1b - My Note"
+/// - ("1a2", 1a2 -> 1b)          -> "1b"
+pub fn rename_stem(stem: &str, old: &Id, new: &Id) -> String {
+    let old_str = old.to_string();
+    match stem.strip_prefix(&old_str) {
+        Some(rest) => format!("{}{}", new, rest),
+        None => new.to_string(),
+    }
+}
+
+/// The set of existing notes, arranged in Folgezettel order
+///
+/// Duplicate IDs are kept (in the order given) so that higher-level checks can
+/// report them; lookups by ID return the first match.
+#[derive(Debug, Clone, Default)]
+pub struct NoteTree {
+    notes: Vec<Note>,
+}
+
+impl NoteTree {
+    /// Build the tree from an unordered list of notes
+    pub fn new(mut notes: Vec<Note>) -> Self {
+        // Stable sort keeps the discovery order of duplicate IDs
+        notes.sort_by(|a, b| a.id.cmp(&b.id));
+        Self { notes }
+    }
+
+    /// All notes in Folgezettel order
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Number of notes in the tree
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// Whether the vault has no notes
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Find the note with the given ID
+    pub fn get(&self, id: &Id) -> Option<&Note> {
+        let index = self.notes.partition_point(|note| note.id < *id);
+        self.notes.get(index).filter(|note| note.id == *id)
+    }
+
+    /// Every note with the given ID, in discovery order
+    ///
+    /// More than one result means the ID is ambiguous (`1a.md` next to
+    /// `1a - Title.md`); `get` returns the first of them.
+    pub fn get_all(&self, id: &Id) -> Vec<&Note> {
+        let index = self.notes.partition_point(|note| note.id < *id);
+        self.notes[index..]
+            .iter()
+            .take_while(|note| note.id == *id)
+            .collect()
+    }
+
+    /// IDs used by more than one note, in Folgezettel order
+    pub fn duplicate_ids(&self) -> Vec<&Id> {
+        let mut ids: Vec<&Id> = self
+            .notes
+            .windows(2)
+            .filter(|pair| pair[0].id == pair[1].id)
+            .map(|pair| &pair[0].id)
+            .collect();
+        ids.dedup();
+        ids
+    }
+
+    /// Whether a note with the given ID exists
+    pub fn contains(&self, id: &Id) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// IDs missing from the ancestor chains of existing notes
+    ///
+    /// With only `1` and `1a2b` present this is `[1a, 1a2]`. Results are in
+    /// Folgezettel order, so every gap comes before the gaps below it.
+    pub fn missing_ancestors(&self) -> Vec<Id> {
+        let mut missing: Vec<Id> = self
+            .notes
+            .iter()
+            .flat_map(|note| note.id.ancestors())
+            .filter(|id| !self.contains(id))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Existing direct children of a note, in Folgezettel order
+    ///
+    /// Only notes whose ID is exactly one level deeper are returned: with
+    /// `1a2b` present but `1a2` missing, `1a2b` is not a child of `1a`.
+    pub fn children(&self, id: &Id) -> Vec<&Note> {
+        self.notes
+            .iter()
+            .filter(|note| note.id.parent().ok().flatten().as_ref() == Some(id))
+            .collect()
+    }
+
+    /// The existing parent of a note, if any
+    pub fn parent(&self, id: &Id) -> Option<&Note> {
+        let parent_id = id.parent().ok().flatten()?;
+        self.get(&parent_id)
+    }
+
+    /// Existing siblings of a note (same parent, note itself included), in
+    /// Folgezettel order
+    ///
+    /// Siblings don't require the parent to exist: `1a2b` and `1a2c` are
+    /// siblings even when `1a2` is missing. All top-level notes are siblings.
+    pub fn siblings(&self, id: &Id) -> Vec<&Note> {
+        self.notes
+            .iter()
+            .filter(|note| note.id.is_sibling_of(id))
+            .collect()
+    }
+
+    /// The existing note after `id` in Luhmann reading order
+    ///
+    /// Reading order is a depth-first walk of the ID tree: a note is followed
+    /// by its first child, then by its next sibling, then by the next sibling
+    /// of its nearest ancestor (1, 1a, 1a1, 1a2, 1b, 2). Because IDs sort in
+    /// exactly this order, the walk is just the next note in the tree. `id`
+    /// itself does not need to exist.
+    pub fn next_in_order(&self, id: &Id) -> Option<&Note> {
+        let index = self.notes.partition_point(|note| note.id <= *id);
+        self.notes.get(index)
+    }
+
+    /// The existing note before `id` in Luhmann reading order
+    pub fn previous_in_order(&self, id: &Id) -> Option<&Note> {
+        let index = self.notes.partition_point(|note| note.id < *id);
+        index.checked_sub(1).and_then(|i| self.notes.get(i))
+    }
+
+    /// The closest existing sibling before a note
+    pub fn previous_sibling(&self, id: &Id) -> Option<&Note> {
+        self.siblings(id).into_iter().rfind(|note| note.id < *id)
+    }
+
+    /// The closest existing sibling after a note
+    pub fn next_sibling(&self, id: &Id) -> Option<&Note> {
+        self.siblings(id).into_iter().find(|note| note.id > *id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str) -> Note {
+        Note::new(
+            Id::parse(id).unwrap(),
+            PathBuf::from(format!("/vault/{}.md", id)),
+            None,
+        )
+    }
+
+    fn tree(ids: &[&str]) -> NoteTree {
+        NoteTree::new(ids.iter().map(|id| note(id)).collect())
+    }
+
+    fn ids(notes: &[&Note]) -> Vec<String> {
+        notes.iter().map(|n| n.id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_title_from_content() {
+        assert_eq!(
+            title_from_content("# My Note\n\nBody"),
+            Some("My Note".to_string())
+        );
+        assert_eq!(
+            title_from_content("---\ntags: [a]\n---\n# After Frontmatter\n"),
+            Some("After Frontmatter".to_string())
+        );
+        assert_eq!(title_from_content("No heading here\n## Sub\n"), None);
+    }
+
+    #[test]
+    fn test_title_from_filename() {
+        let id = Id::parse("1a2").unwrap();
+        assert_eq!(
+            title_from_filename("1a2 - My Note", &id),
+            Some("My Note".to_string())
+        );
+        assert_eq!(
+            title_from_filename("1a2_my-note", &id),
+            Some("my-note".to_string())
+        );
+        assert_eq!(title_from_filename("1a2", &id), None);
+    }
+
+    #[test]
+    fn test_strip_title() {
+        assert_eq!(
+            strip_title("---\ntags: [a]\n---\n# Title\n\nBody\n"),
+            "Body\n"
+        );
+        assert_eq!(strip_title("No heading\n## Sub\n"), "No heading\n## Sub\n");
+        assert_eq!(strip_title("\n# Title\nBody"), "Body");
+
+        // Text around the title survives
+        assert_eq!(
+            strip_title("Status: draft\n# Title\n\nBody\n"),
+            "Status: draft\n\nBody\n"
+        );
+        assert_eq!(strip_title("Intro\n# Title\n"), "Intro\n");
+    }
+
+    #[test]
+    fn test_sections() {
+        let content = "# Title\n\nIntro\n\n## One\nFirst\n### Deep\nMore\n\n## Two\n```\n## not a heading\n```\n\n# Top\n";
+        let found = sections(content, 2);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].title, "One");
+        assert_eq!(found[0].body, "First\n### Deep\nMore");
+        assert_eq!(
+            &content[found[0].start..found[0].end],
+            "## One\nFirst\n### Deep\nMore\n"
+        );
+        assert_eq!(found[1].title, "Two");
+        assert_eq!(found[1].body, "```\n## not a heading\n```");
+
+        assert!(sections("No headings\n", 2).is_empty());
+        assert_eq!(sections("## Last", 2)[0].body, "");
+    }
+
+    #[test]
+    fn test_sections_leave_note_trailer() {
+        // Links appended to the note stay out of the last section
+        let content =
+            "# Title\n\n## One\nFirst\n\n## Two\nSecond\n[[inline]]\n\n[[2]]\n- [[3|Three]]\n";
+        let found = sections(content, 2);
+        assert_eq!(found[1].body, "Second\n[[inline]]");
+        assert_eq!(&content[found[1].end..], "\n[[2]]\n- [[3|Three]]\n");
+
+        // A section of links alone keeps them
+        assert_eq!(sections("## Refs\n\n[[a]]\n", 2)[0].body, "[[a]]");
+
+        // Managed blocks end a section
+        let content =
+            "## One\nFirst\n\n<!-- zettel:children -->\n- [[1a]]\n<!-- /zettel:children -->\n";
+        let found = sections(content, 2);
+        assert_eq!(found[0].body, "First");
+        assert_eq!(found[0].end, "## One\nFirst\n".len());
+    }
+
+    #[test]
+    fn test_rename_stem() {
+        let old = Id::parse("1a2").unwrap();
+        let new = Id::parse("1b").unwrap();
+        assert_eq!(rename_stem("1a2 - My Note", &old, &new), "1b - My Note");
+        assert_eq!(rename_stem("1a2", &old, &new), "1b");
+    }
+
+    #[test]
+    fn test_body_start() {
+        assert_eq!(body_start("# Title"), 0);
+        assert_eq!(body_start("---\na: b\n---\n# Title"), 13);
+        assert_eq!(body_start("---\nunterminated"), 0);
+    }
+
+    #[test]
+    fn test_line_range() {
+        let content = "one\ntwo\nthree\n";
+        assert_eq!(line_range(content, 2, 2), Some((4, 8)));
+        assert_eq!(line_range(content, 1, 3), Some((0, content.len())));
+        assert_eq!(line_range("one\ntwo", 2, 2), Some((4, 7)));
+        assert_eq!(line_range(content, 0, 1), None);
+        assert_eq!(line_range(content, 3, 2), None);
+        assert_eq!(line_range(content, 2, 4), None);
+    }
+
+    #[test]
+    fn test_split_title() {
+        assert_eq!(
+            split_title("## Idea\nDetails here\n"),
+            (Some("Idea".to_string()), "Details here".to_string())
+        );
+        assert_eq!(
+            split_title("\nIntro sentence\n\nMore text"),
+            (Some("Intro sentence".to_string()), "More text".to_string())
+        );
+        // A heading further down wins over the first line
+        assert_eq!(
+            split_title("Lead in\n# Topic\nBody"),
+            (Some("Topic".to_string()), "Lead in\nBody".to_string())
+        );
+        // Hashtags are not headings
+        assert_eq!(split_title("#tag line").0, Some("#tag line".to_string()));
+        assert_eq!(split_title("  \n"), (None, String::new()));
+    }
+
+    #[test]
+    fn test_tree_is_sorted() {
+        let tree = tree(&["2", "1b", "1", "1a1", "1a"]);
+        let sorted: Vec<String> = tree.notes().iter().map(|n| n.id.to_string()).collect();
+        assert_eq!(sorted, vec!["1", "1a", "1a1", "1b", "2"]);
+    }
+
+    #[test]
+    fn test_tree_lookup() {
+        let tree = tree(&["1", "1a", "2"]);
+        assert!(tree.contains(&Id::parse("1a").unwrap()));
+        assert!(!tree.contains(&Id::parse("1b").unwrap()));
+        assert_eq!(
+            tree.get(&Id::parse("2").unwrap()).map(|n| n.id.to_string()),
+            Some("2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tree_duplicates() {
+        let mut notes = vec![note("1"), note("1a"), note("2")];
+        notes.push(Note::new(
+            Id::parse("1a").unwrap(),
+            PathBuf::from("/vault/1a - Again.md"),
+            None,
+        ));
+        let tree = NoteTree::new(notes);
+        let id = Id::parse("1a").unwrap();
+
+        let all = tree.get_all(&id);
+        assert_eq!(all.len(), 2);
+        assert_eq!(tree.get(&id), Some(all[0]));
+        assert_eq!(all[0].path, PathBuf::from("/vault/1a.md"));
+        assert_eq!(tree.duplicate_ids(), vec![&id]);
+        assert_eq!(tree.get_all(&Id::parse("3").unwrap()).len(), 0);
+    }
+
+    #[test]
+    fn test_tree_children_and_parent() {
+        let tree = tree(&["1", "1b", "1a", "1aa", "1z", "1a1", "1a2b", "2"]);
+        let root = Id::parse("1").unwrap();
+        assert_eq!(ids(&tree.children(&root)), vec!["1a", "1b", "1z", "1aa"]);
+
+        // 1a2b is orphaned because 1a2 doesn't exist
+        let one_a = Id::parse("1a").unwrap();
+        assert_eq!(ids(&tree.children(&one_a)), vec!["1a1"]);
+        assert!(tree.parent(&Id::parse("1a2b").unwrap()).is_none());
+
+        assert_eq!(
+            tree.parent(&Id::parse("1a1").unwrap())
+                .map(|n| n.id.to_string()),
+            Some("1a".to_string())
+        );
+        assert!(tree.parent(&root).is_none());
+    }
+
+    #[test]
+    fn test_missing_ancestors() {
+        let tree = NoteTree::new(vec![note("1a2b"), note("1"), note("1a3"), note("2c")]);
+        let missing: Vec<String> = tree
+            .missing_ancestors()
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        assert_eq!(missing, vec!["1a", "1a2", "2"]);
+
+        assert!(
+            NoteTree::new(vec![note("1"), note("1a")])
+                .missing_ancestors()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_tree_siblings() {
+        let tree = tree(&["1", "2", "1a", "1c", "1f", "1a1", "3"]);
+        let one_c = Id::parse("1c").unwrap();
+        assert_eq!(ids(&tree.siblings(&one_c)), vec!["1a", "1c", "1f"]);
+        assert_eq!(
+            tree.previous_sibling(&one_c).map(|n| n.id.to_string()),
+            Some("1a".to_string())
+        );
+        assert_eq!(
+            tree.next_sibling(&one_c).map(|n| n.id.to_string()),
+            Some("1f".to_string())
+        );
+
+        // Roots are siblings of each other
+        let two = Id::parse("2").unwrap();
+        assert_eq!(ids(&tree.siblings(&two)), vec!["1", "2", "3"]);
+        assert!(tree.next_sibling(&Id::parse("3").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let tree = tree(&["1", "1a", "1a1", "1a2", "1b", "2"]);
+        let next = |id: &str| {
+            tree.next_in_order(&Id::parse(id).unwrap())
+                .map(|n| n.id.to_string())
+        };
+        let prev = |id: &str| {
+            tree.previous_in_order(&Id::parse(id).unwrap())
+                .map(|n| n.id.to_string())
+        };
+
+        assert_eq!(next("1"), Some("1a".to_string()));
+        assert_eq!(next("1a2"), Some("1b".to_string()));
+        assert_eq!(next("1b"), Some("2".to_string()));
+        assert_eq!(next("2"), None);
+        assert_eq!(prev("1b"), Some("1a2".to_string()));
+        assert_eq!(prev("1"), None);
+
+        // Missing notes are skipped over
+        assert_eq!(next("1a3"), Some("1b".to_string()));
+    }
+
+    #[test]
+    fn test_note_filename() {
+        let note = Note::new(
+            Id::parse("1a").unwrap(),
+            PathBuf::from("/vault/1a - My Note.md"),
+            Some("My Note".to_string()),
+        );
+        assert_eq!(note.filename(), "1a - My Note");
+    }
+}
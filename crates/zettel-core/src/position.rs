@@ -0,0 +1,208 @@
+// crates/zettel-core/src/position.rs - Editor Cursor Positions
+//
+// Editors report cursor positions as (line, column), but they disagree on
+// what a column counts. Helix and Vim count characters, LSP clients count
+// UTF-16 code units by default, and some tools count raw bytes. For ASCII
+// text these agree; for "naïve" or "日本語" they don't, and inserting at the
+// wrong byte offset would split a character or corrupt the file.
+//
+// This module converts an editor position into a byte offset that is always
+// on a character boundary, and back.
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: No I/O, only string processing
+// - Explicit units: The caller states what the column counts
+// - Fail loudly: Out-of-range positions are errors, never clamped silently
+//
+// CONVENTIONS:
+// Lines and columns are 1-based, matching what editors show to users.
+// Column `len + 1` addresses the end of the line.
+
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors when resolving an editor position
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PositionError {
+    #[error("Line {line} is out of range (file has {line_count} lines)")]
+    LineOutOfRange { line: usize, line_count: usize },
+
+    #[error("Column {col} is out of range on line {line} (line has {width} columns)")]
+    ColumnOutOfRange {
+        line: usize,
+        col: usize,
+        width: usize,
+    },
+
+    #[error("Column {col} on line {line} falls inside a multi-unit character")]
+    InsideCharacter { line: usize, col: usize },
+
+    #[error("Lines and columns are 1-based, got line {line}, column {col}")]
+    ZeroPosition { line: usize, col: usize },
+
+    #[error("Unknown column unit '{0}'. Must be one of: char, byte, utf16")]
+    UnknownUnit(String),
+}
+
+/// What a column number counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnUnit {
+    /// Unicode scalar values (Helix, Vim `virtcol` for non-wide text)
+    Char,
+    /// UTF-8 bytes
+    Byte,
+    /// UTF-16 code units (LSP default)
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Width of a character in this unit
+    fn width(self, c: char) -> usize {
+        match self {
+            ColumnUnit::Char => 1,
+            ColumnUnit::Byte => c.len_utf8(),
+            ColumnUnit::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+impl FromStr for ColumnUnit {
+    type Err = PositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "char" => Ok(ColumnUnit::Char),
+            "byte" => Ok(ColumnUnit::Byte),
+            "utf16" => Ok(ColumnUnit::Utf16),
+            other => Err(PositionError::UnknownUnit(other.to_string())),
+        }
+    }
+}
+
+/// Byte offset where 1-based `line` starts, and the line's text without
+/// its line ending
+fn line_bounds(content: &str, line: usize) -> Result<(usize, &str), PositionError> {
+    let mut offset = 0;
+    let mut line_count = 0;
+
+    for (index, raw) in content.split_inclusive('\n').enumerate() {
+        line_count = index + 1;
+        if line_count == line {
+            let text = raw.trim_end_matches('\n').trim_end_matches('\r');
+            return Ok((offset, text));
+        }
+        offset += raw.len();
+    }
+
+    // The empty line after a trailing newline (or in an empty file) is addressable
+    if line == line_count + 1 && (content.is_empty() || content.ends_with('\n')) {
+        return Ok((content.len(), ""));
+    }
+
+    Err(PositionError::LineOutOfRange { line, line_count })
+}
+
+/// Convert a 1-based (line, column) position into a byte offset
+pub fn byte_offset(
+    content: &str,
+    line: usize,
+    col: usize,
+    unit: ColumnUnit,
+) -> Result<usize, PositionError> {
+    if line == 0 || col == 0 {
+        return Err(PositionError::ZeroPosition { line, col });
+    }
+
+    let (line_start, text) = line_bounds(content, line)?;
+    let target = col - 1;
+
+    let mut units = 0;
+    for (byte_index, c) in text.char_indices() {
+        if units == target {
+            return Ok(line_start + byte_index);
+        }
+        units += unit.width(c);
+        if units > target {
+            return Err(PositionError::InsideCharacter { line, col });
+        }
+    }
+
+    if units == target {
+        Ok(line_start + text.len())
+    } else {
+        Err(PositionError::ColumnOutOfRange {
+            line,
+            col,
+            width: units + 1,
+        })
+    }
+}
+
+/// Convert a byte offset back into a 0-based (line, column) pair, as used by
+/// LSP-style text edits
+pub fn line_col_at(content: &str, offset: usize, unit: ColumnUnit) -> (usize, usize) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let col = before[line_start..].chars().map(|c| unit.width(c)).sum();
+    (line, col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_offsets() {
+        let content = "hello\nworld\n";
+        assert_eq!(byte_offset(content, 1, 1, ColumnUnit::Char), Ok(0));
+        assert_eq!(byte_offset(content, 2, 3, ColumnUnit::Char), Ok(8));
+        // End of line and the empty line after the final newline
+        assert_eq!(byte_offset(content, 2, 6, ColumnUnit::Char), Ok(11));
+        assert_eq!(byte_offset(content, 3, 1, ColumnUnit::Char), Ok(12));
+    }
+
+    #[test]
+    fn test_multibyte_units() {
+        // 'é' is 2 bytes / 1 UTF-16 unit, '😀' is 4 bytes / 2 UTF-16 units
+        let content = "é😀x";
+        assert_eq!(byte_offset(content, 1, 3, ColumnUnit::Char), Ok(6));
+        assert_eq!(byte_offset(content, 1, 7, ColumnUnit::Byte), Ok(6));
+        assert_eq!(byte_offset(content, 1, 4, ColumnUnit::Utf16), Ok(6));
+
+        assert_eq!(
+            byte_offset(content, 1, 2, ColumnUnit::Byte),
+            Err(PositionError::InsideCharacter { line: 1, col: 2 })
+        );
+        assert_eq!(
+            byte_offset(content, 1, 3, ColumnUnit::Utf16),
+            Err(PositionError::InsideCharacter { line: 1, col: 3 })
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let content = "ab\ncd";
+        assert!(matches!(
+            byte_offset(content, 3, 1, ColumnUnit::Char),
+            Err(PositionError::LineOutOfRange { .. })
+        ));
+        assert!(matches!(
+            byte_offset(content, 1, 4, ColumnUnit::Char),
+            Err(PositionError::ColumnOutOfRange { .. })
+        ));
+        assert!(matches!(
+            byte_offset(content, 0, 1, ColumnUnit::Char),
+            Err(PositionError::ZeroPosition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_line_col_round_trip() {
+        let content = "# Tïtle\nsome 😀 text\n";
+        let offset = byte_offset(content, 2, 8, ColumnUnit::Utf16).unwrap();
+        assert_eq!(&content[offset..], " text\n");
+        assert_eq!(line_col_at(content, offset, ColumnUnit::Utf16), (1, 7));
+        assert_eq!(line_col_at(content, offset, ColumnUnit::Char), (1, 6));
+    }
+}
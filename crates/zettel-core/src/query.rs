@@ -0,0 +1,790 @@
+// crates/zettel-core/src/query.rs - Search Query Language
+//
+// Parses the queries accepted by `zettel search` into a backend-neutral
+// syntax tree. Each search backend compiles that tree into its own form: the
+// tantivy index (`crate::search`) into tantivy queries, the plain scan used
+// without the index through `Query::matches`.
+//
+// SYNTAX:
+// ```text
+// entropy                 word anywhere (title, body or tags)
+// entrop*                 word prefix
+// "exact phrase"          consecutive words
+// title:entropy           word in the title (also title:"a phrase", title:ent*)
+// body:entropy            word in the body
+// tag:physics             tag, from frontmatter or #inline (also tag:phys*)
+// id:1a  id:1a*           exact ID or ID prefix
+// under:3c                descendants of 3c
+// links-to:2b             notes linking to 2b
+// created:>2026-01-01     creation date compared with >, >=, <, <= or =
+// a b  /  a AND b         both
+// a OR b                  either
+// -a  /  NOT a            not
+// (a OR b) c              grouping
+// ```
+//
+// AND binds tighter than OR, so `a b OR c` means `(a AND b) OR c`.
+// Matching is case-insensitive. Words are split on non-alphanumeric
+// characters, so `non-linear` searches for the phrase "non linear".
+//
+// ERRORS:
+// `QueryError` carries the 1-based column of the offending input so
+// frontends can point at it (`QueryError::caret`).
+
+use std::fmt;
+use thiserror::Error;
+
+use crate::id::Id;
+
+/// Field names accepted before `:`, for error messages
+const FIELDS: &str = "title, body, tag, id, under, links-to, created";
+
+/// An invalid query and where in the input the problem is
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{message} (column {column})")]
+pub struct QueryError {
+    pub message: String,
+    /// 1-based character column in the query text
+    pub column: usize,
+}
+
+impl QueryError {
+    fn new(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            column: input[..offset].chars().count() + 1,
+        }
+    }
+
+    /// A line with a `^` under the offending column, to print below the query
+    pub fn caret(&self) -> String {
+        format!("{}^", " ".repeat(self.column - 1))
+    }
+}
+
+/// Result type for query parsing
+pub type QueryResult<T> = Result<T, QueryError>;
+
+/// A parsed search query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    Term(Term),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+/// A single condition on a note
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// Consecutive words, as typed; with `prefix` the last one is a prefix
+    Text {
+        field: TextField,
+        words: Vec<String>,
+        prefix: bool,
+    },
+    /// A tag (lowercase, without `#`)
+    Tag { tag: String, prefix: bool },
+    /// A note ID, or the start of one
+    Id { id: String, prefix: bool },
+    /// Descendants of a note
+    Under(Id),
+    /// Notes linking to a note
+    LinksTo(Id),
+    /// Creation date comparison
+    Created(Comparison, Date),
+}
+
+/// Where text terms are looked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    /// Title, body or tags
+    Any,
+    Title,
+    Body,
+}
+
+/// How a date is compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Before,
+    AtOrBefore,
+    On,
+    AtOrAfter,
+    After,
+}
+
+/// A calendar date, ordered chronologically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Parse exactly "YYYY-MM-DD"
+    pub fn parse(text: &str) -> Option<Date> {
+        if text.len() != 10 {
+            return None;
+        }
+        Self::parse_prefix(text)
+    }
+
+    /// Parse the date at the start of `text`
+    ///
+    /// Accepts timestamps ("2026-01-05T10:00") and, for month-only values
+    /// ("2026-01"), uses the first day of the month.
+    pub fn parse_prefix(text: &str) -> Option<Date> {
+        let number = |range: std::ops::Range<usize>| {
+            let part = text.get(range)?;
+            if !part.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            part.parse::<u16>().ok()
+        };
+        if text.get(4..5) != Some("-") {
+            return None;
+        }
+
+        let year = number(0..4)?;
+        let month = number(5..7)?;
+        let day = if text.len() == 7 {
+            1
+        } else if text.get(7..8) == Some("-") {
+            number(8..10)?
+        } else {
+            return None;
+        };
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        Some(Date {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// The date as the number YYYYMMDD, which sorts like the date
+    pub fn as_number(&self) -> u64 {
+        self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Comparison {
+    /// Whether `value` compares to `reference` this way
+    pub fn holds(&self, value: Date, reference: Date) -> bool {
+        match self {
+            Comparison::Before => value < reference,
+            Comparison::AtOrBefore => value <= reference,
+            Comparison::On => value == reference,
+            Comparison::AtOrAfter => value >= reference,
+            Comparison::After => value > reference,
+        }
+    }
+}
+
+/// The searchable data of one note
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteFields {
+    pub id: Id,
+    pub title: String,
+    pub body: String,
+    /// Lowercase tags without `#`
+    pub tags: Vec<String>,
+    /// IDs of the notes this note links to
+    pub links: Vec<Id>,
+    pub created: Option<Date>,
+}
+
+/// Split text into words the way queries and the index do
+pub fn split_words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+/// Split text into lowercase words, the form words are matched in
+pub fn words(text: &str) -> Vec<String> {
+    split_words(text).map(str::to_lowercase).collect()
+}
+
+impl Query {
+    /// Whether a note matches, for backends without an index
+    pub fn matches(&self, note: &NoteFields) -> bool {
+        match self {
+            Query::Term(term) => term.matches(note),
+            Query::Not(query) => !query.matches(note),
+            Query::And(queries) => queries.iter().all(|q| q.matches(note)),
+            Query::Or(queries) => queries.iter().any(|q| q.matches(note)),
+        }
+    }
+}
+
+impl Term {
+    fn matches(&self, note: &NoteFields) -> bool {
+        let starts = |value: &str, wanted: &str, prefix: bool| {
+            if prefix {
+                value.starts_with(wanted)
+            } else {
+                value == wanted
+            }
+        };
+        match self {
+            Term::Text {
+                field,
+                words: typed,
+                prefix,
+            } => {
+                let wanted: Vec<String> = typed.iter().map(|w| w.to_lowercase()).collect();
+                let contains = |text: &str| contains_words(&words(text), &wanted, *prefix);
+                match field {
+                    TextField::Title => contains(&note.title),
+                    TextField::Body => contains(&note.body),
+                    TextField::Any => {
+                        contains(&note.title)
+                            || contains(&note.body)
+                            || (wanted.len() == 1
+                                && note.tags.iter().any(|tag| starts(tag, &wanted[0], *prefix)))
+                    }
+                }
+            }
+            Term::Tag { tag, prefix } => note.tags.iter().any(|t| starts(t, tag, *prefix)),
+            Term::Id { id, prefix } => starts(&note.id.to_string(), id, *prefix),
+            Term::Under(id) => note.id.is_descendant_of(id),
+            Term::LinksTo(id) => note.links.contains(id),
+            Term::Created(comparison, date) => note
+                .created
+                .is_some_and(|created| comparison.holds(created, *date)),
+        }
+    }
+}
+
+/// Whether `wanted` occurs as consecutive words in `text`
+fn contains_words(text: &[String], wanted: &[String], prefix: bool) -> bool {
+    if wanted.is_empty() || wanted.len() > text.len() {
+        return false;
+    }
+    let last = wanted.len() - 1;
+    text.windows(wanted.len()).any(|window| {
+        window[..last] == wanted[..last]
+            && if prefix {
+                window[last].starts_with(&wanted[last])
+            } else {
+                window[last] == wanted[last]
+            }
+    })
+}
+
+/// Parse a query
+pub fn parse(input: &str) -> QueryResult<Query> {
+    let tokens = lex(input)?;
+    let mut parser = Parser {
+        input,
+        tokens,
+        next: 0,
+    };
+
+    if parser.tokens.is_empty() {
+        return Err(QueryError::new(input, 0, "Empty query"));
+    }
+    let query = parser.or()?;
+    if let Some(token) = parser.peek() {
+        return Err(QueryError::new(input, token.offset, "Unexpected ')'"));
+    }
+    Ok(query)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word {
+        field: Option<String>,
+        value: String,
+        quoted: bool,
+        /// Byte offset of the value, after any `field:`
+        value_offset: usize,
+    },
+    Minus,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Spanned {
+    token: Token,
+    offset: usize,
+}
+
+/// Split the input into tokens
+fn lex(input: &str) -> QueryResult<Vec<Spanned>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(offset, c)) = chars.peek() {
+        let simple = match c {
+            _ if c.is_whitespace() => None,
+            '(' => Some(Token::Open),
+            ')' => Some(Token::Close),
+            '-' => Some(Token::Minus),
+            _ => {
+                tokens.push(lex_word(input, offset, &mut chars)?);
+                continue;
+            }
+        };
+        chars.next();
+        if let Some(token) = simple {
+            tokens.push(Spanned { token, offset });
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Lex a bare word, `field:value`, `"phrase"` or `field:"phrase"`
+fn lex_word(
+    input: &str,
+    offset: usize,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+) -> QueryResult<Spanned> {
+    let mut end = offset;
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+            break;
+        }
+        end = i + c.len_utf8();
+        chars.next();
+    }
+    let word = &input[offset..end];
+
+    let (field, value_offset) = match word.split_once(':') {
+        Some((name, _))
+            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic() || c == '-') =>
+        {
+            (Some(name.to_lowercase()), offset + name.len() + 1)
+        }
+        _ => (None, offset),
+    };
+
+    // A phrase, either on its own or right after `field:`
+    let at_quote = chars.peek().is_some_and(|&(_, c)| c == '"');
+    if at_quote && (word.is_empty() || value_offset == end) {
+        let (quote, _) = chars.next().unwrap_or_default();
+        let mut phrase_end = None;
+        for (i, c) in chars.by_ref() {
+            if c == '"' {
+                phrase_end = Some(i);
+                break;
+            }
+        }
+        let Some(phrase_end) = phrase_end else {
+            return Err(QueryError::new(input, quote, "Unterminated phrase"));
+        };
+        return Ok(Spanned {
+            token: Token::Word {
+                field,
+                value: input[quote + 1..phrase_end].to_string(),
+                quoted: true,
+                value_offset: quote,
+            },
+            offset,
+        });
+    }
+
+    let token = match word {
+        "AND" => Token::And,
+        "OR" => Token::Or,
+        "NOT" => Token::Not,
+        _ => Token::Word {
+            field,
+            value: input[value_offset..end].to_string(),
+            quoted: false,
+            value_offset,
+        },
+    };
+    Ok(Spanned { token, offset })
+}
+
+/// Recursive descent parser over the tokens
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Spanned>,
+    next: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.next)
+    }
+
+    fn advance(&mut self) -> Option<Spanned> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn error(&self, offset: usize, message: impl Into<String>) -> QueryError {
+        QueryError::new(self.input, offset, message)
+    }
+
+    /// Offset of the next token, or the end of the input
+    fn here(&self) -> usize {
+        self.peek().map_or(self.input.len(), |t| t.offset)
+    }
+
+    /// or := and ("OR" and)*
+    fn or(&mut self) -> QueryResult<Query> {
+        let mut queries = vec![self.and()?];
+        while self.peek().is_some_and(|t| t.token == Token::Or) {
+            self.advance();
+            queries.push(self.and()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.remove(0)
+        } else {
+            Query::Or(queries)
+        })
+    }
+
+    /// and := unary ("AND"? unary)*
+    fn and(&mut self) -> QueryResult<Query> {
+        let mut queries = Vec::new();
+        loop {
+            match self.peek().map(|t| &t.token) {
+                None | Some(Token::Or) | Some(Token::Close) => break,
+                Some(Token::And) => {
+                    let offset = self.here();
+                    if queries.is_empty() {
+                        return Err(self.error(offset, "Expected a search term before AND"));
+                    }
+                    self.advance();
+                    if matches!(
+                        self.peek().map(|t| &t.token),
+                        None | Some(Token::Or) | Some(Token::Close) | Some(Token::And)
+                    ) {
+                        return Err(self.error(offset, "Expected a search term after AND"));
+                    }
+                }
+                Some(_) => queries.push(self.unary()?),
+            }
+        }
+
+        match queries.len() {
+            0 => {
+                let offset = self.here();
+                let message = match self.peek().map(|t| &t.token) {
+                    Some(Token::Or) => "Expected a search term before OR",
+                    Some(Token::Close) => "Unexpected ')'",
+                    _ => "Expected a search term",
+                };
+                Err(self.error(offset, message))
+            }
+            1 => Ok(queries.remove(0)),
+            _ => Ok(Query::And(queries)),
+        }
+    }
+
+    /// unary := ("-" | "NOT") unary | "(" or ")" | word
+    fn unary(&mut self) -> QueryResult<Query> {
+        let offset = self.here();
+        let Some(Spanned { token, .. }) = self.advance() else {
+            return Err(self.error(offset, "Expected a search term"));
+        };
+
+        match token {
+            Token::Minus | Token::Not => {
+                if matches!(
+                    self.peek().map(|t| &t.token),
+                    None | Some(Token::Or) | Some(Token::And) | Some(Token::Close)
+                ) {
+                    return Err(self.error(offset, "Expected a search term to exclude"));
+                }
+                Ok(Query::Not(Box::new(self.unary()?)))
+            }
+            Token::Open => {
+                let query = self.or()?;
+                match self.advance() {
+                    Some(Spanned {
+                        token: Token::Close,
+                        ..
+                    }) => Ok(query),
+                    _ => Err(self.error(offset, "Missing closing parenthesis")),
+                }
+            }
+            Token::Word {
+                field,
+                value,
+                quoted,
+                value_offset,
+            } => self
+                .term(field.as_deref(), &value, quoted, offset, value_offset)
+                .map(Query::Term),
+            Token::Close | Token::And | Token::Or => {
+                unreachable!("handled by Parser::and")
+            }
+        }
+    }
+
+    /// Build the term for one word
+    fn term(
+        &self,
+        field: Option<&str>,
+        value: &str,
+        quoted: bool,
+        offset: usize,
+        value_offset: usize,
+    ) -> QueryResult<Term> {
+        if value.is_empty() {
+            let message = match field {
+                Some(field) => format!("Missing value after '{}:'", field),
+                None => "Empty phrase".to_string(),
+            };
+            return Err(self.error(value_offset, message));
+        }
+
+        let (value, prefix) = match value.strip_suffix('*') {
+            Some(stem) if !quoted => (stem, true),
+            _ => (value, false),
+        };
+        if value.is_empty() {
+            return Err(self.error(value_offset, "'*' needs a prefix before it"));
+        }
+        let no_prefix = |name: &str| {
+            if prefix {
+                Err(self.error(
+                    value_offset,
+                    format!("'{}:' doesn't support prefix searches", name),
+                ))
+            } else {
+                Ok(())
+            }
+        };
+        let note_id = |name: &str| {
+            no_prefix(name)?;
+            Id::parse(value.to_lowercase()).map_err(|_| {
+                self.error(
+                    value_offset,
+                    format!("'{}:' takes a note ID, not '{}'", name, value),
+                )
+            })
+        };
+
+        match field {
+            None => self.text(TextField::Any, value, prefix, value_offset),
+            Some("title") => self.text(TextField::Title, value, prefix, value_offset),
+            Some("body") => self.text(TextField::Body, value, prefix, value_offset),
+            Some("tag") | Some("tags") => Ok(Term::Tag {
+                tag: value.trim_start_matches('#').to_lowercase(),
+                prefix,
+            }),
+            Some("id") => {
+                let id = value.to_lowercase();
+                if !prefix && Id::parse(&id).is_err() {
+                    return Err(self.error(value_offset, format!("Invalid note ID '{}'", value)));
+                }
+                Ok(Term::Id { id, prefix })
+            }
+            Some("under") => note_id("under").map(Term::Under),
+            Some("links-to") => note_id("links-to").map(Term::LinksTo),
+            Some("created") => {
+                no_prefix("created")?;
+                let (comparison, date) = [
+                    (">=", Comparison::AtOrAfter),
+                    ("<=", Comparison::AtOrBefore),
+                    (">", Comparison::After),
+                    ("<", Comparison::Before),
+                    ("=", Comparison::On),
+                ]
+                .into_iter()
+                .find_map(|(op, comparison)| value.strip_prefix(op).map(|date| (comparison, date)))
+                .unwrap_or((Comparison::On, value));
+                let date_offset = value_offset + value.len() - date.len();
+                Date::parse(date)
+                    .map(|date| Term::Created(comparison, date))
+                    .ok_or_else(|| {
+                        self.error(
+                            date_offset,
+                            format!("Expected a date like 2026-01-31, not '{}'", date),
+                        )
+                    })
+            }
+            Some(other) => Err(self.error(
+                offset,
+                format!("Unknown field '{}' (expected one of: {})", other, FIELDS),
+            )),
+        }
+    }
+
+    fn text(
+        &self,
+        field: TextField,
+        value: &str,
+        prefix: bool,
+        value_offset: usize,
+    ) -> QueryResult<Term> {
+        let words: Vec<String> = split_words(value).map(str::to_string).collect();
+        if words.is_empty() {
+            return Err(self.error(
+                value_offset,
+                format!("'{}' contains no searchable words", value),
+            ));
+        }
+        Ok(Term::Text {
+            field,
+            words,
+            prefix,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(word: &str, prefix: bool) -> Query {
+        Query::Term(Term::Text {
+            field: TextField::Any,
+            words: vec![word.to_string()],
+            prefix,
+        })
+    }
+
+    #[test]
+    fn test_parse_terms() {
+        let query = parse(
+            "title:entropy tag:physics -tag:draft id:1a* under:3c links-to:2b created:>2026-01-01 AND \"exact phrase\"",
+        )
+        .unwrap();
+        let Query::And(terms) = query else {
+            panic!("expected AND, got {:?}", query);
+        };
+        assert_eq!(terms.len(), 8);
+        assert_eq!(
+            terms[0],
+            Query::Term(Term::Text {
+                field: TextField::Title,
+                words: vec!["entropy".to_string()],
+                prefix: false
+            })
+        );
+        assert_eq!(
+            terms[2],
+            Query::Not(Box::new(Query::Term(Term::Tag {
+                tag: "draft".to_string(),
+                prefix: false
+            })))
+        );
+        assert_eq!(
+            terms[3],
+            Query::Term(Term::Id {
+                id: "1a".to_string(),
+                prefix: true
+            })
+        );
+        assert_eq!(terms[4], Query::Term(Term::Under(Id::parse("3c").unwrap())));
+        assert_eq!(
+            terms[6],
+            Query::Term(Term::Created(
+                Comparison::After,
+                Date::parse("2026-01-01").unwrap()
+            ))
+        );
+        assert_eq!(
+            terms[7],
+            Query::Term(Term::Text {
+                field: TextField::Any,
+                words: vec!["exact".to_string(), "phrase".to_string()],
+                prefix: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        assert_eq!(
+            parse("a b OR c").unwrap(),
+            Query::Or(vec![
+                Query::And(vec![text("a", false), text("b", false)]),
+                text("c", false)
+            ])
+        );
+        assert_eq!(
+            parse("a (b OR c*)").unwrap(),
+            Query::And(vec![
+                text("a", false),
+                Query::Or(vec![text("b", false), text("c", true)])
+            ])
+        );
+        assert_eq!(
+            parse("NOT a").unwrap(),
+            Query::Not(Box::new(text("a", false)))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| parse(input).unwrap_err();
+
+        assert_eq!(error("").message, "Empty query");
+        assert_eq!(error("a \"open").column, 3);
+        assert_eq!(error("a (b").column, 3);
+        assert_eq!(error("a )").column, 3);
+        assert_eq!(error("a OR").column, 5);
+        assert_eq!(error("AND a").column, 1);
+        assert!(
+            error("colour:red")
+                .message
+                .starts_with("Unknown field 'colour'")
+        );
+        assert_eq!(error("under:x-y").column, 7);
+        assert_eq!(error("under:3c*").column, 7);
+        assert_eq!(error("created:>2026-13-01").column, 10);
+        assert_eq!(error("é title:").column, 9);
+        assert_eq!(error("x created:bad").caret(), "          ^");
+    }
+
+    #[test]
+    fn test_matches() {
+        let note = NoteFields {
+            id: Id::parse("1a2").unwrap(),
+            title: "Entropy and Time".to_string(),
+            body: "Heat flows from hot to cold, a non-linear process.".to_string(),
+            tags: vec!["physics".to_string()],
+            links: vec![Id::parse("2b").unwrap()],
+            created: Date::parse("2026-02-01"),
+        };
+        let matches = |input: &str| parse(input).unwrap().matches(&note);
+
+        assert!(matches("entropy heat"));
+        assert!(matches("title:entrop* -tag:draft"));
+        assert!(!matches("title:heat"));
+        assert!(matches("\"hot to cold\" non-linear"));
+        assert!(!matches("\"cold to hot\""));
+        assert!(matches("physics"));
+        assert!(matches("tag:phys* id:1a*"));
+        assert!(matches("under:1a under:1 links-to:2b"));
+        assert!(!matches("under:1a2"));
+        assert!(matches("created:>2026-01-01 created:<=2026-02-01"));
+        assert!(!matches("created:2026-01-01"));
+        assert!(matches("missing OR time"));
+    }
+
+    #[test]
+    fn test_date() {
+        let date = Date::parse_prefix("2026-03-05T10:00:00Z").unwrap();
+        assert_eq!(date.to_string(), "2026-03-05");
+        assert_eq!(date.as_number(), 20260305);
+        assert_eq!(Date::parse_prefix("2026-03").unwrap().day, 1);
+        assert_eq!(Date::parse("2026-03"), None);
+        assert_eq!(Date::parse("2026-3-05"), None);
+    }
+}
@@ -0,0 +1,525 @@
+// crates/zettel-core/src/search.rs - Full-Text Search Index
+//
+// An on-disk tantivy index of the vault's notes, available with the `search`
+// feature. The CLI decides which files are notes and reads them; this module
+// owns the index schema, keeps it in step with the files it is given, and
+// answers queries ranked by BM25.
+//
+// Unlike the rest of zettel-core this module does I/O, but only inside the
+// index directory it is handed.
+//
+// SCHEMA:
+// - path:      file path, the document key for incremental updates
+// - mtime:     file modification time, to detect changed files
+// - id:        note ID, indexed as a single token
+// - title:     tokenized, boosted over body matches
+// - body:      tokenized note content
+// - tags:      one token per tag
+// - ancestors: IDs of the note's ancestors, for `under:`
+// - links:     IDs of linked notes, for `links-to:`
+// - created:   creation date as YYYYMMDD, for `created:` ranges
+//
+// QUERIES:
+// Queries are parsed by `crate::query` and compiled here into tantivy
+// queries, so both search backends accept the same language. Titles count
+// double when a word may match anywhere.
+
+use std::collections::HashMap;
+use std::ops::Bound;
+use std::path::Path;
+
+use tantivy::collector::{DocSetCollector, TopDocs};
+use tantivy::query::{
+    AllQuery, BooleanQuery, BoostQuery, Occur, PhrasePrefixQuery, PhraseQuery, Query, RangeQuery,
+    RegexQuery, TermQuery,
+};
+use tantivy::schema::{Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TEXT, Value};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term, doc};
+use thiserror::Error;
+
+use crate::query::{self, Comparison, NoteFields, TextField};
+
+/// Memory budget for the index writer
+const WRITER_MEMORY: usize = 50_000_000;
+
+/// Score multiplier for title matches in queries that search every field
+const TITLE_BOOST: f32 = 2.0;
+
+/// Errors from building or querying the search index
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("Search index error: {0}")]
+    Index(#[from] tantivy::TantivyError),
+
+    #[error("Cannot open index directory: {0}")]
+    Directory(#[from] tantivy::directory::error::OpenDirectoryError),
+
+    #[error("Invalid search query: {0}")]
+    Query(String),
+}
+
+/// Result type for search operations
+pub type SearchResult<T> = Result<T, SearchError>;
+
+/// A note file as it is stored in the index
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexDocument {
+    pub path: String,
+    pub mtime: u64,
+    pub fields: NoteFields,
+}
+
+/// A note matching a query, best matches first
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub id: String,
+    pub path: String,
+    pub title: String,
+    pub score: f32,
+}
+
+/// Fields of the index schema
+#[derive(Debug, Clone, Copy)]
+struct Fields {
+    path: Field,
+    mtime: Field,
+    id: Field,
+    title: Field,
+    body: Field,
+    tags: Field,
+    ancestors: Field,
+    links: Field,
+    created: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Fields) {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            path: builder.add_text_field("path", STRING | STORED),
+            mtime: builder.add_u64_field("mtime", STORED),
+            id: builder.add_text_field("id", STRING | STORED),
+            title: builder.add_text_field("title", TEXT | STORED),
+            body: builder.add_text_field("body", TEXT),
+            tags: builder.add_text_field("tags", STRING),
+            ancestors: builder.add_text_field("ancestors", STRING),
+            links: builder.add_text_field("links", STRING),
+            created: builder.add_u64_field("created", INDEXED),
+        };
+        (builder.build(), fields)
+    }
+}
+
+/// Files to re-index and indexed paths to drop, given current file mtimes
+///
+/// A file is re-indexed when it is new or its mtime differs from the
+/// indexed one. Results are sorted for stable processing.
+pub fn plan_update(
+    indexed: &HashMap<String, u64>,
+    current: &HashMap<String, u64>,
+) -> (Vec<String>, Vec<String>) {
+    let mut changed: Vec<String> = current
+        .iter()
+        .filter(|(path, mtime)| indexed.get(*path) != Some(mtime))
+        .map(|(path, _)| path.clone())
+        .collect();
+    let mut removed: Vec<String> = indexed
+        .keys()
+        .filter(|path| !current.contains_key(*path))
+        .cloned()
+        .collect();
+    changed.sort();
+    removed.sort();
+    (changed, removed)
+}
+
+/// The vault's full-text index
+pub struct SearchIndex {
+    index: Index,
+    reader: IndexReader,
+    fields: Fields,
+}
+
+impl SearchIndex {
+    /// Open the index in `dir`, creating it (or recreating it after a schema
+    /// change) when needed
+    pub fn open_or_create(dir: &Path) -> SearchResult<Self> {
+        if let Some(index) = Self::open(dir)? {
+            return Ok(index);
+        }
+
+        let (schema, _) = Fields::schema();
+        if dir.exists() {
+            std::fs::remove_dir_all(dir).map_err(tantivy::TantivyError::from)?;
+        }
+        std::fs::create_dir_all(dir).map_err(tantivy::TantivyError::from)?;
+        Self::with_index(Index::create_in_dir(dir, schema)?)
+    }
+
+    /// Open the existing index in `dir` without changing anything on disk
+    ///
+    /// `None` when there is no index or it was built with another schema.
+    pub fn open(dir: &Path) -> SearchResult<Option<Self>> {
+        let (schema, _) = Fields::schema();
+        match Index::open_in_dir(dir) {
+            Ok(index) if index.schema() == schema => Self::with_index(index).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn with_index(index: Index) -> SearchResult<Self> {
+        let (_, fields) = Fields::schema();
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        Ok(Self {
+            index,
+            reader,
+            fields,
+        })
+    }
+
+    /// Indexed file paths with the mtime they were indexed at
+    pub fn indexed_files(&self) -> SearchResult<HashMap<String, u64>> {
+        let searcher = self.reader.searcher();
+        let addresses = searcher.search(&AllQuery, &DocSetCollector)?;
+
+        let mut files = HashMap::new();
+        for address in addresses {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            if let Some(path) = doc.get_first(self.fields.path).and_then(|v| v.as_str()) {
+                let mtime = doc
+                    .get_first(self.fields.mtime)
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                files.insert(path.to_string(), mtime);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Replace the documents for `changed` paths and drop `removed` paths
+    pub fn update(&self, changed: Vec<IndexDocument>, removed: &[String]) -> SearchResult<()> {
+        if changed.is_empty() && removed.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer: IndexWriter = self.index.writer(WRITER_MEMORY)?;
+        for path in removed {
+            writer.delete_term(Term::from_field_text(self.fields.path, path));
+        }
+        for document in changed {
+            writer.delete_term(Term::from_field_text(self.fields.path, &document.path));
+            writer.add_document(self.document(document))?;
+        }
+        writer.commit()?;
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    /// Find the best `limit` notes for a query
+    pub fn search(&self, query: &query::Query, limit: usize) -> SearchResult<Vec<SearchHit>> {
+        let query = self.compile(query)?;
+        let searcher = self.reader.searcher();
+
+        let mut hits = Vec::new();
+        for (score, address) in searcher.search(&query, &TopDocs::with_limit(limit))? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let text = |field| {
+                doc.get_first(field)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            hits.push(SearchHit {
+                id: text(self.fields.id),
+                path: text(self.fields.path),
+                title: text(self.fields.title),
+                score,
+            });
+        }
+        Ok(hits)
+    }
+
+    /// Convert an index document into a tantivy document
+    fn document(&self, document: IndexDocument) -> TantivyDocument {
+        let f = self.fields;
+        let note = document.fields;
+
+        let mut doc = doc!(
+            f.path => document.path,
+            f.mtime => document.mtime,
+            f.id => note.id.to_string(),
+            f.title => note.title,
+            f.body => note.body,
+        );
+        for tag in note.tags {
+            doc.add_text(f.tags, tag);
+        }
+        for ancestor in note.id.ancestors() {
+            doc.add_text(f.ancestors, ancestor.to_string());
+        }
+        for link in note.links {
+            doc.add_text(f.links, link.to_string());
+        }
+        if let Some(created) = note.created {
+            doc.add_u64(f.created, created.as_number());
+        }
+        doc
+    }
+
+    /// Compile a parsed query into a tantivy query
+    fn compile(&self, query: &query::Query) -> SearchResult<Box<dyn Query>> {
+        Ok(match query {
+            query::Query::Term(term) => self.compile_term(term)?,
+            query::Query::Not(_) => self.all_of(std::slice::from_ref(query))?,
+            query::Query::And(queries) => self.all_of(queries)?,
+            query::Query::Or(queries) => {
+                let clauses = queries
+                    .iter()
+                    .map(|q| Ok((Occur::Should, self.compile(q)?)))
+                    .collect::<SearchResult<_>>()?;
+                Box::new(BooleanQuery::new(clauses))
+            }
+        })
+    }
+
+    /// Query requiring every query, with negations as exclusions
+    fn all_of(&self, queries: &[query::Query]) -> SearchResult<Box<dyn Query>> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for query in queries {
+            clauses.push(match query {
+                query::Query::Not(inner) => (Occur::MustNot, self.compile(inner)?),
+                _ => (Occur::Must, self.compile(query)?),
+            });
+        }
+        // Exclusions alone match nothing, so start from every note
+        if clauses.iter().all(|(occur, _)| *occur == Occur::MustNot) {
+            clauses.push((Occur::Must, Box::new(AllQuery)));
+        }
+        Ok(Box::new(BooleanQuery::new(clauses)))
+    }
+
+    fn compile_term(&self, term: &query::Term) -> SearchResult<Box<dyn Query>> {
+        let f = self.fields;
+        let exact = |field: Field, value: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, value),
+                IndexRecordOption::Basic,
+            ))
+        };
+
+        Ok(match term {
+            query::Term::Text {
+                field,
+                words,
+                prefix,
+            } => {
+                // The index holds lowercase tokens
+                let words: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+                let mut fields = match field {
+                    TextField::Any => vec![(f.title, TITLE_BOOST), (f.body, 1.0)],
+                    TextField::Title => vec![(f.title, 1.0)],
+                    TextField::Body => vec![(f.body, 1.0)],
+                };
+                if *field == TextField::Any && words.len() == 1 {
+                    fields.push((f.tags, 1.0));
+                }
+
+                let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+                for (field, boost) in fields {
+                    let mut query = text_query(field, &words, *prefix)?;
+                    if boost != 1.0 {
+                        query = Box::new(BoostQuery::new(query, boost));
+                    }
+                    clauses.push((Occur::Should, query));
+                }
+                Box::new(BooleanQuery::new(clauses))
+            }
+            query::Term::Tag { tag, prefix: false } => exact(f.tags, tag),
+            query::Term::Tag { tag, prefix: true } => prefix_query(f.tags, tag)?,
+            query::Term::Id { id, prefix: false } => exact(f.id, id),
+            query::Term::Id { id, prefix: true } => prefix_query(f.id, id)?,
+            query::Term::Under(id) => exact(f.ancestors, &id.to_string()),
+            query::Term::LinksTo(id) => exact(f.links, &id.to_string()),
+            query::Term::Created(comparison, date) => {
+                let date = date.as_number();
+                let (lower, upper) = match comparison {
+                    Comparison::Before => (Bound::Unbounded, Bound::Excluded(date)),
+                    Comparison::AtOrBefore => (Bound::Unbounded, Bound::Included(date)),
+                    Comparison::On => (Bound::Included(date), Bound::Included(date)),
+                    Comparison::AtOrAfter => (Bound::Included(date), Bound::Unbounded),
+                    Comparison::After => (Bound::Excluded(date), Bound::Unbounded),
+                };
+                Box::new(RangeQuery::new_u64_bounds(
+                    "created".to_string(),
+                    lower,
+                    upper,
+                ))
+            }
+        })
+    }
+}
+
+/// Words in a tokenized field: a term, a phrase, or either ending in a prefix
+fn text_query(field: Field, words: &[String], prefix: bool) -> SearchResult<Box<dyn Query>> {
+    let terms: Vec<Term> = words
+        .iter()
+        .map(|word| Term::from_field_text(field, word))
+        .collect();
+
+    Ok(match (terms.len(), prefix) {
+        (1, false) => Box::new(TermQuery::new(
+            terms[0].clone(),
+            IndexRecordOption::WithFreqs,
+        )),
+        (1, true) => prefix_query(field, &words[0])?,
+        (_, false) => Box::new(PhraseQuery::new(terms)),
+        (_, true) => Box::new(PhrasePrefixQuery::new(terms)),
+    })
+}
+
+/// Terms of `field` starting with `prefix`
+fn prefix_query(field: Field, prefix: &str) -> SearchResult<Box<dyn Query>> {
+    let pattern = format!("{}.*", regex::escape(prefix));
+    let query =
+        RegexQuery::from_pattern(&pattern, field).map_err(|e| SearchError::Query(e.to_string()))?;
+    Ok(Box::new(query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::Id;
+    use crate::query::Date;
+
+    fn document(id: &str, title: &str, body: &str, tags: &[&str]) -> IndexDocument {
+        IndexDocument {
+            path: format!("{}.md", id),
+            mtime: 1,
+            fields: NoteFields {
+                id: Id::parse(id).unwrap(),
+                title: title.to_string(),
+                body: body.to_string(),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                links: Vec::new(),
+                created: None,
+            },
+        }
+    }
+
+    fn search(index: &SearchIndex, query: &str) -> Vec<String> {
+        let query = query::parse(query).unwrap();
+        index
+            .search(&query, 10)
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.id)
+            .collect()
+    }
+
+    fn index_with_notes(dir: &Path) -> SearchIndex {
+        let mut one = document(
+            "1",
+            "Thermodynamics",
+            "Heat and entropy in closed systems",
+            &["physics"],
+        );
+        one.fields.created = Date::parse("2025-12-24");
+        let mut one_a = document(
+            "1a",
+            "Entropy",
+            "Entropy always increases, entropy entropy",
+            &["physics", "draft"],
+        );
+        one_a.fields.links = vec![Id::parse("2").unwrap()];
+        one_a.fields.created = Date::parse("2026-01-02");
+
+        let index = SearchIndex::open_or_create(dir).unwrap();
+        index
+            .update(
+                vec![
+                    one,
+                    one_a,
+                    document(
+                        "2",
+                        "Cooking",
+                        "Closed systems of heat in the kitchen",
+                        &["food"],
+                    ),
+                ],
+                &[],
+            )
+            .unwrap();
+        index
+    }
+
+    #[test]
+    fn test_plan_update() {
+        let indexed = HashMap::from([("a".to_string(), 1), ("b".to_string(), 1)]);
+        let current = HashMap::from([
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 1),
+        ]);
+        let (changed, removed) = plan_update(&indexed, &current);
+        assert_eq!(changed, vec!["b", "c"]);
+        assert!(removed.is_empty());
+
+        let (changed, removed) = plan_update(&indexed, &HashMap::new());
+        assert!(changed.is_empty());
+        assert_eq!(removed, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_ranked_phrase_and_prefix_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = index_with_notes(dir.path());
+
+        // Title boost and term frequency put 1a first
+        assert_eq!(search(&index, "entropy"), vec!["1a", "1"]);
+        assert_eq!(search(&index, "\"closed systems\"").len(), 2);
+        assert!(search(&index, "\"systems closed\"").is_empty());
+        assert_eq!(search(&index, "closed-syst* kitch*"), vec!["2"]);
+        assert_eq!(search(&index, "thermo*"), vec!["1"]);
+        assert_eq!(search(&index, "title:entropy"), vec!["1a"]);
+        assert_eq!(search(&index, "physics -tag:draft"), vec!["1"]);
+        assert_eq!(search(&index, "cooking OR thermodynamics").len(), 2);
+    }
+
+    #[test]
+    fn test_structured_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = index_with_notes(dir.path());
+
+        assert_eq!(search(&index, "id:1a"), vec!["1a"]);
+        assert_eq!(search(&index, "id:1*").len(), 2);
+        assert_eq!(search(&index, "under:1"), vec!["1a"]);
+        assert_eq!(search(&index, "links-to:2"), vec!["1a"]);
+        assert_eq!(search(&index, "created:>=2026-01-01"), vec!["1a"]);
+        assert_eq!(search(&index, "created:<2026-01-02"), vec!["1"]);
+        assert_eq!(search(&index, "-tag:physics"), vec!["2"]);
+    }
+
+    #[test]
+    fn test_update_replaces_and_removes() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = index_with_notes(dir.path());
+
+        let mut changed = document("2", "Baking", "Bread", &[]);
+        changed.mtime = 5;
+        index.update(vec![changed], &["1.md".to_string()]).unwrap();
+
+        let files = index.indexed_files().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files.get("2.md"), Some(&5));
+        assert!(search(&index, "cooking").is_empty());
+        assert_eq!(search(&index, "bread"), vec!["2"]);
+
+        // Reopening finds the same documents
+        drop(index);
+        let reopened = SearchIndex::open_or_create(dir.path()).unwrap();
+        assert_eq!(reopened.indexed_files().unwrap().len(), 2);
+    }
+}
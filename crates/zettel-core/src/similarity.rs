@@ -0,0 +1,190 @@
+// crates/zettel-core/src/similarity.rs - Content Similarity Between Notes
+//
+// Ranks notes by how much their text resembles a given note or draft, for
+// `zettel related` and `zettel suggest-parent`. Unlike search, there is no
+// query to satisfy: every shared word counts, weighted by how rare it is in
+// the vault.
+//
+// SCORING:
+// Okapi BM25 with the whole input text as the query. Each distinct term of
+// the input contributes, per note,
+//
+//   idf(term) * tf * (K1 + 1) / (tf + K1 * (1 - B + B * len / avg_len))
+//
+// multiplied by how often the term occurs in the input, where `tf` is the
+// term's count in the note and `len` the note's length in terms. Rare words
+// shared with a short note weigh the most.
+//
+// TERMS:
+// Lowercased words of the note body (frontmatter excluded), skipping
+// one-character words and common English stop words, which would make
+// every note look alike.
+
+use std::collections::HashMap;
+
+use crate::note;
+use crate::query;
+
+/// Term frequency saturation: higher values let repeated words count longer
+pub const K1: f64 = 1.2;
+
+/// Length normalization: 0 ignores note length, 1 fully normalizes it
+pub const B: f64 = 0.75;
+
+/// How many of the shared terms a result reports
+pub const TOP_TERMS: usize = 5;
+
+/// Words too common to tell notes apart
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "because",
+    "been", "but", "by", "can", "could", "do", "does", "each", "for", "from", "had", "has", "have",
+    "he", "her", "his", "how", "if", "in", "into", "is", "it", "its", "more", "most", "no", "not",
+    "of", "on", "one", "only", "or", "other", "our", "out", "she", "so", "some", "such", "than",
+    "that", "the", "their", "them", "then", "there", "these", "they", "this", "those", "to", "up",
+    "us", "was", "we", "were", "what", "when", "where", "which", "while", "who", "why", "will",
+    "with", "would", "you", "your",
+];
+
+/// A document similar to the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Similar {
+    /// Position of the document in the corpus
+    pub index: usize,
+    pub score: f64,
+    /// Shared terms contributing the most to the score, highest first
+    pub terms: Vec<String>,
+}
+
+/// Term statistics of a set of documents, ready to be compared against
+#[derive(Debug, Clone, Default)]
+pub struct Corpus {
+    /// Term counts of each document
+    documents: Vec<HashMap<String, usize>>,
+    /// Number of terms in each document
+    lengths: Vec<usize>,
+    /// Number of documents containing each term
+    document_frequency: HashMap<String, usize>,
+}
+
+impl Corpus {
+    /// Build a corpus from note contents, in the order results refer to
+    pub fn new<'a>(contents: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut corpus = Corpus::default();
+        for content in contents {
+            let terms = terms(content);
+            let counts = counts(&terms);
+            for term in counts.keys() {
+                *corpus.document_frequency.entry(term.clone()).or_default() += 1;
+            }
+            corpus.lengths.push(terms.len());
+            corpus.documents.push(counts);
+        }
+        corpus
+    }
+
+    /// Documents sharing terms with `content`, most similar first
+    ///
+    /// Ties keep corpus order; documents sharing no terms are left out.
+    pub fn rank(&self, content: &str) -> Vec<Similar> {
+        let query = counts(&terms(content));
+        let total = self.documents.len() as f64;
+        let average_length = self.lengths.iter().sum::<usize>() as f64 / total.max(1.0);
+
+        let mut similar: Vec<Similar> = self
+            .documents
+            .iter()
+            .enumerate()
+            .filter_map(|(index, document)| {
+                let length_norm = if average_length > 0.0 {
+                    1.0 - B + B * self.lengths[index] as f64 / average_length
+                } else {
+                    1.0
+                };
+
+                let mut contributions: Vec<(&String, f64)> = query
+                    .iter()
+                    .filter_map(|(term, &query_count)| {
+                        let tf = *document.get(term)? as f64;
+                        let df = self.document_frequency[term] as f64;
+                        let idf = (1.0 + (total - df + 0.5) / (df + 0.5)).ln();
+                        let weight = idf * tf * (K1 + 1.0) / (tf + K1 * length_norm);
+                        Some((term, weight * query_count as f64))
+                    })
+                    .collect();
+                if contributions.is_empty() {
+                    return None;
+                }
+
+                // Highest contributions first, alphabetical among equals
+                contributions.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                Some(Similar {
+                    index,
+                    score: contributions.iter().map(|(_, weight)| weight).sum(),
+                    terms: contributions
+                        .iter()
+                        .take(TOP_TERMS)
+                        .map(|(term, _)| term.to_string())
+                        .collect(),
+                })
+            })
+            .collect();
+
+        // Stable sort keeps corpus order among equal scores
+        similar.sort_by(|a, b| b.score.total_cmp(&a.score));
+        similar
+    }
+}
+
+/// The terms of a note body that similarity is computed on
+pub fn terms(content: &str) -> Vec<String> {
+    query::words(&content[note::body_start(content)..])
+        .into_iter()
+        .filter(|word| word.chars().count() > 1 && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// How often each term occurs
+fn counts(terms: &[String]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for term in terms {
+        *counts.entry(term.clone()).or_default() += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTES: &[&str] = &[
+        "---\ntags: [physics]\n---\n# Thermodynamics\n\nHeat, work and energy.",
+        "# Entropy\n\nEntropy measures disorder. Heat flows from hot to cold, raising entropy.",
+        "# Black holes\n\nBlack holes have entropy proportional to their area.",
+        "# Bread\n\nKnead the dough and let it rise.",
+    ];
+
+    #[test]
+    fn test_terms() {
+        assert_eq!(
+            terms("---\ntitle: The Entropy\n---\nThe entropy of a gas, in J/K."),
+            vec!["entropy", "gas"]
+        );
+    }
+
+    #[test]
+    fn test_rank() {
+        let corpus = Corpus::new(NOTES.iter().copied());
+
+        let similar = corpus.rank("Why does heat make entropy grow?");
+        let order: Vec<usize> = similar.iter().map(|s| s.index).collect();
+        assert_eq!(order, vec![1, 0, 2]);
+        assert_eq!(similar[0].terms, vec!["entropy", "heat"]);
+
+        // A rare shared word outweighs a common one
+        let similar = corpus.rank("entropy of dough");
+        assert_eq!(similar[0].index, 3);
+
+        assert!(corpus.rank("quantum chromodynamics").is_empty());
+        assert!(Corpus::new([]).rank("heat").is_empty());
+    }
+}
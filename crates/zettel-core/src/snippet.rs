@@ -0,0 +1,202 @@
+// crates/zettel-core/src/snippet.rs - Matching Lines for Search Results
+//
+// Search backends decide which notes match; this module finds where in a
+// note the match is, grep style: the lines containing a pattern, the spans
+// to highlight in them, and the surrounding context lines.
+//
+// PATTERNS:
+// A pattern is a regular expression. For query-language searches it is
+// derived from the query's text terms (`query_pattern`): every word or
+// phrase that isn't excluded, matched on word boundaries. Field filters such
+// as `tag:` or `under:` don't point at any line, so a query made only of
+// filters has no pattern and results show no lines.
+//
+// POSITIONS:
+// Line numbers and columns are 1-based, columns counted in characters, as
+// editors and grep report them. Spans are byte ranges into the line text.
+
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+use crate::query::{Query, Term};
+
+/// A line containing at least one match
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineMatch {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based character column of the first match
+    pub column: usize,
+    /// The line, without its line ending
+    pub text: String,
+    /// Byte ranges of every match in `text`
+    pub spans: Vec<Range<usize>>,
+}
+
+/// One line of an excerpt: a match or context around one
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExcerptLine {
+    pub line: usize,
+    pub text: String,
+    /// Byte ranges to highlight; empty for context lines
+    pub spans: Vec<Range<usize>>,
+    pub is_match: bool,
+}
+
+/// Build the pattern for the positive text terms of a query
+///
+/// Returns `None` when the query has no text terms outside exclusions.
+pub fn query_pattern(query: &Query, case_sensitive: bool) -> Option<Regex> {
+    let mut alternatives = Vec::new();
+    collect_terms(query, &mut alternatives);
+    if alternatives.is_empty() {
+        return None;
+    }
+
+    RegexBuilder::new(&alternatives.join("|"))
+        .case_insensitive(!case_sensitive)
+        .build()
+        .ok()
+}
+
+/// Regex alternatives for the text terms of `query` that aren't negated
+fn collect_terms(query: &Query, alternatives: &mut Vec<String>) {
+    match query {
+        Query::Term(Term::Text { words, prefix, .. }) => {
+            let words: Vec<String> = words.iter().map(|w| regex::escape(w)).collect();
+            let end = if *prefix { r"\w*" } else { r"\b" };
+            // Words are separated by anything that isn't a letter or digit
+            alternatives.push(format!(r"\b{}{}", words.join(r"[^\p{L}\p{N}]+"), end));
+        }
+        Query::Term(_) | Query::Not(_) => {}
+        Query::And(queries) | Query::Or(queries) => {
+            for query in queries {
+                collect_terms(query, alternatives);
+            }
+        }
+    }
+}
+
+/// Lines of `content` matching `pattern`
+pub fn matching_lines(content: &str, pattern: &Regex) -> Vec<LineMatch> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, text)| {
+            let spans: Vec<Range<usize>> = pattern
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| m.range())
+                .collect();
+            let first = spans.first()?;
+            Some(LineMatch {
+                line: index + 1,
+                column: text[..first.start].chars().count() + 1,
+                text: text.to_string(),
+                spans,
+            })
+        })
+        .collect()
+}
+
+/// Group matches with `before` and `after` lines of context
+///
+/// Overlapping or adjacent windows are merged into one excerpt, so each line
+/// appears at most once.
+pub fn excerpts(
+    content: &str,
+    matches: &[LineMatch],
+    before: usize,
+    after: usize,
+) -> Vec<Vec<ExcerptLine>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut groups: Vec<Vec<ExcerptLine>> = Vec::new();
+    let mut last_shown = 0;
+
+    for (i, found) in matches.iter().enumerate() {
+        let start = found.line.saturating_sub(before).max(1).max(last_shown + 1);
+        // Stop context at the next match, which adds its own line
+        let next_match = matches.get(i + 1).map_or(usize::MAX, |m| m.line - 1);
+        let end = (found.line + after).min(lines.len()).min(next_match);
+
+        if groups.is_empty() || start > last_shown + 1 {
+            groups.push(Vec::new());
+        }
+        let group = groups.last_mut().expect("group was just ensured");
+
+        for number in start..=end.max(found.line) {
+            let is_match = number == found.line;
+            group.push(ExcerptLine {
+                line: number,
+                text: lines[number - 1].to_string(),
+                spans: if is_match {
+                    found.spans.clone()
+                } else {
+                    Vec::new()
+                },
+                is_match,
+            });
+        }
+        last_shown = end.max(found.line);
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query;
+
+    const CONTENT: &str =
+        "# Entropy\n\nHeat flows.\nEntropy rises; entropic time.\nOther.\nMore.\nentropy again\n";
+
+    #[test]
+    fn test_query_pattern() {
+        let pattern = |input: &str, case_sensitive| {
+            query_pattern(&query::parse(input).unwrap(), case_sensitive)
+        };
+
+        let found = matching_lines(CONTENT, &pattern("entropy -heat", false).unwrap());
+        assert_eq!(
+            found.iter().map(|m| m.line).collect::<Vec<_>>(),
+            vec![1, 4, 7]
+        );
+        assert_eq!(found[1].spans, vec![0..7]);
+
+        let found = matching_lines(CONTENT, &pattern("Entrop*", true).unwrap());
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].spans.len(), 1);
+
+        let found = matching_lines(CONTENT, &pattern("\"entropic-time\"", false).unwrap());
+        assert_eq!(found[0].column, 16);
+
+        assert!(pattern("tag:physics -entropy", false).is_none());
+    }
+
+    #[test]
+    fn test_excerpts() {
+        let pattern = Regex::new("(?i)entropy").unwrap();
+        let found = matching_lines(CONTENT, &pattern);
+
+        let lines = |before, after| -> Vec<Vec<(usize, bool)>> {
+            excerpts(CONTENT, &found, before, after)
+                .iter()
+                .map(|g| g.iter().map(|l| (l.line, l.is_match)).collect())
+                .collect()
+        };
+
+        assert_eq!(
+            lines(1, 0),
+            vec![
+                vec![(1, true)],
+                vec![(3, false), (4, true)],
+                vec![(6, false), (7, true)],
+            ]
+        );
+        // Adjacent windows merge, and context stops at the end of the note
+        assert_eq!(lines(1, 1).len(), 1);
+        assert_eq!(lines(1, 1)[0].len(), 7);
+        assert_eq!(excerpts(CONTENT, &found, 0, 0).len(), 3);
+    }
+}
@@ -0,0 +1,348 @@
+// crates/zettel-core/src/stats.rs - Vault Statistics
+//
+// This module computes the numbers behind `zettel stats`: size, link
+// structure, shape of the Folgezettel tree, growth over time and tags.
+// The CLI loads note contents and decides where each link points; this
+// module only aggregates.
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: No I/O, callers pass contents and a link resolver
+// - Serializable output: `VaultStats` is the JSON schema of `zettel stats --json`,
+//   so field names are part of the interface
+// - Deterministic: Rankings break ties by ID, maps are ordered
+//
+// DEFINITIONS:
+// - Link: any wiki or local markdown link (see `LinkService::find_links`);
+//   links from a note to itself are not counted
+// - Orphan: a note with no links to or from other notes
+// - Branching factor: average number of existing children of the notes at
+//   a depth, leaves included
+// - Subtree size: number of existing descendants
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::frontmatter;
+use crate::id::Id;
+use crate::link::LinkService;
+use crate::note::{self, Note, NoteTree};
+
+/// How many entries ranked lists (subtrees, most linked, tags) keep
+pub const TOP_N: usize = 10;
+
+/// A note together with the data statistics need from disk
+#[derive(Debug, Clone)]
+pub struct NoteInput<'a> {
+    pub note: &'a Note,
+    pub content: &'a str,
+    /// Month the note was created, as "YYYY-MM"
+    pub created: Option<String>,
+}
+
+/// Where a link target points, as decided by the caller
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+    /// An existing note
+    Note(Id),
+    /// An existing file that isn't a note (image, PDF, ...)
+    File,
+    /// Nothing
+    Broken,
+}
+
+/// Statistics for a whole vault
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VaultStats {
+    pub total_notes: usize,
+    pub orphaned_notes: usize,
+    pub total_links: usize,
+    pub broken_links: usize,
+    pub deepest_level: usize,
+    pub total_words: usize,
+    pub average_words: usize,
+    pub branching: Vec<DepthStats>,
+    pub largest_subtrees: Vec<RankedNote>,
+    pub most_linked: Vec<RankedNote>,
+    pub notes_per_month: BTreeMap<String, usize>,
+    pub tags: Vec<TagCount>,
+}
+
+/// Tree shape at one depth (1 = top-level notes)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepthStats {
+    pub depth: usize,
+    pub notes: usize,
+    pub branching_factor: f64,
+}
+
+/// A note in a ranking, with the value it was ranked by
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedNote {
+    pub id: String,
+    pub title: Option<String>,
+    pub count: usize,
+}
+
+/// How many notes use a tag
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+impl VaultStats {
+    /// Compute statistics for the notes in `tree`
+    ///
+    /// `inputs` should cover every note in the tree; notes without input
+    /// count as empty. `resolve` maps a link target to what it points at.
+    pub fn compute(
+        tree: &NoteTree,
+        inputs: &[NoteInput],
+        resolve: impl Fn(&str) -> LinkTarget,
+    ) -> Self {
+        let mut stats = VaultStats {
+            total_notes: tree.len(),
+            deepest_level: tree.notes().iter().map(|n| n.id.depth()).max().unwrap_or(0),
+            ..Default::default()
+        };
+
+        let mut inbound: BTreeMap<&Id, usize> = BTreeMap::new();
+        let mut linked: BTreeSet<&Id> = BTreeSet::new();
+        let mut tag_notes: BTreeMap<String, usize> = BTreeMap::new();
+
+        for input in inputs {
+            let source = &input.note.id;
+            for link in LinkService::find_links(input.content) {
+                match resolve(&link.target) {
+                    LinkTarget::Note(target) if target == *source => continue,
+                    LinkTarget::Note(target) => {
+                        if let Some(target_note) = tree.get(&target) {
+                            *inbound.entry(&target_note.id).or_default() += 1;
+                            linked.insert(&target_note.id);
+                        }
+                        linked.insert(source);
+                    }
+                    LinkTarget::File => {}
+                    LinkTarget::Broken => stats.broken_links += 1,
+                }
+                stats.total_links += 1;
+            }
+
+            stats.total_words += word_count(input.content);
+            if let Some(month) = &input.created {
+                *stats.notes_per_month.entry(month.clone()).or_default() += 1;
+            }
+            for tag in tags(input.content) {
+                *tag_notes.entry(tag).or_default() += 1;
+            }
+        }
+
+        stats.orphaned_notes = tree
+            .notes()
+            .iter()
+            .filter(|n| !linked.contains(&n.id))
+            .count();
+        stats.average_words = stats.total_words.checked_div(inputs.len()).unwrap_or(0);
+        stats.branching = branching(tree, stats.deepest_level);
+
+        stats.largest_subtrees = ranked(tree, |note| {
+            tree.notes()
+                .iter()
+                .filter(|n| n.id.is_descendant_of(&note.id))
+                .count()
+        });
+        stats.most_linked = ranked(tree, |note| inbound.get(&note.id).copied().unwrap_or(0));
+
+        let mut tags: Vec<TagCount> = tag_notes
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag, count })
+            .collect();
+        // Stable sort keeps alphabetical order among equal counts
+        tags.sort_by_key(|t| std::cmp::Reverse(t.count));
+        tags.truncate(TOP_N);
+        stats.tags = tags;
+
+        stats
+    }
+}
+
+/// Words in the note body, excluding frontmatter
+pub fn word_count(content: &str) -> usize {
+    content[note::body_start(content)..]
+        .split_whitespace()
+        .count()
+}
+
+/// Tags of a note: frontmatter `tags` plus inline `#tags`, deduplicated
+///
+/// Frontmatter tags may be a list or a comma/space separated string. Inline
+/// tags start with `#` at the beginning of a word and must not be all digits
+/// (so "#1" issue references and `# Heading`s aren't tags).
+pub fn tags(content: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut add = |tag: &str| {
+        let tag = tag.trim().trim_start_matches('#').to_lowercase();
+        if !tag.is_empty() && !found.contains(&tag) {
+            found.push(tag);
+        }
+    };
+
+    if let (Some(yaml), _) = frontmatter::split(content)
+        && let Ok(map) = frontmatter::parse(yaml)
+    {
+        match map.get("tags") {
+            Some(serde_yaml::Value::Sequence(items)) => {
+                items.iter().filter_map(|v| v.as_str()).for_each(&mut add)
+            }
+            Some(serde_yaml::Value::String(list)) => list
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .for_each(&mut add),
+            _ => {}
+        }
+    }
+
+    let mut in_fence = false;
+    for line in content[note::body_start(content)..].lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            continue;
+        }
+        for word in line.split_whitespace() {
+            let Some(tag) = word.strip_prefix('#') else {
+                continue;
+            };
+            let tag = tag.trim_end_matches(|c: char| !c.is_alphanumeric());
+            let valid = tag
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '/');
+            if valid && tag.chars().any(|c| !c.is_ascii_digit()) {
+                add(tag);
+            }
+        }
+    }
+
+    found
+}
+
+/// Branching factor for each depth from 1 to `deepest`
+fn branching(tree: &NoteTree, deepest: usize) -> Vec<DepthStats> {
+    (1..=deepest)
+        .map(|depth| {
+            let at_depth: Vec<&Note> = tree
+                .notes()
+                .iter()
+                .filter(|n| n.id.depth() == depth)
+                .collect();
+            let children: usize = at_depth.iter().map(|n| tree.children(&n.id).len()).sum();
+            let branching_factor = if at_depth.is_empty() {
+                0.0
+            } else {
+                children as f64 / at_depth.len() as f64
+            };
+            DepthStats {
+                depth,
+                notes: at_depth.len(),
+                branching_factor,
+            }
+        })
+        .collect()
+}
+
+/// The `TOP_N` notes with the highest non-zero `count`, ties broken by ID
+fn ranked(tree: &NoteTree, count: impl Fn(&Note) -> usize) -> Vec<RankedNote> {
+    let mut ranked: Vec<RankedNote> = tree
+        .notes()
+        .iter()
+        .map(|note| RankedNote {
+            id: note.id.to_string(),
+            title: note.title.clone(),
+            count: count(note),
+        })
+        .filter(|r| r.count > 0)
+        .collect();
+    // Notes are in Folgezettel order, so a stable sort breaks ties by ID
+    ranked.sort_by_key(|r| std::cmp::Reverse(r.count));
+    ranked.truncate(TOP_N);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn note(id: &str) -> Note {
+        Note::new(
+            Id::parse(id).unwrap(),
+            PathBuf::from(format!("{}.md", id)),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_tags() {
+        let content = "---\ntags: [Physics, entropy]\n---\n# Title\n\nOn #entropy and #time, not #1.\n```\n#code\n```\n";
+        assert_eq!(tags(content), vec!["physics", "entropy", "time"]);
+        assert_eq!(tags("---\ntags: a, b\n---\n"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(
+            word_count("---\ntags: [a]\n---\n# Two words\nand three more"),
+            6
+        );
+    }
+
+    #[test]
+    fn test_compute() {
+        let notes = vec![note("1"), note("1a"), note("1b"), note("1a1"), note("2")];
+        let tree = NoteTree::new(notes.clone());
+        let contents = [
+            "# One\n[[1a]] [[1b]] [[1]] [[gone]]",
+            "# One A\n[[1]] ![x](pic.png)",
+            "# One B\n[[1a]] #idea",
+            "",
+            "# Two\n#idea",
+        ];
+        let inputs: Vec<NoteInput> = notes
+            .iter()
+            .zip(contents)
+            .map(|(note, content)| NoteInput {
+                note,
+                content,
+                created: Some("2026-10".to_string()),
+            })
+            .collect();
+        let resolve = |target: &str| match Id::parse(target) {
+            Ok(id) if tree.contains(&id) => LinkTarget::Note(id),
+            _ if target == "pic.png" => LinkTarget::File,
+            _ => LinkTarget::Broken,
+        };
+
+        let stats = VaultStats::compute(&tree, &inputs, resolve);
+
+        assert_eq!(stats.total_notes, 5);
+        assert_eq!(stats.total_links, 6);
+        assert_eq!(stats.broken_links, 1);
+        // 1a1 and 2 have no links to or from other notes
+        assert_eq!(stats.orphaned_notes, 2);
+        assert_eq!(stats.deepest_level, 3);
+        assert_eq!(stats.branching[0].notes, 2);
+        assert_eq!(stats.branching[0].branching_factor, 1.0);
+        assert_eq!(stats.largest_subtrees[0].id, "1");
+        assert_eq!(stats.largest_subtrees[0].count, 3);
+        assert_eq!(stats.most_linked[0].id, "1a");
+        assert_eq!(stats.most_linked[0].count, 2);
+        assert_eq!(stats.notes_per_month.get("2026-10"), Some(&5));
+        assert_eq!(
+            stats.tags,
+            vec![TagCount {
+                tag: "idea".to_string(),
+                count: 2
+            }]
+        );
+    }
+}
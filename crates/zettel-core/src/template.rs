@@ -13,6 +13,37 @@
 // Templates are markdown files with special placeholders:
 // - {{title}} - Replaced with user-provided note title
 // - {{link}} - Replaced with backlink to parent note
+// - {{id}}, {{parent_id}} - The new note's ID and its parent's ID
+// - {{date}}, {{time}} - Creation date (2026-01-31) and time (14:05)
+// - {{author}} - The configured author
+// - Any name from `[template.variables]` or `--var name=value`
+//
+// MODIFIERS:
+// A placeholder may end in `:modifier`:
+// - {{date:%d %B %Y}}, {{time:%H:%M:%S}} - strftime formats for dates
+// - {{title:slug}} - "My Note!" becomes "my-note"
+// - {{title:lower}}, {{title:upper}} - change case
+//
+// Unknown placeholders are left unchanged; `validate_template` reports
+// unknown modifiers.
+//
+// BLOCKS AND PARTIALS:
+// - {{#if name}}...{{else}}...{{/if}} - kept when the variable is set and
+//   not empty, or the list has items; {{else}} is optional
+// - {{#each ancestors}}...{{/each}} - repeated for each item of a list,
+//   whose fields ({{id}}, {{title}}, {{link}}) shadow the note's own;
+//   {{else}} renders for an empty list
+// - {{> name}} - includes another template, found by the caller (the CLI
+//   looks in the template directory)
+//
+// A block tag or include alone on its line takes the whole line with it, so
+// blocks don't leave blank lines behind. Unbalanced and unknown blocks are
+// `TemplateError::SyntaxError`s with the line and column of the tag.
+//
+// SETTINGS:
+// A `zettel` key in the template's frontmatter declares how notes created
+// from it are stored (see `TemplateSettings`). It is removed from the notes;
+// the rest of the frontmatter is rendered like the body.
 //
 // EXAMPLE TEMPLATE:
 // ```markdown
@@ -26,11 +57,17 @@
 // ## References
 // ```
 
+use chrono::NaiveDateTime;
+use chrono::format::{Item, StrftimeItems};
 use regex::Regex;
-use std::collections::HashMap;
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path};
 use thiserror::Error;
 
-use crate::config::TemplateConfig;
+use crate::config::{TemplateConfig, TemplateRule};
+use crate::frontmatter;
+use crate::id::Id;
 
 /// Errors that can occur during template operations
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -46,6 +83,19 @@ pub enum TemplateError {
 
     #[error("Template processing error: {0}")]
     ProcessingError(String),
+
+    #[error("Template syntax error at line {line}, column {column}: {message}")]
+    SyntaxError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    #[error("In partial '{name}': {error}")]
+    PartialError {
+        name: String,
+        error: Box<TemplateError>,
+    },
 }
 
 /// Result type for template operations
@@ -86,6 +136,851 @@ impl ValidationResult {
     }
 }
 
+/// Built-in content for stub notes created for missing parents
+pub const DEFAULT_STUB_TEMPLATE: &str =
+    "# {{title}}\n\n{{link}}\n\n_Stub for a missing parent note. Expand or merge it._\n";
+
+/// Date format of {{date}} without a modifier
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Time format of {{time}} without a modifier
+pub const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+/// Modifiers every variable accepts
+pub const TEXT_MODIFIERS: &[&str] = &["slug", "lower", "upper"];
+
+/// Placeholder syntax: {{name}} or {{name:modifier}}
+const PLACEHOLDER_PATTERN: &str = r"\{\{\s*(\w+)(?::([^{}]*?))?\s*\}\}";
+
+/// Any tag: placeholders, block tags and includes
+const TAG_PATTERN: &str = r"\{\{(.*?)\}\}";
+
+/// How deeply partials may include other partials
+pub const MAX_PARTIAL_DEPTH: usize = 8;
+
+/// Frontmatter key holding a template's settings
+pub const SETTINGS_KEY: &str = "zettel";
+
+/// Variables a filename pattern may use
+pub const FILENAME_VARIABLES: &[&str] =
+    &["id", "sep", "title", "parent_id", "type", "date", "time"];
+
+/// Filename pattern syntax: {name} or {name:modifier}
+const FILENAME_PATTERN: &str = r"\{(\w+)(?::([^{}]*))?\}";
+
+/// The values a template is filled in with
+///
+/// Variables are plain strings set by name; `set` replaces earlier values,
+/// so callers layer built-in values, configured constants and command-line
+/// values in that order. {{date}} and {{time}} are derived from the
+/// creation time when one is set. Lists, such as the ancestors of the new
+/// note, feed {{#each}} blocks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateContext {
+    variables: BTreeMap<String, String>,
+    lists: BTreeMap<String, Vec<BTreeMap<String, String>>>,
+    now: Option<NaiveDateTime>,
+}
+
+impl TemplateContext {
+    /// A context with the title and backlink every note has
+    pub fn new(title: &str, link: &str) -> Self {
+        let mut context = Self::default();
+        context.set("title", title);
+        context.set("link", link);
+        context
+    }
+
+    /// Set a variable, replacing any earlier value
+    pub fn set<K: Into<String>, V: Into<String>>(&mut self, name: K, value: V) {
+        self.variables.insert(name.into(), value.into());
+    }
+
+    /// Set a list for {{#each}}, each item a set of named fields
+    pub fn set_list<K: Into<String>>(&mut self, name: K, items: Vec<BTreeMap<String, String>>) {
+        self.lists.insert(name.into(), items);
+    }
+
+    /// Set the creation time behind {{date}} and {{time}}
+    pub fn set_time(&mut self, now: NaiveDateTime) {
+        self.now = Some(now);
+    }
+
+    /// The value of a variable, without modifier
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.value(name, None).ok().flatten()
+    }
+
+    /// The value of a placeholder, `None` if the variable is unknown
+    ///
+    /// Fails with a message when the modifier doesn't apply.
+    fn value(&self, name: &str, modifier: Option<&str>) -> Result<Option<String>, String> {
+        let date_format = match name {
+            "date" => Some(DEFAULT_DATE_FORMAT),
+            "time" => Some(DEFAULT_TIME_FORMAT),
+            _ => None,
+        };
+
+        // Explicit values win, so `--var date=2026-01-31` can backdate a note
+        if let Some(value) = self.variables.get(name) {
+            return match (date_format, modifier) {
+                (Some(_), Some(format)) if !TEXT_MODIFIERS.contains(&format) => {
+                    reformat_date(value, format).map(Some)
+                }
+                _ => apply_modifier(value, modifier).map(Some),
+            };
+        }
+
+        let Some(default_format) = date_format else {
+            return Ok(None);
+        };
+        let format = modifier.unwrap_or(default_format);
+        check_date_format(format)?;
+        Ok(self.now.map(|now| now.format(format).to_string()))
+    }
+}
+
+/// Apply a text modifier (`slug`, `lower`, `upper`) to a value
+fn apply_modifier(value: &str, modifier: Option<&str>) -> Result<String, String> {
+    match modifier {
+        None => Ok(value.to_string()),
+        Some("slug") => Ok(slugify(value)),
+        Some("lower") => Ok(value.to_lowercase()),
+        Some("upper") => Ok(value.to_uppercase()),
+        Some(other) => Err(format!(
+            "Unknown modifier '{}' (expected {})",
+            other,
+            TEXT_MODIFIERS.join(", ")
+        )),
+    }
+}
+
+/// Format a date or time given as text, as for `--var date=2026-01-31`
+fn reformat_date(value: &str, format: &str) -> Result<String, String> {
+    check_date_format(format)?;
+    let value = value.trim();
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M") {
+        return Ok(datetime.format(format).to_string());
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.format(format).to_string());
+    }
+    if let Ok(time) = chrono::NaiveTime::parse_from_str(value, "%H:%M") {
+        return Ok(time.format(format).to_string());
+    }
+    Err(format!(
+        "Cannot apply format '{}' to '{}' (expected YYYY-MM-DD or HH:MM)",
+        format, value
+    ))
+}
+
+/// Check a strftime format, which would otherwise fail while formatting
+fn check_date_format(format: &str) -> Result<(), String> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        Err(format!("Invalid date format '{}'", format))
+    } else {
+        Ok(())
+    }
+}
+
+/// Lowercase words joined by dashes: "My Note!" -> "my-note"
+pub fn slugify(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A parsed template: text with placeholders, blocks and includes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+/// A piece of a parsed template
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// Literal text, possibly holding placeholders
+    Text(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        name: String,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Partial {
+        name: String,
+        line: usize,
+        column: usize,
+        /// Whether the include took the line break after it, to be given
+        /// back when the partial doesn't end in one
+        line_break: bool,
+    },
+}
+
+/// The kinds of `{{#block}}`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockKind {
+    If,
+    Each,
+}
+
+impl BlockKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            BlockKind::If => "if",
+            BlockKind::Each => "each",
+        }
+    }
+}
+
+/// A tag other than a placeholder
+enum Tag<'a> {
+    Open(BlockKind, &'a str),
+    Else,
+    Close(&'a str),
+    Partial(&'a str),
+}
+
+/// A block whose closing tag hasn't been reached yet
+struct OpenBlock {
+    kind: BlockKind,
+    name: String,
+    line: usize,
+    column: usize,
+    body: Vec<Node>,
+    otherwise: Option<Vec<Node>>,
+}
+
+impl OpenBlock {
+    /// Where the nodes parsed next belong
+    fn nodes(&mut self) -> &mut Vec<Node> {
+        match &mut self.otherwise {
+            Some(otherwise) => otherwise,
+            None => &mut self.body,
+        }
+    }
+}
+
+impl Template {
+    /// Parse a template, checking that its blocks are known and balanced
+    pub fn parse(source: &str) -> TemplateResult<Self> {
+        let tag_regex = Regex::new(TAG_PATTERN).unwrap();
+        let mut nodes = Vec::new();
+        let mut open: Vec<OpenBlock> = Vec::new();
+        let mut position = 0;
+
+        for found in tag_regex.find_iter(source) {
+            let (line, column) = line_column(source, found.start());
+            let error = |message: String| TemplateError::SyntaxError {
+                line,
+                column,
+                message,
+            };
+            let inner = &found.as_str()[2..found.len() - 2];
+            let Some(tag) = classify(inner).map_err(error)? else {
+                continue; // Placeholders stay in the text
+            };
+
+            let (text_end, next) = standalone(source, found.start(), found.end())
+                .unwrap_or((found.start(), found.end()));
+            let target = open.last_mut().map_or(&mut nodes, OpenBlock::nodes);
+            push_text(target, &source[position..text_end]);
+            position = next;
+
+            match tag {
+                Tag::Open(kind, name) => open.push(OpenBlock {
+                    kind,
+                    name: name.to_string(),
+                    line,
+                    column,
+                    body: Vec::new(),
+                    otherwise: None,
+                }),
+                Tag::Else => match open.last_mut() {
+                    None => return Err(error("{{else}} outside of a block".to_string())),
+                    Some(block) if block.otherwise.is_some() => {
+                        return Err(error(format!(
+                            "Second {{{{else}}}} in {{{{#{} {}}}}}",
+                            block.kind.keyword(),
+                            block.name
+                        )));
+                    }
+                    Some(block) => block.otherwise = Some(Vec::new()),
+                },
+                Tag::Close(keyword) => {
+                    let Some(block) = open.pop() else {
+                        return Err(error(format!(
+                            "{{{{/{}}}}} without an opening block",
+                            keyword
+                        )));
+                    };
+                    if block.kind.keyword() != keyword {
+                        return Err(error(format!(
+                            "{{{{/{}}}}} closes {{{{#{} {}}}}} from line {}, expected {{{{/{}}}}}",
+                            keyword,
+                            block.kind.keyword(),
+                            block.name,
+                            block.line,
+                            block.kind.keyword()
+                        )));
+                    }
+                    let otherwise = block.otherwise.unwrap_or_default();
+                    let node = match block.kind {
+                        BlockKind::If => Node::If {
+                            name: block.name,
+                            then: block.body,
+                            otherwise,
+                        },
+                        BlockKind::Each => Node::Each {
+                            name: block.name,
+                            body: block.body,
+                            otherwise,
+                        },
+                    };
+                    open.last_mut()
+                        .map_or(&mut nodes, OpenBlock::nodes)
+                        .push(node);
+                }
+                Tag::Partial(name) => {
+                    let target = open.last_mut().map_or(&mut nodes, OpenBlock::nodes);
+                    target.push(Node::Partial {
+                        name: name.to_string(),
+                        line,
+                        column,
+                        line_break: source[..next].ends_with('\n'),
+                    });
+                }
+            }
+        }
+
+        if let Some(block) = open.pop() {
+            return Err(TemplateError::SyntaxError {
+                line: block.line,
+                column: block.column,
+                message: format!(
+                    "{{{{#{} {}}}}} is never closed with {{{{/{}}}}}",
+                    block.kind.keyword(),
+                    block.name,
+                    block.kind.keyword()
+                ),
+            });
+        }
+        push_text(&mut nodes, &source[position..]);
+        Ok(Template { nodes })
+    }
+
+    /// Fill in the template, loading included partials with `partials`
+    ///
+    /// `partials` returns a partial's source by name, or `None` when there
+    /// is no such partial, which is an error.
+    pub fn render(
+        &self,
+        context: &TemplateContext,
+        partials: &dyn Fn(&str) -> Option<String>,
+    ) -> TemplateResult<String> {
+        let mut out = String::new();
+        let scope = Scope {
+            context,
+            items: Vec::new(),
+        };
+        render_nodes(&self.nodes, &scope, partials, 0, &mut out)?;
+        Ok(out)
+    }
+
+    /// Names of the partials the template includes, in order
+    pub fn partials(&self) -> Vec<String> {
+        fn collect(nodes: &[Node], names: &mut Vec<String>) {
+            for node in nodes {
+                match node {
+                    Node::Partial { name, .. } if !names.contains(name) => names.push(name.clone()),
+                    Node::If {
+                        then: body,
+                        otherwise,
+                        ..
+                    }
+                    | Node::Each {
+                        body, otherwise, ..
+                    } => {
+                        collect(body, names);
+                        collect(otherwise, names);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        collect(&self.nodes, &mut names);
+        names
+    }
+}
+
+/// Variables visible while rendering: the loop items being repeated,
+/// innermost last, over the template context
+struct Scope<'a> {
+    context: &'a TemplateContext,
+    items: Vec<&'a BTreeMap<String, String>>,
+}
+
+impl Scope<'_> {
+    fn value(&self, name: &str, modifier: Option<&str>) -> Result<Option<String>, String> {
+        match self.items.iter().rev().find_map(|item| item.get(name)) {
+            Some(value) => apply_modifier(value, modifier).map(Some),
+            None => self.context.value(name, modifier),
+        }
+    }
+
+    /// Whether {{#if name}} holds: a non-empty value or list
+    fn is_set(&self, name: &str) -> bool {
+        if let Some(value) = self.items.iter().rev().find_map(|item| item.get(name)) {
+            return !value.trim().is_empty();
+        }
+        if let Some(list) = self.context.lists.get(name) {
+            return !list.is_empty();
+        }
+        matches!(self.context.value(name, None), Ok(Some(value)) if !value.trim().is_empty())
+    }
+}
+
+fn render_nodes<'a>(
+    nodes: &'a [Node],
+    scope: &Scope<'a>,
+    partials: &dyn Fn(&str) -> Option<String>,
+    depth: usize,
+    out: &mut String,
+) -> TemplateResult<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(&substitute_placeholders(text, |name, modifier| {
+                scope.value(name, modifier)
+            })),
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                let branch = if scope.is_set(name) { then } else { otherwise };
+                render_nodes(branch, scope, partials, depth, out)?;
+            }
+            Node::Each {
+                name,
+                body,
+                otherwise,
+            } => match scope.context.lists.get(name) {
+                Some(items) if !items.is_empty() => {
+                    for item in items {
+                        let mut inner = Scope {
+                            context: scope.context,
+                            items: scope.items.clone(),
+                        };
+                        inner.items.push(item);
+                        render_nodes(body, &inner, partials, depth, out)?;
+                    }
+                }
+                _ => render_nodes(otherwise, scope, partials, depth, out)?,
+            },
+            Node::Partial {
+                name,
+                line,
+                column,
+                line_break,
+            } => {
+                let error = |message: String| TemplateError::SyntaxError {
+                    line: *line,
+                    column: *column,
+                    message,
+                };
+                if depth >= MAX_PARTIAL_DEPTH {
+                    return Err(error(format!(
+                        "Partials nested more than {} deep at {{{{> {}}}}}",
+                        MAX_PARTIAL_DEPTH, name
+                    )));
+                }
+                let source =
+                    partials(name).ok_or_else(|| error(format!("Unknown partial '{}'", name)))?;
+                let in_partial = |error| TemplateError::PartialError {
+                    name: name.clone(),
+                    error: Box::new(error),
+                };
+                let partial = Template::parse(&source).map_err(in_partial)?;
+                let start = out.len();
+                render_nodes(&partial.nodes, scope, partials, depth + 1, out)
+                    .map_err(in_partial)?;
+                if *line_break && out.len() > start && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// What a tag is, `None` for placeholders and other text
+fn classify(inner: &str) -> Result<Option<Tag<'_>>, String> {
+    let inner = inner.trim();
+    let word = |rest: &str| rest.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    if let Some(rest) = inner.strip_prefix('#') {
+        let (keyword, name) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let kind = match keyword {
+            "if" => BlockKind::If,
+            "each" => BlockKind::Each,
+            _ => {
+                return Err(format!(
+                    "Unknown block {{{{#{}}}}} (expected #if or #each)",
+                    keyword
+                ));
+            }
+        };
+        let name = name.trim();
+        if name.is_empty() || !word(name) {
+            return Err(format!(
+                "{{{{#{}}}}} needs a variable name, as in {{{{#{} parent_id}}}}",
+                keyword, keyword
+            ));
+        }
+        Ok(Some(Tag::Open(kind, name)))
+    } else if let Some(keyword) = inner.strip_prefix('/') {
+        match keyword.trim() {
+            keyword @ ("if" | "each") => Ok(Some(Tag::Close(keyword))),
+            other => Err(format!(
+                "Unknown block {{{{/{}}}}} (expected /if or /each)",
+                other
+            )),
+        }
+    } else if let Some(name) = inner.strip_prefix('>') {
+        let name = name.trim();
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_-./".contains(c));
+        if !valid {
+            return Err(format!("Invalid partial name in {{{{>{}}}}}", name));
+        }
+        Ok(Some(Tag::Partial(name)))
+    } else if inner == "else" {
+        Ok(Some(Tag::Else))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The text to keep before a tag and where to resume after it
+///
+/// For a tag alone on its line, the line's indentation and line break go
+/// with it; `None` otherwise.
+fn standalone(source: &str, start: usize, end: usize) -> Option<(usize, usize)> {
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |i| end + i + 1);
+    let alone =
+        source[line_start..start].trim().is_empty() && source[end..line_end].trim().is_empty();
+    alone.then_some((line_start, line_end))
+}
+
+fn push_text(nodes: &mut Vec<Node>, text: &str) {
+    if !text.is_empty() {
+        nodes.push(Node::Text(text.to_string()));
+    }
+}
+
+/// 1-based line and character column of a byte offset
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Replace the placeholders `value` knows, applying their modifiers
+///
+/// Unknown placeholders, and placeholders whose modifier doesn't apply,
+/// are left unchanged so the problem stays visible in the note.
+fn substitute_placeholders<F>(text: &str, value: F) -> String
+where
+    F: Fn(&str, Option<&str>) -> Result<Option<String>, String>,
+{
+    let placeholder_regex = Regex::new(PLACEHOLDER_PATTERN).unwrap();
+    placeholder_regex
+        .replace_all(text, |capture: &regex::Captures| {
+            let modifier = capture.get(2).map(|m| m.as_str().trim());
+            match value(&capture[1], modifier) {
+                Ok(Some(value)) => value,
+                _ => capture[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// How notes created from a template are stored
+///
+/// Declared under a `zettel` key in the template's frontmatter:
+///
+/// ```yaml
+/// ---
+/// zettel:
+///   directory: literature
+///   filename: "{id}{sep}{title:slug}"
+///   tags: [source]
+///   type: literature
+/// ---
+/// ```
+///
+/// The filename pattern takes the variables in `FILENAME_VARIABLES` with
+/// the usual modifiers; `{sep}` is the configured ID separator, left out
+/// when the note has no title. Without settings, the `[note]` and `[id]`
+/// configuration decide as before.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateSettings {
+    /// Directory for new notes, relative to the vault root
+    pub directory: Option<String>,
+    /// Filename pattern, without extension
+    pub filename: Option<String>,
+    /// Tags added to the frontmatter of new notes
+    pub tags: Vec<String>,
+    /// Note type, added to the frontmatter as `type`
+    pub note_type: Option<String>,
+}
+
+impl TemplateSettings {
+    /// Split a template into its settings and the template proper
+    ///
+    /// The `zettel` block is cut from the frontmatter as text, leaving the
+    /// other lines untouched: they may hold placeholders that aren't valid
+    /// YAML until rendered. A frontmatter left empty is removed.
+    pub fn extract(template: &str) -> TemplateResult<(Self, String)> {
+        let (Some(yaml), body) = frontmatter::split(template) else {
+            return Ok((Self::default(), template.to_string()));
+        };
+
+        let mut block = String::new();
+        let mut rest = String::new();
+        let mut in_block = false;
+        for line in yaml.split_inclusive('\n') {
+            let is_key = line
+                .strip_prefix(SETTINGS_KEY)
+                .is_some_and(|after| after.starts_with(':'));
+            let continues = line.starts_with([' ', '\t']) || line.trim().is_empty();
+            if is_key || (in_block && continues) {
+                in_block = true;
+                block.push_str(line);
+            } else {
+                in_block = false;
+                rest.push_str(line);
+            }
+        }
+        if block.is_empty() {
+            return Ok((Self::default(), template.to_string()));
+        }
+
+        let settings = Self::parse(&block)?;
+        let template = if rest.trim().is_empty() {
+            body.to_string()
+        } else {
+            if !rest.ends_with('\n') {
+                rest.push('\n');
+            }
+            format!("---\n{}---\n{}", rest, body)
+        };
+        Ok((settings, template))
+    }
+
+    /// Read the settings from the YAML of the `zettel` block
+    fn parse(block: &str) -> TemplateResult<Self> {
+        let error = |message: String| TemplateError::ValidationError(message);
+        let root: Mapping = serde_yaml::from_str(block)
+            .map_err(|e| error(format!("Invalid '{}' settings: {}", SETTINGS_KEY, e)))?;
+
+        let mut settings = Self::default();
+        let entries = match root.get(SETTINGS_KEY) {
+            Some(Value::Mapping(entries)) => entries.clone(),
+            Some(Value::Null) | None => Mapping::new(),
+            Some(_) => {
+                return Err(error(format!(
+                    "'{}' settings must be a mapping",
+                    SETTINGS_KEY
+                )));
+            }
+        };
+        for (key, value) in entries {
+            let key = key.as_str().unwrap_or_default().to_string();
+            let text = |value: &Value| {
+                value
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| error(format!("Template setting '{}' must be text", key)))
+            };
+            match key.as_str() {
+                "directory" => settings.directory = Some(text(&value)?),
+                "filename" => settings.filename = Some(text(&value)?),
+                "type" => settings.note_type = Some(text(&value)?),
+                "tags" => {
+                    settings.tags = match &value {
+                        Value::Sequence(items) => {
+                            items.iter().map(text).collect::<TemplateResult<_>>()?
+                        }
+                        Value::String(list) => list.split(',').map(str::to_string).collect(),
+                        _ => return Err(error("Template setting 'tags' must be a list".into())),
+                    };
+                    settings.tags = settings
+                        .tags
+                        .iter()
+                        .map(|tag| tag.trim().to_string())
+                        .filter(|tag| !tag.is_empty())
+                        .collect();
+                }
+                _ => {
+                    return Err(error(format!(
+                        "Unknown template setting '{}' (expected directory, filename, tags, type)",
+                        key
+                    )));
+                }
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Problems with the declared settings, empty when they are usable
+    pub fn check(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if let Some(directory) = &self.directory {
+            let path = Path::new(directory.trim());
+            if directory.trim().is_empty() {
+                problems.push("Template setting 'directory' is empty".to_string());
+            } else if path.is_absolute()
+                || path
+                    .components()
+                    .any(|c| matches!(c, Component::ParentDir | Component::Prefix(_)))
+            {
+                problems.push(format!(
+                    "Template setting 'directory' must stay inside the vault: {}",
+                    directory
+                ));
+            }
+        }
+
+        if let Some(pattern) = &self.filename {
+            if !pattern.starts_with("{id}") {
+                problems.push(format!(
+                    "Filename pattern '{}' must start with {{id}}, which identifies the note",
+                    pattern
+                ));
+            }
+            if pattern.contains(['/', '\\']) {
+                problems.push(format!(
+                    "Filename pattern '{}' must not contain directories; use 'directory'",
+                    pattern
+                ));
+            }
+            let filename_regex = Regex::new(FILENAME_PATTERN).unwrap();
+            for capture in filename_regex.captures_iter(pattern) {
+                let name = &capture[1];
+                let modifier = capture.get(2).map(|m| m.as_str().trim());
+                if !FILENAME_VARIABLES.contains(&name) {
+                    problems.push(format!(
+                        "Unknown variable {{{}}} in filename pattern (expected {})",
+                        name,
+                        FILENAME_VARIABLES.join(", ")
+                    ));
+                    continue;
+                }
+                let result = match (name, modifier) {
+                    (_, None) => Ok(()),
+                    ("date" | "time", Some(format)) if !TEXT_MODIFIERS.contains(&format) => {
+                        check_date_format(format)
+                    }
+                    (_, modifier) => apply_modifier("", modifier).map(|_| ()),
+                };
+                if let Err(problem) = result {
+                    problems.push(format!("{} in filename pattern", problem));
+                }
+            }
+        }
+
+        if self
+            .note_type
+            .as_deref()
+            .is_some_and(|t| t.trim().is_empty())
+        {
+            problems.push("Template setting 'type' is empty".to_string());
+        }
+
+        problems
+    }
+
+    /// The filename stem for a new note, `None` without a pattern
+    ///
+    /// Path separators in values are replaced, so a title can't move the
+    /// note to another directory.
+    pub fn filename(
+        &self,
+        context: &TemplateContext,
+        separator: &str,
+    ) -> TemplateResult<Option<String>> {
+        let Some(pattern) = &self.filename else {
+            return Ok(None);
+        };
+        let has_title = context
+            .get("title")
+            .is_some_and(|title| !title.trim().is_empty());
+
+        let filename_regex = Regex::new(FILENAME_PATTERN).unwrap();
+        let mut problem = None;
+        let stem = filename_regex.replace_all(pattern, |capture: &regex::Captures| {
+            let modifier = capture.get(2).map(|m| m.as_str().trim());
+            let value = match &capture[1] {
+                "sep" if has_title => Ok(Some(separator.to_string())),
+                "sep" => Ok(Some(String::new())),
+                name => context.value(name, modifier),
+            };
+            match value {
+                Ok(value) => value.unwrap_or_default().replace(['/', '\\'], "-"),
+                Err(message) => {
+                    problem.get_or_insert(message);
+                    String::new()
+                }
+            }
+        });
+
+        match problem {
+            Some(message) => Err(TemplateError::ProcessingError(message)),
+            None => Ok(Some(stem.trim().to_string())),
+        }
+    }
+
+    /// Add the declared tags and type to a note's frontmatter
+    ///
+    /// Values the note already has are kept; see `frontmatter::merge_into`.
+    pub fn apply(&self, content: &str) -> TemplateResult<String> {
+        let mut additions = Mapping::new();
+        if !self.tags.is_empty() {
+            let tags = self.tags.iter().map(|tag| Value::from(tag.as_str()));
+            additions.insert("tags".into(), Value::Sequence(tags.collect()));
+        }
+        if let Some(note_type) = &self.note_type {
+            additions.insert("type".into(), note_type.as_str().into());
+        }
+        if additions.is_empty() {
+            return Ok(content.to_string());
+        }
+
+        let error = |message: String| TemplateError::ProcessingError(message);
+        let yaml = serde_yaml::to_string(&additions).map_err(|e| error(e.to_string()))?;
+        frontmatter::merge_into(content, &format!("---\n{}---\n", yaml))
+            .map_err(|e| error(format!("Cannot add template tags and type: {}", e)))
+    }
+}
+
 /// Core template processing service
 ///
 /// This handles all template-related business logic:
@@ -103,11 +998,57 @@ impl TemplateService {
     ///
     /// Business rule: Templates are used when:
     /// 1. Template system is enabled in config
-    /// 2. Template file path is specified and non-empty
+    /// 2. A template file or template directory is specified
     ///
     /// This is a pure function that only examines configuration.
     pub fn should_use_template(config: &TemplateConfig) -> bool {
-        config.enabled && !config.file.trim().is_empty()
+        config.enabled && (!config.file.trim().is_empty() || !config.directory.trim().is_empty())
+    }
+
+    /// The template name the configured rules choose for a new note
+    ///
+    /// Rules are checked in order and the first one whose conditions all
+    /// hold wins. `None` when no rule matches.
+    pub fn select_template<'a>(
+        config: &'a TemplateConfig,
+        id: &Id,
+        note_type: Option<&str>,
+    ) -> Option<&'a str> {
+        config
+            .rules
+            .iter()
+            .find(|rule| Self::rule_matches(rule, id, note_type))
+            .map(|rule| rule.template.trim())
+    }
+
+    /// Whether a note with this ID and type satisfies a rule
+    pub fn rule_matches(rule: &TemplateRule, id: &Id, note_type: Option<&str>) -> bool {
+        let depth = match rule.depth.as_deref() {
+            Some("root") => id.is_root(),
+            Some("child") => !id.is_root(),
+            _ => true,
+        };
+        let under = rule.under.as_deref().is_none_or(|under| {
+            Id::parse(under).is_ok_and(|ancestor| id.is_descendant_of(&ancestor))
+        });
+        let note_type = rule
+            .note_type
+            .as_deref()
+            .is_none_or(|wanted| note_type == Some(wanted));
+        depth && under && note_type
+    }
+
+    /// Path of a named template in a template directory
+    ///
+    /// `.md` is added unless the name already has it.
+    pub fn template_in_directory(directory: &str, name: &str) -> String {
+        let directory = directory.trim().trim_end_matches('/');
+        let name = name.trim();
+        if name.ends_with(".md") {
+            format!("{}/{}", directory, name)
+        } else {
+            format!("{}/{}.md", directory, name)
+        }
     }
 
     /// Validates template content against configuration requirements
@@ -118,10 +1059,16 @@ impl TemplateService {
     /// VALIDATION RULES:
     /// - If require_title is true, {{title}} must be present
     /// - If require_link is true, {{link}} must be present
+    /// - Blocks must be known and balanced (see `Template::parse`)
+    /// - Settings in the frontmatter must be valid (see `TemplateSettings`)
+    /// - Modifiers must be known text modifiers, or valid date formats on
+    ///   {{date}} and {{time}}
     /// - Unknown placeholders are allowed (forward compatibility)
     ///
+    /// Included partials aren't checked, having to be read by the caller.
+    ///
     /// EXAMPLES:
-    /// ```rust
+    /// ```rust,ignore
     /// let config = TemplateConfig { require_title: true, require_link: true, .. };
     /// let content = "# {{title}}\n\nParent: {{link}}";
     /// let result = TemplateService::validate_template(content, &config);
@@ -143,8 +1090,18 @@ impl TemplateService {
         }
 
         // Generate validation result
-        if missing_placeholders.is_empty() {
-            ValidationResult::success(found_placeholders)
+        let settings = TemplateSettings::extract(content)
+            .map(|(settings, _)| settings.check())
+            .unwrap_or_else(|error| vec![error.to_string()]);
+        if let Some(problem) = settings.into_iter().next() {
+            ValidationResult::failure(problem, Vec::new(), found_placeholders)
+        } else if let Err(error) = Template::parse(content) {
+            ValidationResult::failure(error.to_string(), Vec::new(), found_placeholders)
+        } else if missing_placeholders.is_empty() {
+            match Self::check_modifiers(content).into_iter().next() {
+                Some(problem) => ValidationResult::failure(problem, Vec::new(), found_placeholders),
+                None => ValidationResult::success(found_placeholders),
+            }
         } else {
             let message = format!(
                 "Template missing required placeholder(s): {{{{{}}}}}",
@@ -159,22 +1116,24 @@ impl TemplateService {
     /// Finds all instances of {{placeholder_name}} and returns the names.
     /// This is used for validation and debugging.
     ///
-    /// REGEX PATTERN: {{(\w+)}}
+    /// REGEX PATTERN: {{(\w+)(:modifier)?}}
     /// - {{ and }} are literal braces
     /// - (\w+) captures word characters (letters, numbers, underscore)
+    /// - An optional modifier follows a colon and is not part of the name
     ///
     /// EXAMPLES:
     /// - "# {{title}}" -> ["title"]
     /// - "{{title}} and {{link}}" -> ["title", "link"]
-    /// - "{{title}} {{title}}" -> ["title"] (deduplicated)
+    /// - "{{title}} {{title:slug}}" -> ["title"] (deduplicated)
+    /// - "{{#if id}}{{id}}{{else}}-{{/if}}" -> ["id"] (block tags excluded)
     fn extract_placeholders(content: &str) -> Vec<String> {
-        let placeholder_regex = Regex::new(r"\{\{(\w+)\}\}").unwrap();
+        let placeholder_regex = Regex::new(PLACEHOLDER_PATTERN).unwrap();
         let mut placeholders = Vec::new();
 
         for capture in placeholder_regex.captures_iter(content) {
             if let Some(placeholder) = capture.get(1) {
                 let name = placeholder.as_str().to_string();
-                if !placeholders.contains(&name) {
+                if name != "else" && !placeholders.contains(&name) {
                     placeholders.push(name);
                 }
             }
@@ -183,14 +1142,39 @@ impl TemplateService {
         placeholders
     }
 
+    /// Problems with the modifiers of a template's placeholders
+    fn check_modifiers(content: &str) -> Vec<String> {
+        let placeholder_regex = Regex::new(PLACEHOLDER_PATTERN).unwrap();
+        let mut problems = Vec::new();
+
+        for capture in placeholder_regex.captures_iter(content) {
+            let Some(modifier) = capture.get(2).map(|m| m.as_str().trim()) else {
+                continue;
+            };
+            let result = match &capture[1] {
+                "date" | "time" if !TEXT_MODIFIERS.contains(&modifier) => {
+                    check_date_format(modifier)
+                }
+                _ => apply_modifier("", Some(modifier)).map(|_| ()),
+            };
+            if let Err(problem) = result {
+                problems.push(format!("{} in {}", problem, &capture[0]));
+            }
+        }
+
+        problems
+    }
+
     /// Generates final note content using template or built-in format
     ///
     /// This is the core content generation function. It handles both template
     /// mode (with placeholder substitution) and built-in mode (standard format).
     ///
     /// TEMPLATE MODE:
-    /// Replaces all placeholders with provided values. Unknown placeholders
-    /// are left unchanged for forward compatibility.
+    /// Renders blocks and partials (loaded with `partials`) and replaces all
+    /// placeholders with values from the context. Unknown placeholders are
+    /// left unchanged for forward compatibility. Fails on template syntax
+    /// errors and unknown partials.
     ///
     /// BUILT-IN MODE:
     /// Creates standard "# Title\n\nBacklink" format when no template provided.
@@ -202,57 +1186,58 @@ impl TemplateService {
     /// - Multiple occurrences of same placeholder are all replaced
     ///
     /// EXAMPLES:
-    /// ```rust
+    /// ```rust,ignore
+    /// let context = TemplateContext::new("My Note", "[[parent]]");
+    ///
     /// // Template mode
     /// let template = "# {{title}}\n\nParent: {{link}}";
-    /// let content = TemplateService::generate_content(
-    ///     Some(template), "My Note", "[[parent]]"
-    /// );
+    /// let content = TemplateService::generate_content(Some(template), &context, &|_| None)?;
     /// // Result: "# My Note\n\nParent: [[parent]]"
     ///
     /// // Built-in mode
-    /// let content = TemplateService::generate_content(
-    ///     None, "My Note", "[[parent]]"
-    /// );
+    /// let content = TemplateService::generate_content(None, &context, &|_| None)?;
     /// // Result: "# My Note\n\n[[parent]]"
     /// ```
     pub fn generate_content(
         template_content: Option<&str>,
-        title: &str,
-        backlink_content: &str,
-    ) -> String {
+        context: &TemplateContext,
+        partials: &dyn Fn(&str) -> Option<String>,
+    ) -> TemplateResult<String> {
         match template_content {
             Some(template) => {
-                // Template mode: substitute placeholders
-                Self::substitute_placeholders(template, title, backlink_content)
+                // Template mode: render blocks and substitute placeholders
+                Template::parse(template)?.render(context, partials)
             }
             None => {
                 // Built-in mode: standard markdown format
-                Self::generate_builtin_content(title, backlink_content)
+                Ok(Self::generate_builtin_content(
+                    &context.get("title").unwrap_or_default(),
+                    &context.get("link").unwrap_or_default(),
+                ))
             }
         }
     }
 
-    /// Substitutes placeholders in template with actual values
-    ///
-    /// Replaces known placeholders and leaves unknown ones unchanged.
-    /// This enables forward compatibility with future placeholder types.
-    ///
-    /// CURRENT PLACEHOLDERS:
-    /// - {{title}} -> note title
-    /// - {{link}} -> backlink content
+    /// Generate the content of a stub note standing in for a missing parent
     ///
-    /// FUTURE PLACEHOLDERS (examples):
-    /// - {{date}} -> current date
-    /// - {{author}} -> note author
-    /// - {{id}} -> note ID
-    /// - {{parent_id}} -> parent note ID
-    fn substitute_placeholders(template: &str, title: &str, backlink: &str) -> String {
+    /// Stubs have no title of their own, so {{title}} and {{id}} both become
+    /// the stub's ID. Without a configured stub template the built-in
+    /// `DEFAULT_STUB_TEMPLATE` is used. Root stubs have no backlink, so a
+    /// line holding only {{link}} is dropped along with the blank line after it.
+    pub fn generate_stub(template_content: Option<&str>, id: &str, backlink: &str) -> String {
+        let template = template_content.unwrap_or(DEFAULT_STUB_TEMPLATE);
+        let template = if backlink.is_empty() {
+            template
+                .replace("{{link}}\n\n", "")
+                .replace("{{link}}\n", "")
+        } else {
+            template.to_string()
+        };
+
         template
-            .replace("{{title}}", title)
+            .replace("{{id}}", id)
+            .replace("{{title}}", id)
             .replace("{{link}}", backlink)
-        // Note: Unknown placeholders like {{date}} are left unchanged
-        // This allows templates to include future features
     }
 
     /// Generates built-in content format when no template is used
@@ -292,13 +1277,26 @@ impl TemplateService {
     /// Returns the final template file path to read.
     ///
     /// RESOLUTION LOGIC:
-    /// 1. If specific file is configured, use that
-    /// 2. If directory is configured, use default template within directory
-    /// 3. Validate configuration makes sense
+    /// 1. If a template name is given (`--template` or a rule), use it
+    ///    from the template directory
+    /// 2. If specific file is configured, use that
+    /// 3. If directory is configured, use default template within directory
+    /// 4. Validate configuration makes sense
     ///
     /// This is pure logic - actual file reading is handled by CLI layer.
-    pub fn resolve_template_path(config: &TemplateConfig) -> TemplateResult<String> {
-        if !config.file.trim().is_empty() {
+    pub fn resolve_template_path(
+        config: &TemplateConfig,
+        name: Option<&str>,
+    ) -> TemplateResult<String> {
+        if let Some(name) = name {
+            if config.directory.trim().is_empty() {
+                return Err(TemplateError::ConfigError(format!(
+                    "Template '{}' requested but no template directory is configured",
+                    name
+                )));
+            }
+            Ok(Self::template_in_directory(&config.directory, name))
+        } else if !config.file.trim().is_empty() {
             // Direct file path specified
             Ok(config.file.trim().to_string())
         } else if !config.directory.trim().is_empty() {
@@ -310,44 +1308,16 @@ impl TemplateService {
                 ));
             }
 
-            let directory = config.directory.trim();
-            let template_name = config.default_template.trim();
-            Ok(format!("{}/{}", directory, template_name))
+            Ok(Self::template_in_directory(
+                &config.directory,
+                &config.default_template,
+            ))
         } else {
             Err(TemplateError::ConfigError(
                 "No template file or directory specified".to_string(),
             ))
         }
     }
-
-    /// Creates a context map for advanced template processing (future feature)
-    ///
-    /// This prepares for more sophisticated template systems that might use
-    /// templating engines like Handlebars or Tera. Currently returns basic
-    /// key-value pairs for the placeholders we support.
-    ///
-    /// FUTURE ENHANCEMENT:
-    /// Could support conditional logic, loops, includes, etc.
-    ///
-    /// ```handlebars
-    /// # {{title}}
-    /// {{#if parent}}
-    /// Parent: {{parent}}
-    /// {{/if}}
-    /// ```
-    #[allow(dead_code)]
-    pub fn create_template_context(title: &str, backlink: &str) -> HashMap<String, String> {
-        let mut context = HashMap::new();
-        context.insert("title".to_string(), title.to_string());
-        context.insert("link".to_string(), backlink.to_string());
-
-        // Future: Add more context variables
-        // context.insert("date".to_string(), chrono::Utc::now().format("%Y-%m-%d").to_string());
-        // context.insert("author".to_string(), get_author_from_config());
-        // context.insert("id".to_string(), note_id.to_string());
-
-        context
-    }
 }
 
 /// Template manager for handling multiple templates (future feature)
@@ -364,6 +1334,12 @@ pub struct TemplateManager {
     templates: HashMap<String, String>,
 }
 
+impl Default for TemplateManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[allow(dead_code)]
 impl TemplateManager {
     /// Create new template manager
@@ -471,3 +1447,303 @@ impl TemplateManager {
 // # Create note with template
 // zettel note create 1a "My Note" --template academic.md
 // ```
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_partials(_: &str) -> Option<String> {
+        None
+    }
+
+    fn context() -> TemplateContext {
+        let mut context = TemplateContext::new("My Note: Draft!", "[[1a]]");
+        context.set("id", "1a1");
+        context.set("parent_id", "1a");
+        context.set_time(
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 31)
+                .unwrap()
+                .and_hms_opt(14, 5, 0)
+                .unwrap(),
+        );
+        context
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        let template =
+            "# {{title}}\n{{id}} < {{parent_id}} {{link}}\n{{date}} {{time}} {{unknown}}";
+        assert_eq!(
+            TemplateService::generate_content(Some(template), &context(), &no_partials).unwrap(),
+            "# My Note: Draft!\n1a1 < 1a [[1a]]\n2026-01-31 14:05 {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let template = "{{title:slug}} {{ title:upper }} {{date:%d %B %Y}} {{time:%H}}";
+        assert_eq!(
+            TemplateService::generate_content(Some(template), &context(), &no_partials).unwrap(),
+            "my-note-draft MY NOTE: DRAFT! 31 January 2026 14"
+        );
+
+        // Explicit dates are reformatted; bad modifiers are left visible
+        let mut context = context();
+        context.set("date", "2025-12-24");
+        assert_eq!(
+            TemplateService::generate_content(
+                Some("{{date:%b %d}} {{id:bogus}}"),
+                &context,
+                &no_partials
+            )
+            .unwrap(),
+            "Dec 24 {{id:bogus}}"
+        );
+    }
+
+    #[test]
+    fn test_validate_modifiers() {
+        let config = TemplateConfig {
+            require_link: false,
+            ..TemplateConfig::default()
+        };
+        let valid = TemplateService::validate_template("# {{title:slug}} {{date:%Y}}", &config);
+        assert!(valid.valid);
+        assert_eq!(valid.found_placeholders, vec!["title", "date"]);
+
+        let invalid = TemplateService::validate_template("# {{title:shout}}", &config);
+        assert!(!invalid.valid);
+        assert!(invalid.message.unwrap().contains("{{title:shout}}"));
+        assert!(!TemplateService::validate_template("{{title}} {{date:%Q}}", &config).valid);
+    }
+
+    fn ancestor(id: &str, title: &str) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("id".to_string(), id.to_string()),
+            ("title".to_string(), title.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_blocks() {
+        let template = "# {{title}}\n\
+            {{#if parent_id}}\n\
+            Parent: {{parent_id}}\n\
+            {{else}}\n\
+            A root note.\n\
+            {{/if}}\n\
+            {{#each ancestors}}\n\
+            - {{id}} {{title:upper}} (in {{parent_id}})\n\
+            {{else}}\n\
+            No ancestors.\n\
+            {{/each}}\n\
+            {{#if missing}}x{{/if}}end\n";
+
+        let mut context = context();
+        context.set_list(
+            "ancestors",
+            vec![ancestor("1", "Root"), ancestor("1a", "Topic")],
+        );
+        assert_eq!(
+            Template::parse(template)
+                .unwrap()
+                .render(&context, &no_partials)
+                .unwrap(),
+            "# My Note: Draft!\nParent: 1a\n- 1 ROOT (in 1a)\n- 1a TOPIC (in 1a)\nend\n"
+        );
+
+        let mut root = TemplateContext::new("Root", "");
+        root.set("parent_id", "");
+        assert_eq!(
+            Template::parse(template)
+                .unwrap()
+                .render(&root, &no_partials)
+                .unwrap(),
+            "# Root\nA root note.\nNo ancestors.\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_partials() {
+        let partials = |name: &str| match name {
+            "footer" => Some("---\n{{> sign}}\n".to_string()),
+            "sign" => Some("by {{author}}".to_string()),
+            "loop" => Some("{{> loop}}".to_string()),
+            "broken" => Some("ok\n{{#if x}}".to_string()),
+            _ => None,
+        };
+        let mut context = context();
+        context.set("author", "Niklas");
+
+        let template = Template::parse("# {{title}}\n{{> footer}}\n").unwrap();
+        assert_eq!(template.partials(), vec!["footer"]);
+        assert_eq!(
+            template.render(&context, &partials).unwrap(),
+            "# My Note: Draft!\n---\nby Niklas\n"
+        );
+
+        let render = |source: &str| Template::parse(source).unwrap().render(&context, &partials);
+        assert_eq!(
+            render("x\n  {{> nope}}").unwrap_err(),
+            TemplateError::SyntaxError {
+                line: 2,
+                column: 3,
+                message: "Unknown partial 'nope'".to_string()
+            }
+        );
+        assert!(render("{{> loop}}").is_err());
+        assert_eq!(
+            render("{{> broken}}").unwrap_err().to_string(),
+            "In partial 'broken': Template syntax error at line 2, column 1: \
+             {{#if x}} is never closed with {{/if}}"
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let position = |source: &str| match Template::parse(source) {
+            Err(TemplateError::SyntaxError { line, column, .. }) => (line, column),
+            other => panic!("expected a syntax error, got {:?}", other),
+        };
+        assert_eq!(position("a\n{{#if x}}\nb"), (2, 1));
+        assert_eq!(position("{{#if x}}\n  {{/each}}"), (2, 3));
+        assert_eq!(position("ok {{/if}}"), (1, 4));
+        assert_eq!(position("{{else}}"), (1, 1));
+        assert_eq!(position("{{#if x}}{{else}}{{else}}{{/if}}"), (1, 18));
+        assert_eq!(position("\n\n  {{#with x}}{{/with}}"), (3, 3));
+        assert_eq!(position("{{#if}}{{/if}}"), (1, 1));
+
+        let config = TemplateConfig::default();
+        let invalid = TemplateService::validate_template("{{title}} {{link}} {{#if x}}", &config);
+        assert!(!invalid.valid);
+        assert!(invalid.message.unwrap().contains("line 1, column 20"));
+        let valid = TemplateService::validate_template(
+            "{{title}}{{#if id}}{{link}}{{else}}{{/if}}",
+            &config,
+        );
+        assert_eq!(valid.found_placeholders, vec!["title", "link"]);
+    }
+
+    #[test]
+    fn test_select_template() {
+        let rule = |template: &str, depth: Option<&str>, under: Option<&str>| TemplateRule {
+            template: template.to_string(),
+            depth: depth.map(str::to_string),
+            under: under.map(str::to_string),
+            note_type: None,
+        };
+        let config = TemplateConfig {
+            directory: "templates".to_string(),
+            rules: vec![
+                TemplateRule {
+                    note_type: Some("meeting".to_string()),
+                    ..rule("meeting", None, None)
+                },
+                rule("paper", Some("child"), Some("3")),
+                rule("topic", Some("root"), None),
+            ],
+            ..TemplateConfig::default()
+        };
+        let select = |id: &str, note_type: Option<&str>| {
+            TemplateService::select_template(&config, &Id::parse(id).unwrap(), note_type)
+        };
+
+        assert_eq!(select("3", None), Some("topic"));
+        assert_eq!(select("3a1", None), Some("paper"));
+        assert_eq!(select("3a1", Some("meeting")), Some("meeting"));
+        assert_eq!(select("2a", None), None);
+
+        assert_eq!(
+            TemplateService::resolve_template_path(&config, Some("paper")).unwrap(),
+            "templates/paper.md"
+        );
+        assert_eq!(
+            TemplateService::resolve_template_path(&config, None).unwrap(),
+            "templates/default.md"
+        );
+        let single = TemplateConfig {
+            file: "note.md".to_string(),
+            ..TemplateConfig::default()
+        };
+        assert_eq!(
+            TemplateService::resolve_template_path(&single, None).unwrap(),
+            "note.md"
+        );
+        assert!(TemplateService::resolve_template_path(&single, Some("paper")).is_err());
+    }
+
+    #[test]
+    fn test_settings() {
+        let template = "---\n\
+            zettel:\n  directory: literature\n  filename: \"{id}{sep}{title:slug}\"\n\n  tags: [source, draft]\n  type: paper\n\
+            created: {{date}}\n\
+            ---\n# {{title}}\n";
+        let (settings, rest) = TemplateSettings::extract(template).unwrap();
+        assert_eq!(rest, "---\ncreated: {{date}}\n---\n# {{title}}\n");
+        assert_eq!(settings.directory.as_deref(), Some("literature"));
+        assert_eq!(settings.tags, vec!["source", "draft"]);
+        assert_eq!(settings.note_type.as_deref(), Some("paper"));
+        assert!(settings.check().is_empty());
+
+        let context = context();
+        assert_eq!(
+            settings.filename(&context, " - ").unwrap().as_deref(),
+            Some("1a1 - my-note-draft")
+        );
+        let mut untitled = TemplateContext::new("", "");
+        untitled.set("id", "2");
+        assert_eq!(
+            settings.filename(&untitled, " - ").unwrap().as_deref(),
+            Some("2")
+        );
+
+        let applied = settings.apply("---\ntags: [mine]\n---\n# Note\n").unwrap();
+        let map = frontmatter::parse(frontmatter::split(&applied).0.unwrap()).unwrap();
+        assert_eq!(
+            map.get("tags"),
+            Some(&serde_yaml::from_str::<Value>("[mine, source, draft]").unwrap())
+        );
+        assert_eq!(map.get("type"), Some(&Value::from("paper")));
+
+        // Templates without settings are returned unchanged
+        let plain = "---\ntags: [a]\n---\n# {{title}}\n";
+        assert_eq!(
+            TemplateSettings::extract(plain).unwrap(),
+            (TemplateSettings::default(), plain.to_string())
+        );
+        let (_, rest) = TemplateSettings::extract("---\nzettel:\n  type: x\n---\nBody\n").unwrap();
+        assert_eq!(rest, "Body\n");
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let config = TemplateConfig::default();
+        let validate = |settings: &str| {
+            let template = format!(
+                "---\nzettel:\n{}---\n{{{{title}}}} {{{{link}}}}\n",
+                settings
+            );
+            TemplateService::validate_template(&template, &config)
+        };
+
+        assert!(validate("  filename: \"{id} {date:%Y} {title:upper}\"\n").valid);
+        for invalid in [
+            "  filename: \"{title}-{id}\"\n",
+            "  filename: \"{id}/{title}\"\n",
+            "  filename: \"{id}{author}\"\n",
+            "  filename: \"{id}{title:shout}\"\n",
+            "  directory: ../outside\n",
+            "  folder: notes\n",
+            "  tags: 3\n",
+        ] {
+            let result = validate(invalid);
+            assert!(!result.valid, "{} should be invalid", invalid);
+        }
+        assert!(
+            validate("  colour: red\n")
+                .message
+                .unwrap()
+                .contains("Unknown template setting 'colour'")
+        );
+    }
+}
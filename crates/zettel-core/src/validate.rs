@@ -0,0 +1,361 @@
+// crates/zettel-core/src/validate.rs - Vault Validation
+//
+// This module describes problems found in a vault and implements the checks
+// that only need already-loaded data. The CLI reads files, resolves links
+// against the filesystem and reports the combined issue list (`zettel doctor`).
+//
+// DESIGN PRINCIPLES:
+// - Pure functions: No I/O, callers pass notes, content and resolvers
+// - One issue per problem: Each finding names its file and, where there is
+//   one, the command or edit that fixes it
+// - Severity reflects impact: Errors break zettel commands, warnings leave
+//   the vault inconsistent, info marks conventions that aren't followed
+//
+// CHECKS:
+// - Duplicate IDs across files
+// - Filenames that look like IDs but don't parse, or use uppercase letters
+// - Broken links
+// - Missing parents in ancestor chains
+// - Parent notes that don't link to their children
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::id::Id;
+use crate::link::LinkService;
+use crate::note::{Note, NoteTree};
+
+/// A problem found during vault validation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub severity: IssueSeverity,
+    pub category: IssueCategory,
+    pub description: String,
+    pub file: Option<PathBuf>,
+    pub suggestion: Option<String>,
+}
+
+/// How serious an issue is; sorts with errors first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// Breaks zettel commands
+    Error,
+    /// Potential problem
+    Warning,
+    /// Suggestion for improvement
+    Info,
+}
+
+/// What kind of problem an issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCategory {
+    DuplicateId,
+    InvalidId,
+    UppercaseId,
+    BrokenLink,
+    MissingParent,
+    MissingChildLink,
+    InvalidTemplate,
+    ConfigurationIssue,
+    UnreadableFile,
+}
+
+impl ValidationIssue {
+    /// Create an issue without file or suggestion
+    pub fn new<S: Into<String>>(
+        severity: IssueSeverity,
+        category: IssueCategory,
+        description: S,
+    ) -> Self {
+        Self {
+            severity,
+            category,
+            description: description.into(),
+            file: None,
+            suggestion: None,
+        }
+    }
+
+    /// Attach the file the issue was found in
+    pub fn with_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+
+    /// Attach a suggested fix
+    pub fn with_suggestion<S: Into<String>>(mut self, suggestion: S) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+impl std::fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueSeverity::Error => write!(f, "error"),
+            IssueSeverity::Warning => write!(f, "warning"),
+            IssueSeverity::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// Displays the same snake_case names used in JSON output
+impl std::fmt::Display for IssueCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            IssueCategory::DuplicateId => "duplicate_id",
+            IssueCategory::InvalidId => "invalid_id",
+            IssueCategory::UppercaseId => "uppercase_id",
+            IssueCategory::BrokenLink => "broken_link",
+            IssueCategory::MissingParent => "missing_parent",
+            IssueCategory::MissingChildLink => "missing_child_link",
+            IssueCategory::InvalidTemplate => "invalid_template",
+            IssueCategory::ConfigurationIssue => "configuration_issue",
+            IssueCategory::UnreadableFile => "unreadable_file",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Report every file whose ID is also used by an earlier file
+pub fn check_duplicate_ids(tree: &NoteTree) -> Vec<ValidationIssue> {
+    let mut by_id: BTreeMap<&Id, Vec<&Note>> = BTreeMap::new();
+    for note in tree.notes() {
+        by_id.entry(&note.id).or_default().push(note);
+    }
+
+    by_id
+        .values()
+        .filter(|notes| notes.len() > 1)
+        .flat_map(|notes| {
+            let first = notes[0];
+            notes[1..].iter().map(move |dup| {
+                ValidationIssue::new(
+                    IssueSeverity::Error,
+                    IssueCategory::DuplicateId,
+                    format!(
+                        "ID {} is also used by {}",
+                        dup.id,
+                        first.path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                )
+                .with_file(&dup.path)
+                .with_suggestion(format!("Run `zettel id dedupe {}`", dup.id))
+            })
+        })
+        .collect()
+}
+
+/// Check a filename's leading ID-like token
+///
+/// `id` is the ID the configured matching rule extracted, if any. Tokens
+/// with uppercase letters are reported even when a prefix of them matched
+/// (the regex stops at "1" in "1A2"); tokens that don't parse are reported
+/// for files that weren't recognised as notes.
+pub fn check_filename(path: &Path, id: Option<&Id>) -> Option<ValidationIssue> {
+    let stem = path.file_stem()?.to_str()?;
+    let token_len = stem
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(stem.len());
+    let token = &stem[..token_len];
+    if !token.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    if token.chars().any(|c| c.is_ascii_uppercase()) {
+        let lower = token.to_ascii_lowercase();
+        let issue = ValidationIssue::new(
+            IssueSeverity::Warning,
+            IssueCategory::UppercaseId,
+            format!("ID-like prefix {} uses uppercase letters", token),
+        )
+        .with_file(path);
+        return Some(match Id::parse(&lower) {
+            Ok(_) => issue.with_suggestion(format!("Rename to {}{}", lower, &stem[token_len..])),
+            Err(_) => issue,
+        });
+    }
+
+    if id.is_none()
+        && let Err(e) = Id::parse(token)
+    {
+        return Some(
+            ValidationIssue::new(
+                IssueSeverity::Warning,
+                IssueCategory::InvalidId,
+                format!("Filename looks like an ID but {} is invalid: {}", token, e),
+            )
+            .with_file(path)
+            .with_suggestion("Rename the file to a valid ID or drop the leading digits"),
+        );
+    }
+
+    None
+}
+
+/// Report links in `content` whose target `resolves` doesn't accept
+///
+/// Each broken target is reported once per note.
+pub fn check_links(
+    note: &Note,
+    content: &str,
+    resolves: impl Fn(&str) -> bool,
+) -> Vec<ValidationIssue> {
+    let mut broken: Vec<String> = Vec::new();
+    for link in LinkService::find_links(content) {
+        if !resolves(&link.target) && !broken.contains(&link.target) {
+            broken.push(link.target);
+        }
+    }
+
+    broken
+        .into_iter()
+        .map(|target| {
+            ValidationIssue::new(
+                IssueSeverity::Warning,
+                IssueCategory::BrokenLink,
+                format!("Link to missing note or file: {}", target),
+            )
+            .with_file(&note.path)
+            .with_suggestion("Fix the link target or remove the link")
+        })
+        .collect()
+}
+
+/// Report each ID missing from the ancestor chain of existing notes
+pub fn check_missing_parents(tree: &NoteTree) -> Vec<ValidationIssue> {
+    tree.missing_ancestors()
+        .into_iter()
+        .map(|id| {
+            let below: Vec<String> = tree
+                .notes()
+                .iter()
+                .filter(|note| note.id.is_descendant_of(&id))
+                .map(|note| note.id.to_string())
+                .collect();
+            ValidationIssue::new(
+                IssueSeverity::Warning,
+                IssueCategory::MissingParent,
+                format!("Missing note {} (above {})", id, below.join(", ")),
+            )
+            .with_suggestion("Run `zettel note create-stubs`")
+        })
+        .collect()
+}
+
+/// Report parents that don't link to their existing children
+///
+/// `links_to(parent, child)` tells whether the parent's content links to the child.
+pub fn check_child_links(
+    tree: &NoteTree,
+    links_to: impl Fn(&Note, &Note) -> bool,
+) -> Vec<ValidationIssue> {
+    tree.notes()
+        .iter()
+        .filter_map(|child| {
+            let parent = tree.parent(&child.id)?;
+            if links_to(parent, child) {
+                return None;
+            }
+            Some(
+                ValidationIssue::new(
+                    IssueSeverity::Info,
+                    IssueCategory::MissingChildLink,
+                    format!(
+                        "Parent {} doesn't link to its child {}",
+                        parent.id, child.id
+                    ),
+                )
+                .with_file(&parent.path)
+                .with_suggestion(format!("Run `zettel note link {} {}`", parent.id, child.id)),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, file: &str) -> Note {
+        Note::new(Id::parse(id).unwrap(), PathBuf::from(file), None)
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let tree = NoteTree::new(vec![
+            note("1", "1 - One.md"),
+            note("1", "1 - Again.md"),
+            note("2", "2.md"),
+        ]);
+        let issues = check_duplicate_ids(&tree);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].category, IssueCategory::DuplicateId);
+        assert_eq!(issues[0].file, Some(PathBuf::from("1 - Again.md")));
+        assert!(issues[0].description.contains("1 - One.md"));
+    }
+
+    #[test]
+    fn test_check_filename() {
+        let ok = Id::parse("1a2").unwrap();
+        assert_eq!(check_filename(Path::new("1a2 - Note.md"), Some(&ok)), None);
+        assert_eq!(check_filename(Path::new("Readme.md"), None), None);
+
+        let upper = check_filename(Path::new("1A2 - Note.md"), None).unwrap();
+        assert_eq!(upper.category, IssueCategory::UppercaseId);
+        assert_eq!(upper.suggestion.as_deref(), Some("Rename to 1a2 - Note"));
+
+        let invalid = check_filename(Path::new("99999999999a - Note.md"), None).unwrap();
+        assert_eq!(invalid.category, IssueCategory::InvalidId);
+    }
+
+    #[test]
+    fn test_check_links() {
+        let source = note("1", "1.md");
+        let content = "[[2]] [[missing]] and [[missing|again]]";
+        let issues = check_links(&source, content, |target| target == "2");
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].description.ends_with("missing"));
+    }
+
+    #[test]
+    fn test_missing_parents_and_child_links() {
+        let tree = NoteTree::new(vec![
+            note("1", "1.md"),
+            note("1a", "1a.md"),
+            note("1b", "1b.md"),
+            note("1c2", "1c2.md"),
+        ]);
+
+        let missing = check_missing_parents(&tree);
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].description.contains("1c (above 1c2)"));
+
+        let unlinked = check_child_links(&tree, |_, child| child.id.to_string() == "1a");
+        assert_eq!(unlinked.len(), 1);
+        assert!(unlinked[0].description.contains("child 1b"));
+    }
+
+    #[test]
+    fn test_severity_order() {
+        let mut severities = vec![
+            IssueSeverity::Info,
+            IssueSeverity::Error,
+            IssueSeverity::Warning,
+        ];
+        severities.sort();
+        assert_eq!(
+            severities,
+            vec![
+                IssueSeverity::Error,
+                IssueSeverity::Warning,
+                IssueSeverity::Info
+            ]
+        );
+    }
+}